@@ -19,15 +19,18 @@ struct Cli {
 
 fn main() -> DocxResult<()> {
     let cli = Cli::parse();
+    // documents generated by poi-tl leave out the required `w:nsid` of numberings
     let fdoc = DocxFile::from_file(&cli.doc1).unwrap();
-    // merge.exe --doc1 E:\temp\report_datas\docx_merge\doc1.docx --doc2 E:\temp\report_datas\docx_merge\doc2.docx
-    // when parse docx generated by poi-tl,got the following error: 
-    // thread 'main' panicked at examples\merge.rs:22:33:
-    // called `Result::unwrap()` on an `Err` value: Xml(MissingField { name: "AbstractNum", field: "nsid" })
-    let mut docx = fdoc.parse().unwrap();
+    let (mut docx, warnings) = fdoc.parse_lenient().unwrap();
+    for warning in warnings {
+        eprintln!("{}: {}", cli.doc1.display(), warning);
+    }
 
     let fdoc2 = DocxFile::from_file(&cli.doc2).unwrap();
-    let docx2 = fdoc2.parse().unwrap();
+    let (docx2, warnings) = fdoc2.parse_lenient().unwrap();
+    for warning in warnings {
+        eprintln!("{}: {}", cli.doc2.display(), warning);
+    }
     for content in docx2.document.body.content {
         docx.document.push(content);
     }
@@ -1,12 +1,23 @@
 //! Application-Defined File Properties part
 //!
 //! The corresponding ZIP item is `/docProps/app.xml`.
+//!
+//! The statistics it holds can be recomputed with [`App::set_statistics`], or
+//! on every write with [`Docx::refresh_statistics`].
+//!
+//! [`Docx::refresh_statistics`]: ../struct.Docx.html#structfield.refresh_statistics
 
 use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
 use crate::schema::{SCHEMAS_EXTENDED, SCHEMA_DOC_PROPS_V_TYPES, SCHEMA_XML};
+use crate::statistics::Statistics;
+
+/// Name of the application written in the extended properties
+pub const APPLICATION: &str = "docx-rs";
+/// Version of the application written in the extended properties, as `XX.YYYY`
+pub const APP_VERSION: &str = "12.0000";
 
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 pub enum App<'a> {
@@ -16,6 +27,69 @@ pub enum App<'a> {
     AppWithApNamespace(AppWithApNamespace<'a>),
 }
 
+impl Default for App<'_> {
+    fn default() -> Self {
+        App::AppNoApNamespace(AppNoApNamespace::default())
+    }
+}
+
+macro_rules! __app_field {
+    ($app:expr, $field:ident) => {
+        match $app {
+            App::AppNoApNamespace(a) => &a.$field,
+            App::AppWithApNamespace(a) => &a.$field,
+        }
+    };
+    (mut $app:expr, $field:ident) => {
+        match $app {
+            App::AppNoApNamespace(a) => &mut a.$field,
+            App::AppWithApNamespace(a) => &mut a.$field,
+        }
+    };
+}
+
+macro_rules! __app_text {
+    ($($field:ident, $setter:ident;)*) => {
+        $(
+            pub fn $field(&self) -> Option<&str> {
+                __app_field!(self, $field).as_deref()
+            }
+
+            pub fn $setter<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+                *__app_field!(mut self, $field) = Some(value.into());
+                self
+            }
+        )*
+    };
+}
+
+impl<'a> App<'a> {
+    __app_text! {
+        template, set_template;
+        pages, set_pages;
+        words, set_words;
+        characters, set_characters;
+        application, set_application;
+        lines, set_lines;
+        paragraphs, set_paragraphs;
+        company, set_company;
+        characters_with_spaces, set_characters_with_spaces;
+        app_version, set_app_version;
+    }
+
+    /// Replaces the statistics, and marks this crate as the application that wrote them.
+    pub fn set_statistics(&mut self, statistics: &Statistics) -> &mut Self {
+        self.set_pages(statistics.pages.to_string())
+            .set_words(statistics.words.to_string())
+            .set_characters(statistics.characters.to_string())
+            .set_characters_with_spaces(statistics.characters_with_spaces.to_string())
+            .set_lines(statistics.lines.to_string())
+            .set_paragraphs(statistics.paragraphs.to_string())
+            .set_application(APPLICATION)
+            .set_app_version(APP_VERSION)
+    }
+}
+
 #[derive(Debug, XmlRead, Clone)]
 #[xml(tag = "Properties")]
 pub struct AppNoApNamespace<'a> {
@@ -98,7 +172,7 @@ impl Default for AppNoApNamespace<'static> {
             pages: Some("1".into()),
             words: Some("0".into()),
             characters: Some("0".into()),
-            application: Some("docx-rs".into()),
+            application: Some(APPLICATION.into()),
             doc_security: Some("0".into()),
             lines: Some("0".into()),
             paragraphs: Some("1".into()),
@@ -108,7 +182,7 @@ impl Default for AppNoApNamespace<'static> {
             characters_with_spaces: Some("25".into()),
             shared_doc: Some("false".into()),
             hyperlinks_changed: Some("false".into()),
-            app_version: Some("12.0000".into()),
+            app_version: Some(APP_VERSION.into()),
         }
     }
 }
@@ -121,7 +195,7 @@ impl Default for AppWithApNamespace<'static> {
             pages: Some("1".into()),
             words: Some("0".into()),
             characters: Some("0".into()),
-            application: Some("docx-rs".into()),
+            application: Some(APPLICATION.into()),
             doc_security: Some("0".into()),
             lines: Some("0".into()),
             paragraphs: Some("1".into()),
@@ -131,7 +205,7 @@ impl Default for AppWithApNamespace<'static> {
             characters_with_spaces: Some("25".into()),
             shared_doc: Some("false".into()),
             hyperlinks_changed: Some("false".into()),
-            app_version: Some("12.0000".into()),
+            app_version: Some(APP_VERSION.into()),
         }
     }
 }
@@ -0,0 +1,644 @@
+//! Bookmarks
+//!
+//! A bookmark is a named range of the document, marked by a `w:bookmarkStart` and a
+//! `w:bookmarkEnd` sharing the same id. It can span several paragraphs, or a
+//! rectangle of table cells when its start has `w:colFirst` and `w:colLast`.
+//! [`Docx::bookmarks`] lists the bookmarks of the body, and the range they cover in
+//! [`Body::content`]. Templates often mark the regions to fill in with bookmarks,
+//! which [`Docx::replace_bookmark_content`] fills.
+//!
+//! ```rust
+//! use docx_rust::{document::*, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document
+//!     .push(Paragraph::default().push_text("Dear customer,"))
+//!     .push(Paragraph::default().push_text("See the terms."));
+//! docx.add_bookmark(0..1, "Greeting").unwrap();
+//!
+//! docx.replace_bookmark_content("Greeting", [Run::default().push_text("Dear Alice,")])
+//!     .unwrap();
+//! assert_eq!(docx.bookmark("Greeting").unwrap().text, "Dear Alice,");
+//!
+//! let link = Hyperlink::to_bookmark("Greeting", "the greeting");
+//! let reference = docx.bookmark_reference("Greeting").unwrap();
+//! docx.document.push(Paragraph::default().push(link).push(reference));
+//! ```
+//!
+//! [`Docx::bookmarks`]: ../struct.Docx.html#method.bookmarks
+//! [`Docx::replace_bookmark_content`]: ../struct.Docx.html#method.replace_bookmark_content
+//! [`Body::content`]: ../document/struct.Body.html#structfield.content
+
+use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
+
+use crate::{
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, Paragraph, ParagraphContent, PermEnd, PermStart,
+        Run, SimpleField, Table, TableCell, TableCellContent, TableRowContent,
+    },
+    field, Docx, DocxError, DocxResult,
+};
+
+/// Longest bookmark name Word accepts
+const MAX_NAME_LENGTH: usize = 40;
+
+/// A bookmark of the body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub id: String,
+    pub name: String,
+    /// Index of the block of [`Body::content`] the bookmark starts in
+    ///
+    /// [`Body::content`]: ../document/struct.Body.html#structfield.content
+    pub start_block: usize,
+    /// Index of the block the bookmark ends in, if it ends
+    pub end_block: Option<usize>,
+    /// Columns of the table cells the bookmark covers
+    pub columns: Option<RangeInclusive<usize>>,
+    /// Text between the start and the end
+    pub text: String,
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the bookmarks of the body, in the order they start.
+    pub fn bookmarks(&self) -> Vec<Bookmark> {
+        let content = &self.document.body.content;
+        let texts = field::bookmarks(content, false);
+        let mut bookmarks: Vec<Bookmark> = Vec::new();
+        // indices in `bookmarks` by id
+        let mut open = HashMap::new();
+        for (i, block) in content.iter().enumerate() {
+            markers(block, &mut |marker| match marker {
+                Marker::Start(start) => {
+                    let (Some(id), Some(name)) = (&start.id, &start.name) else {
+                        return;
+                    };
+                    open.insert(id.to_string(), bookmarks.len());
+                    bookmarks.push(Bookmark {
+                        id: id.to_string(),
+                        name: name.to_string(),
+                        start_block: i,
+                        end_block: None,
+                        columns: start.col_first.zip(start.col_last).map(|(f, l)| f..=l),
+                        text: texts
+                            .get(name.as_ref())
+                            .map(|(text, _)| text.clone())
+                            .unwrap_or_default(),
+                    });
+                }
+                Marker::End(end) => {
+                    if let Some(index) = end.id.as_ref().and_then(|id| open.remove(id.as_ref())) {
+                        bookmarks[index].end_block = Some(i);
+                    }
+                }
+                Marker::PermStart(_) | Marker::PermEnd(_) => {}
+            });
+        }
+        // the text of table cells in between goes beyond the columns
+        for bookmark in &mut bookmarks {
+            if let (Some(columns), Some(BodyContent::Table(t))) =
+                (&bookmark.columns, content.get(bookmark.start_block))
+            {
+                if let Some(text) = column_text(t, &bookmark.id, columns) {
+                    bookmark.text = text;
+                }
+            }
+        }
+        bookmarks
+    }
+
+    /// Returns the bookmark of the body with this name.
+    pub fn bookmark(&self, name: &str) -> Option<Bookmark> {
+        self.bookmarks().into_iter().find(|b| b.name == name)
+    }
+
+    /// Returns an id not used by any bookmark of the document, whether in the body,
+    /// headers, footers, notes or comments, since ids are shared by all of them.
+    pub fn next_bookmark_id(&self) -> String {
+        let mut next = 0;
+        let mut record = |start: &BookmarkStart| {
+            if let Some(id) = start.id.as_ref().and_then(|id| id.parse::<usize>().ok()) {
+                next = next.max(id + 1);
+            }
+        };
+        let blocks = self
+            .document
+            .body
+            .content
+            .iter()
+            .chain(self.headers.values().flat_map(|h| h.content.iter()))
+            .chain(self.footers.values().flat_map(|f| f.content.iter()))
+            .chain(
+                self.footnotes
+                    .iter()
+                    .flat_map(|n| &n.content)
+                    .flat_map(|n| &n.content),
+            )
+            .chain(
+                self.endnotes
+                    .iter()
+                    .flat_map(|n| &n.content)
+                    .flat_map(|n| &n.content),
+            );
+        for block in blocks {
+            markers(block, &mut |marker| {
+                if let Marker::Start(start) = marker {
+                    record(start);
+                }
+            });
+        }
+        let comments = self
+            .comments
+            .iter()
+            .flat_map(|c| &c.comments)
+            .flat_map(|c| &c.content.content);
+        for c in comments {
+            if let ParagraphContent::BookmarkStart(start) = c {
+                record(start);
+            }
+        }
+        next.to_string()
+    }
+
+    /// Adds a bookmark covering some blocks of the body, and returns its id.
+    ///
+    /// The markers go inside the first and last blocks when they are paragraphs,
+    /// and around them otherwise.
+    pub fn add_bookmark(&mut self, blocks: Range<usize>, name: &str) -> DocxResult<String> {
+        self.check_bookmark_name(name)?;
+        if blocks.is_empty() || blocks.end > self.document.body.content.len() {
+            return Err(DocxError::Bookmark(format!(
+                "{:?} is not a range of body blocks",
+                blocks
+            )));
+        }
+        let id = self.next_bookmark_id();
+        let start = BookmarkStart::default()
+            .id(id.clone())
+            .name(name.to_string());
+        let end = BookmarkEnd::default().id(id.clone());
+
+        wrap_blocks(&mut self.document.body.content, blocks, start, end);
+        Ok(id)
+    }
+
+    /// Adds a bookmark covering a rectangle of cells of the table at `table` in the
+    /// body, and returns its id.
+    pub fn add_cell_bookmark(
+        &mut self,
+        table: usize,
+        rows: Range<usize>,
+        columns: RangeInclusive<usize>,
+        name: &str,
+    ) -> DocxResult<String> {
+        self.check_bookmark_name(name)?;
+        let id = self.next_bookmark_id();
+        let missing = || {
+            DocxError::Bookmark(format!(
+                "block {} has no cells in rows {:?} and columns {:?}",
+                table, rows, columns
+            ))
+        };
+        let Some(BodyContent::Table(t)) = self.document.body.content.get_mut(table) else {
+            return Err(missing());
+        };
+        if rows.is_empty()
+            || columns.is_empty()
+            || cell(t, rows.start, *columns.start()).is_none()
+            || cell(t, rows.end - 1, *columns.end()).is_none()
+        {
+            return Err(missing());
+        }
+
+        let end = BookmarkEnd::default().id(id.clone());
+        if let Some(last) = cell(t, rows.end - 1, *columns.end()) {
+            cell_paragraph(last, false).content.push(end.into());
+        }
+        let start = BookmarkStart::default()
+            .id(id.clone())
+            .name(name.to_string())
+            .col_first(*columns.start())
+            .col_last(*columns.end());
+        if let Some(first) = cell(t, rows.start, *columns.start()) {
+            cell_paragraph(first, true).content.insert(0, start.into());
+        }
+        Ok(id)
+    }
+
+    /// Inserts content right after the start of a bookmark, in a new paragraph if the
+    /// start is between blocks. Returns `false` if there is no such bookmark.
+    pub fn insert_content_at_bookmark<T, I>(&mut self, name: &str, content: I) -> bool
+    where
+        T: Into<ParagraphContent<'a>>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut content = Some(content.into_iter().map(Into::into).collect());
+        insert_at(&mut self.document.body.content, name, &mut content)
+    }
+
+    /// Replaces the content between the start and the end of a bookmark, keeping the
+    /// bookmark around the new content. Returns `false` if there is no such bookmark.
+    ///
+    /// Paragraphs the bookmark spans are merged into one. Fails if the bookmark
+    /// doesn't end in the table cell or content control it starts in.
+    pub fn replace_bookmark_content<T, I>(&mut self, name: &str, content: I) -> DocxResult<bool>
+    where
+        T: Into<ParagraphContent<'a>>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut content = Some(content.into_iter().map(Into::into).collect());
+        replace_in(&mut self.document.body.content, name, &mut content)
+    }
+
+    /// Returns a `REF` field showing the text of a bookmark, which links to it when
+    /// clicked.
+    pub fn bookmark_reference(&self, name: &str) -> Option<SimpleField<'a>> {
+        let bookmark = self.bookmark(name)?;
+        Some(
+            SimpleField::default()
+                .instruction(format!(" REF {} \\h ", name))
+                .push(Run::default().push_text(bookmark.text)),
+        )
+    }
+
+    fn check_bookmark_name(&self, name: &str) -> DocxResult<()> {
+        let valid = name.chars().next().is_some_and(char::is_alphabetic)
+            && name.chars().count() <= MAX_NAME_LENGTH
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(DocxError::Bookmark(format!(
+                "{:?} must start with a letter, and have up to {} letters, digits or underscores",
+                name, MAX_NAME_LENGTH
+            )));
+        }
+        if self.bookmarks().iter().any(|b| b.name == name) {
+            return Err(DocxError::Bookmark(format!("{:?} already exists", name)));
+        }
+        Ok(())
+    }
+}
+
+/// Start or end of a range of the document
+pub(crate) enum Marker<'b, 'a> {
+    Start(&'b BookmarkStart<'a>),
+    End(&'b BookmarkEnd<'a>),
+    PermStart(&'b PermStart<'a>),
+    PermEnd(&'b PermEnd<'a>),
+}
+
+/// Calls `f` with the bookmark and permission markers of a block, in document order.
+pub(crate) fn markers<'b, 'a, F: FnMut(Marker<'b, 'a>)>(content: &'b BodyContent<'a>, f: &mut F) {
+    fn paragraph<'b, 'a, F: FnMut(Marker<'b, 'a>)>(p: &'b Paragraph<'a>, f: &mut F) {
+        for c in &p.content {
+            match c {
+                ParagraphContent::BookmarkStart(start) => f(Marker::Start(start)),
+                ParagraphContent::BookmarkEnd(end) => f(Marker::End(end)),
+                ParagraphContent::PermStart(start) => f(Marker::PermStart(start)),
+                ParagraphContent::PermEnd(end) => f(Marker::PermEnd(end)),
+                _ => {}
+            }
+        }
+    }
+
+    fn cell<'b, 'a, F: FnMut(Marker<'b, 'a>)>(cell: &'b TableCell<'a>, f: &mut F) {
+        for TableCellContent::Paragraph(p) in &cell.content {
+            paragraph(p, f);
+        }
+    }
+
+    match content {
+        BodyContent::Paragraph(p) => paragraph(p, f),
+        BodyContent::Table(t) => {
+            for row in &t.rows {
+                for c in &row.cells {
+                    match c {
+                        TableRowContent::TableCell(c) => cell(c, f),
+                        TableRowContent::SDT(sdt) => {
+                            for c in sdt.content.iter().flat_map(|c| &c.content) {
+                                markers(c, f);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        BodyContent::Sdt(sdt) => {
+            for c in sdt.content.iter().flat_map(|c| &c.content) {
+                markers(c, f);
+            }
+        }
+        BodyContent::TableCell(c) => cell(c, f),
+        BodyContent::BookmarkStart(start) => f(Marker::Start(start)),
+        BodyContent::BookmarkEnd(end) => f(Marker::End(end)),
+        BodyContent::PermStart(start) => f(Marker::PermStart(start)),
+        BodyContent::PermEnd(end) => f(Marker::PermEnd(end)),
+        BodyContent::SectionProperty(_) | BodyContent::Run(_) => {}
+    }
+}
+
+/// Puts markers around some blocks, inside the first and last ones when they are
+/// paragraphs.
+pub(crate) fn wrap_blocks<'a, S, E>(
+    content: &mut Vec<BodyContent<'a>>,
+    blocks: Range<usize>,
+    start: S,
+    end: E,
+) where
+    S: Into<ParagraphContent<'a>> + Into<BodyContent<'a>>,
+    E: Into<ParagraphContent<'a>> + Into<BodyContent<'a>>,
+{
+    match &mut content[blocks.end - 1] {
+        BodyContent::Paragraph(p) => p.content.push(end.into()),
+        _ => content.insert(blocks.end, end.into()),
+    }
+    match &mut content[blocks.start] {
+        BodyContent::Paragraph(p) => p.content.insert(0, start.into()),
+        _ => content.insert(blocks.start, start.into()),
+    }
+}
+
+fn is_named(start: &BookmarkStart, name: &str) -> bool {
+    start.name.as_deref() == Some(name)
+}
+
+fn is_start(content: &ParagraphContent, name: &str) -> bool {
+    matches!(content, ParagraphContent::BookmarkStart(start) if is_named(start, name))
+}
+
+fn is_end(content: &ParagraphContent, id: &str) -> bool {
+    matches!(content, ParagraphContent::BookmarkEnd(end) if end.id.as_deref() == Some(id))
+}
+
+/// Joins the text of the cells in these columns, from the row a bookmark starts in
+/// to the row it ends in.
+fn column_text(table: &Table, id: &str, columns: &RangeInclusive<usize>) -> Option<String> {
+    let has = |cell: &TableCell, f: &dyn Fn(&ParagraphContent) -> bool| {
+        cell.content
+            .iter()
+            .any(|TableCellContent::Paragraph(p)| p.content.iter().any(f))
+    };
+    let is_start = |c: &ParagraphContent| matches!(c, ParagraphContent::BookmarkStart(start) if start.id.as_deref() == Some(id));
+    let mut texts = Vec::new();
+    let mut inside = false;
+    for row in &table.rows {
+        let cells: Vec<_> = row
+            .cells
+            .iter()
+            .filter_map(|c| match c {
+                TableRowContent::TableCell(c) => Some(c),
+                TableRowContent::SDT(_) => None,
+            })
+            .collect();
+        inside |= cells.iter().any(|c| has(c, &is_start));
+        if !inside {
+            continue;
+        }
+        for c in cells
+            .iter()
+            .skip(*columns.start())
+            .take(columns.clone().count())
+        {
+            texts.push(c.iter_text().map(|t| t.as_ref()).collect::<String>());
+        }
+        if cells.iter().any(|c| has(c, &|p| is_end(p, id))) {
+            return Some(texts.join(" "));
+        }
+    }
+    None
+}
+
+/// Returns the cell at this column of a row, leaving out row-level content controls.
+fn cell<'b, 'a>(
+    table: &'b mut Table<'a>,
+    row: usize,
+    column: usize,
+) -> Option<&'b mut TableCell<'a>> {
+    table
+        .rows
+        .get_mut(row)?
+        .cells
+        .iter_mut()
+        .filter_map(|c| match c {
+            TableRowContent::TableCell(c) => Some(c),
+            TableRowContent::SDT(_) => None,
+        })
+        .nth(column)
+}
+
+/// Returns the first or last paragraph of a cell, adding one to empty cells.
+fn cell_paragraph<'b, 'a>(cell: &'b mut TableCell<'a>, first: bool) -> &'b mut Paragraph<'a> {
+    if cell.content.is_empty() {
+        cell.content
+            .push(TableCellContent::Paragraph(Paragraph::default()));
+    }
+    let last = cell.content.len() - 1;
+    let TableCellContent::Paragraph(p) = &mut cell.content[if first { 0 } else { last }];
+    p
+}
+
+fn insert_at<'a>(
+    blocks: &mut Vec<BodyContent<'a>>,
+    name: &str,
+    content: &mut Option<Vec<ParagraphContent<'a>>>,
+) -> bool {
+    fn paragraph<'a>(
+        p: &mut Paragraph<'a>,
+        name: &str,
+        content: &mut Option<Vec<ParagraphContent<'a>>>,
+    ) -> bool {
+        let Some(pos) = p.content.iter().position(|c| is_start(c, name)) else {
+            return false;
+        };
+        let items = content.take().unwrap_or_default();
+        p.content.splice(pos + 1..pos + 1, items);
+        true
+    }
+
+    fn cell<'a>(
+        cell: &mut TableCell<'a>,
+        name: &str,
+        content: &mut Option<Vec<ParagraphContent<'a>>>,
+    ) -> bool {
+        cell.content
+            .iter_mut()
+            .any(|TableCellContent::Paragraph(p)| paragraph(p, name, content))
+    }
+
+    for i in 0..blocks.len() {
+        let found = match &mut blocks[i] {
+            BodyContent::Paragraph(p) => paragraph(p, name, content),
+            BodyContent::Table(t) => t.rows.iter_mut().any(|row| {
+                row.cells.iter_mut().any(|c| match c {
+                    TableRowContent::TableCell(c) => cell(c, name, content),
+                    TableRowContent::SDT(sdt) => sdt
+                        .content
+                        .as_mut()
+                        .is_some_and(|c| insert_at(&mut c.content, name, content)),
+                })
+            }),
+            BodyContent::Sdt(sdt) => sdt
+                .content
+                .as_mut()
+                .is_some_and(|c| insert_at(&mut c.content, name, content)),
+            BodyContent::TableCell(c) => cell(c, name, content),
+            BodyContent::BookmarkStart(start) if is_named(start, name) => {
+                let p = Paragraph {
+                    content: content.take().unwrap_or_default(),
+                    ..Default::default()
+                };
+                blocks.insert(i + 1, p.into());
+                true
+            }
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+fn replace_in<'a>(
+    blocks: &mut Vec<BodyContent<'a>>,
+    name: &str,
+    content: &mut Option<Vec<ParagraphContent<'a>>>,
+) -> DocxResult<bool> {
+    // block and position in the paragraph of the start, along with the id
+    let mut start = None;
+    for (i, block) in blocks.iter_mut().enumerate() {
+        match block {
+            BodyContent::Paragraph(p) => {
+                if let Some(j) = p.content.iter().position(|c| is_start(c, name)) {
+                    if let ParagraphContent::BookmarkStart(s) = &p.content[j] {
+                        start = Some((i, Some(j), s.id.clone().unwrap_or_default()));
+                    }
+                    break;
+                }
+            }
+            BodyContent::BookmarkStart(s) if is_named(s, name) => {
+                start = Some((i, None, s.id.clone().unwrap_or_default()));
+                break;
+            }
+            BodyContent::Table(t) => {
+                for row in &mut t.rows {
+                    for c in &mut row.cells {
+                        let found = match c {
+                            TableRowContent::TableCell(c) => replace_in_cell(c, name, content)?,
+                            TableRowContent::SDT(sdt) => match &mut sdt.content {
+                                Some(c) => replace_in(&mut c.content, name, content)?,
+                                None => false,
+                            },
+                        };
+                        if found {
+                            return Ok(true);
+                        }
+                    }
+                }
+            }
+            BodyContent::Sdt(sdt) => {
+                let found = match &mut sdt.content {
+                    Some(c) => replace_in(&mut c.content, name, content)?,
+                    None => false,
+                };
+                if found {
+                    return Ok(true);
+                }
+            }
+            BodyContent::TableCell(c) => {
+                let found = replace_in_cell(c, name, content)?;
+                if found {
+                    return Ok(true);
+                }
+            }
+            _ => {}
+        }
+    }
+    let Some((i, head, id)) = start else {
+        return Ok(false);
+    };
+
+    let mut end = None;
+    for (k, block) in blocks.iter().enumerate().skip(i) {
+        match block {
+            BodyContent::Paragraph(p) => {
+                let from = if k == i { head.map_or(0, |j| j + 1) } else { 0 };
+                if let Some(m) = p.content[from..].iter().position(|c| is_end(c, &id)) {
+                    end = Some((k, Some(from + m)));
+                    break;
+                }
+            }
+            BodyContent::BookmarkEnd(e) if e.id.as_deref() == Some(id.as_ref()) => {
+                end = Some((k, None));
+                break;
+            }
+            _ => {}
+        }
+    }
+    let Some((k, tail)) = end else {
+        return Err(DocxError::Bookmark(format!(
+            "{:?} doesn't end where it starts",
+            name
+        )));
+    };
+
+    let items = content.take().unwrap_or_default();
+    if let (true, Some(j), Some(m)) = (i == k, head, tail) {
+        if let BodyContent::Paragraph(p) = &mut blocks[i] {
+            p.content.splice(j + 1..m, items);
+        }
+        return Ok(true);
+    }
+
+    // the start and the end are in different blocks
+    let (first, last) = {
+        let mut spanned = blocks.drain(i..=k);
+        (spanned.next(), spanned.next_back())
+    };
+    let (mut merged, mut replacement) = match (first, head) {
+        (Some(BodyContent::Paragraph(mut p)), Some(j)) => {
+            p.content.truncate(j + 1);
+            (p, Vec::new())
+        }
+        (marker, _) => (Paragraph::default(), marker.into_iter().collect()),
+    };
+    merged.content.extend(items);
+    match (last, tail) {
+        (Some(BodyContent::Paragraph(mut p)), Some(m)) => {
+            if head.is_none() {
+                merged.property = p.property.take();
+            }
+            merged.content.extend(p.content.drain(m..));
+            replacement.push(merged.into());
+        }
+        (marker, _) => {
+            replacement.push(merged.into());
+            replacement.extend(marker);
+        }
+    }
+    blocks.splice(i..i, replacement);
+    Ok(true)
+}
+
+/// Replaces the content of a bookmark inside a cell, whose paragraphs are handled
+/// like blocks of the body.
+fn replace_in_cell<'a>(
+    cell: &mut TableCell<'a>,
+    name: &str,
+    content: &mut Option<Vec<ParagraphContent<'a>>>,
+) -> DocxResult<bool> {
+    let mut blocks: Vec<BodyContent<'a>> = cell
+        .content
+        .drain(..)
+        .map(|TableCellContent::Paragraph(p)| p.into())
+        .collect();
+    let replaced = replace_in(&mut blocks, name, content);
+    cell.content = blocks
+        .into_iter()
+        .filter_map(|block| match block {
+            BodyContent::Paragraph(p) => Some(TableCellContent::Paragraph(p)),
+            _ => None,
+        })
+        .collect();
+    replaced
+}
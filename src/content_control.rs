@@ -0,0 +1,561 @@
+//! Content controls
+//!
+//! Content controls (`w:sdt`) are found by their tag or alias, and [`SDT::set_value`]
+//! replaces their content after checking the value fits the type of the control.
+//! Controls with a `w:dataBinding` also write the value back to the custom XML part
+//! they are bound to.
+//!
+//! ```rust
+//! use docx_rust::{content_control::SDTValue, document::*, field::DateTime, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document
+//!     .push(
+//!         SDT::default().property(
+//!             SDTProperty::default()
+//!                 .tag("status")
+//!                 .drop_down_list(
+//!                     SDTDropDownList::default()
+//!                         .push(SDTListItem::new("Open", "open"))
+//!                         .push(SDTListItem::new("Closed", "closed")),
+//!                 ),
+//!         ),
+//!     )
+//!     .push(
+//!         SDT::default().property(
+//!             SDTProperty::default()
+//!                 .tag("due")
+//!                 .date(SDTDate::default().date_format("yyyy-MM-dd")),
+//!         ),
+//!     );
+//!
+//! assert_eq!(docx.set_content_control("status", "closed").unwrap(), 1);
+//! assert!(docx.set_content_control("status", "Pending").is_err());
+//! assert_eq!(
+//!     docx.set_content_control("due", DateTime::new(2024, 3, 1, 0, 0, 0))
+//!         .unwrap(),
+//!     1
+//! );
+//! assert_eq!(docx.document.body.text(), "Closed\r\n2024-03-01");
+//! ```
+//!
+//! [`SDT::set_value`]: ../document/struct.SDT.html#method.set_value
+
+use std::collections::HashMap;
+
+use crate::{
+    custom_xml::CustomXmlPart,
+    document::{
+        BodyContent, Break, Document, Footer, Header, Paragraph, ParagraphContent, Run, RunContent,
+        SDTContent, SDTType, TableCellContent, TableRowContent, SDT,
+    },
+    field::DateTime,
+    formatting::CharacterProperty,
+    Docx, DocxError, DocxResult,
+};
+
+/// A value of a content control
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SDTValue {
+    /// Text of a plain text, rich text, drop-down list or combo box control
+    Text(String),
+    /// Date of a date picker control
+    Date(DateTime),
+    /// State of a check box control
+    Checked(bool),
+}
+
+impl From<&str> for SDTValue {
+    fn from(val: &str) -> Self {
+        SDTValue::Text(val.to_string())
+    }
+}
+
+impl From<String> for SDTValue {
+    fn from(val: String) -> Self {
+        SDTValue::Text(val)
+    }
+}
+
+impl From<DateTime> for SDTValue {
+    fn from(val: DateTime) -> Self {
+        SDTValue::Date(val)
+    }
+}
+
+impl From<bool> for SDTValue {
+    fn from(val: bool) -> Self {
+        SDTValue::Checked(val)
+    }
+}
+
+impl<'a> SDT<'a> {
+    /// Replaces the content of the control with a value.
+    ///
+    /// Fails if the value doesn't fit the type of the control, like a date for a
+    /// check box, a line break in a single-line text control, or text that isn't one
+    /// of the items of a drop-down list. Bound custom XML isn't changed, use
+    /// [`Docx::set_content_control`] for that.
+    ///
+    /// [`Docx::set_content_control`]: ../struct.Docx.html#method.set_content_control
+    pub fn set_value<T: Into<SDTValue>>(&mut self, value: T) -> DocxResult<()> {
+        let block = self.content.iter().flat_map(|c| &c.content).any(|c| {
+            matches!(
+                c,
+                BodyContent::Paragraph(_) | BodyContent::Table(_) | BodyContent::TableCell(_)
+            )
+        });
+        self.apply(value.into(), block).map(|_| ())
+    }
+
+    /// Sets the value and returns the text to store in bound custom XML.
+    fn apply(&mut self, value: SDTValue, block: bool) -> DocxResult<String> {
+        let ty = self.control_type();
+        let property = self.property.get_or_insert_with(Default::default);
+
+        let (display, stored) = match (ty, value) {
+            (SDTType::RichText, SDTValue::Text(text)) => (text.clone(), text),
+            (SDTType::PlainText, SDTValue::Text(text)) => {
+                let multi_line = property
+                    .text
+                    .as_ref()
+                    .and_then(|t| t.multi_line)
+                    .unwrap_or_default();
+                if !multi_line && text.contains('\n') {
+                    return Err(invalid("line breaks in a single-line text control"));
+                }
+                (text.clone(), text)
+            }
+            (SDTType::DropDownList, SDTValue::Text(text)) => {
+                let list = property.drop_down_list.as_mut().unwrap();
+                let item = list
+                    .items
+                    .iter()
+                    .find(|i| i.display() == text || i.value.as_deref() == Some(text.as_str()))
+                    .ok_or_else(|| invalid(format!("{:?} is not an item of the list", text)))?;
+                let display = item.display().to_string();
+                let stored = item.value.as_deref().unwrap_or(&display).to_string();
+                list.last_value = Some(stored.clone().into());
+                (display, stored)
+            }
+            (SDTType::ComboBox, SDTValue::Text(text)) => {
+                let combo = property.combo_box.as_mut().unwrap();
+                let (display, stored) = match combo
+                    .items
+                    .iter()
+                    .find(|i| i.display() == text || i.value.as_deref() == Some(text.as_str()))
+                {
+                    Some(item) => (
+                        item.display().to_string(),
+                        item.value.as_deref().unwrap_or(item.display()).to_string(),
+                    ),
+                    None => (text.clone(), text),
+                };
+                combo.last_value = Some(stored.clone().into());
+                (display, stored)
+            }
+            (SDTType::Date, SDTValue::Text(text)) => {
+                let date = parse_date(&text)
+                    .ok_or_else(|| invalid(format!("{:?} is not a date", text)))?;
+                return self.apply(SDTValue::Date(date), block);
+            }
+            (SDTType::Date, SDTValue::Date(date)) => {
+                let picker = property.date.as_mut().unwrap();
+                let full_date = format!(
+                    "{:04}-{:02}-{:02}T00:00:00Z",
+                    date.year, date.month, date.day
+                );
+                let display = date.format(
+                    picker
+                        .date_format
+                        .as_ref()
+                        .map_or("M/d/yyyy", |f| f.value.as_ref()),
+                );
+                let stored = match picker
+                    .store_mapped_data_as
+                    .as_ref()
+                    .map(|s| s.value.as_ref())
+                {
+                    Some("text") => display.clone(),
+                    Some("date") => full_date[..10].to_string(),
+                    _ => full_date[..19].to_string(),
+                };
+                picker.full_date = Some(full_date.into());
+                (display, stored)
+            }
+            (SDTType::Checkbox, SDTValue::Text(text)) => {
+                let checked = match text.trim() {
+                    "1" | "true" => true,
+                    "0" | "false" | "" => false,
+                    _ => return Err(invalid(format!("{:?} is not a check box state", text))),
+                };
+                return self.apply(SDTValue::Checked(checked), block);
+            }
+            (SDTType::Checkbox, SDTValue::Checked(checked)) => {
+                let checkbox = property.checkbox.as_mut().unwrap();
+                checkbox.checked = Some(crate::document::CheckboxChecked {
+                    value: if checked { "1" } else { "0" }.into(),
+                });
+                (checkbox.symbol(checked).to_string(), checked.to_string())
+            }
+            (ty, value) => {
+                return Err(invalid(format!(
+                    "{:?} doesn't fit a {:?} control",
+                    value, ty
+                )))
+            }
+        };
+
+        let run_property = if property.showing_placeholder.is_some() {
+            property.run_property.clone()
+        } else {
+            self.content
+                .as_ref()
+                .and_then(|c| first_run(&c.content))
+                .and_then(|r| r.property.clone())
+                .or_else(|| property.run_property.clone())
+        };
+        property.showing_placeholder = None;
+
+        let run = text_run(display, run_property);
+        let content = &mut self.content.get_or_insert_with(SDTContent::default).content;
+        let first_cell = content.iter_mut().find_map(|c| match c {
+            BodyContent::TableCell(cell) => Some(cell),
+            _ => None,
+        });
+        if let Some(cell) = first_cell {
+            let first = cell
+                .content
+                .drain(..)
+                .next()
+                .map(|TableCellContent::Paragraph(p)| p)
+                .unwrap_or_default();
+            cell.content = vec![TableCellContent::Paragraph(replace_paragraph(first, run))];
+        } else if block
+            || content
+                .iter()
+                .any(|c| matches!(c, BodyContent::Paragraph(_)))
+        {
+            let first = content
+                .drain(..)
+                .find_map(|c| match c {
+                    BodyContent::Paragraph(p) => Some(p),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            content.push(BodyContent::Paragraph(replace_paragraph(first, run)));
+        } else {
+            *content = vec![BodyContent::Run(run)];
+        }
+
+        Ok(stored)
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the content controls of the document, headers and footers, including
+    /// nested ones.
+    pub fn content_controls(&self) -> Vec<&SDT<'a>> {
+        let mut controls = Vec::new();
+        collect(&self.document.body.content, &mut controls);
+        for header in self.headers.values() {
+            collect(&header.content, &mut controls);
+        }
+        for footer in self.footers.values() {
+            collect(&footer.content, &mut controls);
+        }
+        controls
+    }
+
+    /// Returns the content controls with a tag or alias.
+    pub fn find_content_controls(&self, name: &str) -> Vec<&SDT<'a>> {
+        self.content_controls()
+            .into_iter()
+            .filter(|sdt| matches_name(sdt, name))
+            .collect()
+    }
+
+    /// Sets the value of the content controls with a tag or alias, and of the custom
+    /// XML nodes they are bound to.
+    ///
+    /// Returns the number of controls found. Stops at the first control the value
+    /// doesn't fit and returns the error.
+    pub fn set_content_control<T: Into<SDTValue>>(
+        &mut self,
+        name: &str,
+        value: T,
+    ) -> DocxResult<usize> {
+        let value = value.into();
+        let mut count = 0;
+        let mut bound = Vec::new();
+        let mut result = Ok(());
+
+        for_each_control(
+            &mut self.document,
+            &mut self.headers,
+            &mut self.footers,
+            &mut |sdt, block| {
+                if result.is_err() || !matches_name(sdt, name) {
+                    return;
+                }
+                match sdt.apply(value.clone(), block) {
+                    Ok(stored) => {
+                        count += 1;
+                        if let Some(binding) = binding(sdt) {
+                            bound.push((binding, stored));
+                        }
+                    }
+                    Err(e) => result = Err(e),
+                }
+            },
+        );
+        result?;
+
+        for ((item_id, xpath, mappings), stored) in bound {
+            if let Some(part) = find_part(&mut self.custom_xml, item_id.as_deref(), |part| {
+                part.get(&xpath, &mappings).is_some()
+            }) {
+                part.set(&xpath, &mappings, &stored)?;
+            }
+        }
+        self.update_bound_controls();
+
+        Ok(count)
+    }
+
+    /// Shows the values of the custom XML nodes in the content controls bound to them.
+    ///
+    /// Returns the number of updated controls. Controls whose node is missing or
+    /// holds a value that doesn't fit are left alone.
+    pub fn update_bound_controls(&mut self) -> usize {
+        let parts = &mut self.custom_xml;
+        let mut count = 0;
+
+        for_each_control(
+            &mut self.document,
+            &mut self.headers,
+            &mut self.footers,
+            &mut |sdt, block| {
+                let Some((item_id, xpath, mappings)) = binding(sdt) else {
+                    return;
+                };
+                let value = find_part(parts, item_id.as_deref(), |part| {
+                    part.get(&xpath, &mappings).is_some()
+                })
+                .and_then(|part| part.get(&xpath, &mappings));
+                if let Some(value) = value {
+                    if sdt.apply(SDTValue::Text(value), block).is_ok() {
+                        count += 1;
+                    }
+                }
+            },
+        );
+
+        count
+    }
+}
+
+fn invalid<T: Into<String>>(message: T) -> DocxError {
+    DocxError::ContentControl(message.into())
+}
+
+fn matches_name(sdt: &SDT, name: &str) -> bool {
+    sdt.tag() == Some(name) || sdt.alias() == Some(name)
+}
+
+/// Returns the store item id, XPath and prefix mappings of a bound control.
+fn binding(sdt: &SDT) -> Option<(Option<String>, String, String)> {
+    let binding = sdt.property.as_ref()?.data_binding.as_ref()?;
+    Some((
+        binding.store_item_id.as_ref().map(|id| id.to_string()),
+        binding.xpath.to_string(),
+        binding
+            .prefix_mappings
+            .as_ref()
+            .map(|m| m.to_string())
+            .unwrap_or_default(),
+    ))
+}
+
+/// Finds the part with an item id, or the first part that passes the check without one.
+fn find_part<'p, 'a, F: Fn(&CustomXmlPart<'a>) -> bool>(
+    parts: &'p mut [CustomXmlPart<'a>],
+    item_id: Option<&str>,
+    check: F,
+) -> Option<&'p mut CustomXmlPart<'a>> {
+    match item_id {
+        Some(id) => parts.iter_mut().find(|p| {
+            p.item_id
+                .as_deref()
+                .is_some_and(|i| i.eq_ignore_ascii_case(id))
+        }),
+        None => parts.iter_mut().find(|p| check(p)),
+    }
+}
+
+/// Parses the date of `yyyy-MM-dd` or `yyyy-MM-ddTHH:mm:ss`.
+fn parse_date(text: &str) -> Option<DateTime> {
+    let mut parts = text.trim().get(..10)?.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=31).contains(&day))
+        .then(|| DateTime::new(year, month, day, 0, 0, 0))
+}
+
+fn text_run<'a>(text: String, property: Option<CharacterProperty<'a>>) -> Run<'a> {
+    let mut run = Run {
+        property,
+        ..Default::default()
+    };
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            run.content.push(RunContent::Break(Break::default()));
+        }
+        if !line.is_empty() {
+            run.content.push(RunContent::Text(line.to_string().into()));
+        }
+    }
+    run
+}
+
+fn replace_paragraph<'a>(first: Paragraph<'a>, run: Run<'a>) -> Paragraph<'a> {
+    Paragraph {
+        property: first.property,
+        ..Default::default()
+    }
+    .push(run)
+}
+
+fn first_run<'b, 'a>(content: &'b [BodyContent<'a>]) -> Option<&'b Run<'a>> {
+    content.iter().find_map(|c| match c {
+        BodyContent::Run(r) => Some(r),
+        BodyContent::Paragraph(p) => paragraph_run(p),
+        BodyContent::TableCell(cell) => cell
+            .content
+            .iter()
+            .find_map(|TableCellContent::Paragraph(p)| paragraph_run(p)),
+        BodyContent::Sdt(sdt) => sdt.content.as_ref().and_then(|c| first_run(&c.content)),
+        _ => None,
+    })
+}
+
+fn paragraph_run<'b, 'a>(p: &'b Paragraph<'a>) -> Option<&'b Run<'a>> {
+    p.content.iter().find_map(|c| match c {
+        ParagraphContent::Run(r) => Some(r),
+        _ => None,
+    })
+}
+
+fn collect<'b, 'a>(content: &'b [BodyContent<'a>], out: &mut Vec<&'b SDT<'a>>) {
+    fn paragraph<'b, 'a>(p: &'b Paragraph<'a>, out: &mut Vec<&'b SDT<'a>>) {
+        for c in &p.content {
+            if let ParagraphContent::SDT(sdt) = c {
+                sdt_and_children(sdt, out);
+            }
+        }
+    }
+
+    fn sdt_and_children<'b, 'a>(sdt: &'b SDT<'a>, out: &mut Vec<&'b SDT<'a>>) {
+        out.push(sdt);
+        if let Some(content) = &sdt.content {
+            collect(&content.content, out);
+        }
+    }
+
+    for c in content {
+        match c {
+            BodyContent::Paragraph(p) => paragraph(p, out),
+            BodyContent::Table(t) => {
+                for row in &t.rows {
+                    for cell in &row.cells {
+                        match cell {
+                            TableRowContent::TableCell(cell) => {
+                                for TableCellContent::Paragraph(p) in &cell.content {
+                                    paragraph(p, out);
+                                }
+                            }
+                            TableRowContent::SDT(sdt) => sdt_and_children(sdt, out),
+                        }
+                    }
+                }
+            }
+            BodyContent::TableCell(cell) => {
+                for TableCellContent::Paragraph(p) in &cell.content {
+                    paragraph(p, out);
+                }
+            }
+            BodyContent::Sdt(sdt) => sdt_and_children(sdt, out),
+            _ => {}
+        }
+    }
+}
+
+/// Calls `f` with every control and whether it holds paragraphs, then visits the
+/// controls nested in its new content.
+fn for_each_control<'a, F: FnMut(&mut SDT<'a>, bool)>(
+    document: &mut Document<'a>,
+    headers: &mut HashMap<String, Header<'a>>,
+    footers: &mut HashMap<String, Footer<'a>>,
+    f: &mut F,
+) {
+    visit(&mut document.body.content, true, f);
+    for header in headers.values_mut() {
+        visit(&mut header.content, true, f);
+    }
+    for footer in footers.values_mut() {
+        visit(&mut footer.content, true, f);
+    }
+}
+
+fn visit<'a, F: FnMut(&mut SDT<'a>, bool)>(
+    content: &mut [BodyContent<'a>],
+    block: bool,
+    f: &mut F,
+) {
+    fn paragraph<'a, F: FnMut(&mut SDT<'a>, bool)>(p: &mut Paragraph<'a>, f: &mut F) {
+        for c in &mut p.content {
+            if let ParagraphContent::SDT(sdt) = c {
+                sdt_and_children(sdt, false, f);
+            }
+        }
+    }
+
+    fn sdt_and_children<'a, F: FnMut(&mut SDT<'a>, bool)>(
+        sdt: &mut SDT<'a>,
+        block: bool,
+        f: &mut F,
+    ) {
+        f(sdt, block);
+        if let Some(content) = &mut sdt.content {
+            visit(&mut content.content, block, f);
+        }
+    }
+
+    for c in content {
+        match c {
+            BodyContent::Paragraph(p) => paragraph(p, f),
+            BodyContent::Table(t) => {
+                for row in &mut t.rows {
+                    for cell in &mut row.cells {
+                        match cell {
+                            TableRowContent::TableCell(cell) => {
+                                for TableCellContent::Paragraph(p) in &mut cell.content {
+                                    paragraph(p, f);
+                                }
+                            }
+                            TableRowContent::SDT(sdt) => sdt_and_children(sdt, true, f),
+                        }
+                    }
+                }
+            }
+            BodyContent::TableCell(cell) => {
+                for TableCellContent::Paragraph(p) in &mut cell.content {
+                    paragraph(p, f);
+                }
+            }
+            BodyContent::Sdt(sdt) => sdt_and_children(sdt, block, f),
+            _ => {}
+        }
+    }
+}
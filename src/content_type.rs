@@ -13,10 +13,33 @@ const CONTENT_TYPE_CORE: &str = "application/vnd.openxmlformats-package.core-pro
 const CONTENT_TYPE_RELATIONSHIP: &str = "application/vnd.openxmlformats-package.relationships+xml";
 const CONTENT_TYPE_EXTENDED: &str =
     "application/vnd.openxmlformats-officedocument.extended-properties+xml";
+const CONTENT_TYPE_CUSTOM: &str =
+    "application/vnd.openxmlformats-officedocument.custom-properties+xml";
 const CONTENT_TYPE_DOCUMENT: &str =
     "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml";
 const CONTENT_TYPE_STYLES: &str =
     "application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml";
+const CONTENT_TYPE_NUMBERING: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml";
+const CONTENT_TYPE_SETTINGS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml";
+const CONTENT_TYPE_WEB_SETTINGS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.webSettings+xml";
+const CONTENT_TYPE_FONT_TABLE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.fontTable+xml";
+const CONTENT_TYPE_FOOTNOTES: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.footnotes+xml";
+const CONTENT_TYPE_ENDNOTES: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.endnotes+xml";
+const CONTENT_TYPE_COMMENTS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.comments+xml";
+const CONTENT_TYPE_HEADER: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml";
+const CONTENT_TYPE_FOOTER: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml";
+const CONTENT_TYPE_THEME: &str = "application/vnd.openxmlformats-officedocument.theme+xml";
+const CONTENT_TYPE_CUSTOM_XML_PROPERTIES: &str =
+    "application/vnd.openxmlformats-officedocument.customXmlProperties+xml";
 
 #[derive(Debug, XmlRead, Clone)]
 #[xml(tag = "Types")]
@@ -62,6 +85,73 @@ impl Default for ContentTypes<'static> {
     }
 }
 
+impl<'a> ContentTypes<'a> {
+    /// Registers the content type of a file extension, unless it is already present.
+    pub fn add_default<E, T>(&mut self, ext: E, ty: T) -> &mut Self
+    where
+        E: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+    {
+        let ext = ext.into();
+        if !self
+            .defaults
+            .iter()
+            .any(|d| d.ext.eq_ignore_ascii_case(&ext))
+        {
+            self.defaults
+                .push(DefaultContentType { ext, ty: ty.into() });
+        }
+        self
+    }
+
+    /// Registers the content type of a part, unless it is already present.
+    pub fn add_override<P, T>(&mut self, part: P, ty: T) -> &mut Self
+    where
+        P: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+    {
+        let part = part.into();
+        if !self.overrides.iter().any(|o| o.part == part) {
+            self.overrides.push(OverrideContentType {
+                part,
+                ty: ty.into(),
+            });
+        }
+        self
+    }
+
+    /// Registers the content type of a known WordprocessingML part, like `/word/numbering.xml`.
+    ///
+    /// Parts that aren't recognized by their name are left alone.
+    pub fn add_part<P: Into<Cow<'a, str>>>(&mut self, part: P) -> &mut Self {
+        let part = part.into();
+        let name = part.rsplit('/').next().unwrap_or_default();
+        let ty = match name
+            .trim_end_matches(".xml")
+            .trim_end_matches(char::is_numeric)
+        {
+            "app" => CONTENT_TYPE_EXTENDED,
+            "core" => CONTENT_TYPE_CORE,
+            "custom" => CONTENT_TYPE_CUSTOM,
+            "document" => CONTENT_TYPE_DOCUMENT,
+            "styles" => CONTENT_TYPE_STYLES,
+            "numbering" => CONTENT_TYPE_NUMBERING,
+            "settings" => CONTENT_TYPE_SETTINGS,
+            "webSettings" => CONTENT_TYPE_WEB_SETTINGS,
+            "fontTable" => CONTENT_TYPE_FONT_TABLE,
+            "footnotes" => CONTENT_TYPE_FOOTNOTES,
+            "endnotes" => CONTENT_TYPE_ENDNOTES,
+            "comments" => CONTENT_TYPE_COMMENTS,
+            "header" => CONTENT_TYPE_HEADER,
+            "footer" => CONTENT_TYPE_FOOTER,
+            "theme" => CONTENT_TYPE_THEME,
+            "itemProps" => CONTENT_TYPE_CUSTOM_XML_PROPERTIES,
+            _ => return self,
+        };
+        self.add_override(part, ty)
+    }
+}
+
 impl<'a> XmlWrite for ContentTypes<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let ContentTypes {
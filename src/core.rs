@@ -1,12 +1,36 @@
 //! Core File Properties part
 //!
 //! The corresponding ZIP item is `/docProps/core.xml`.
+//!
+//! Some applications write the elements of the part with the `cp` prefix, others in the
+//! default namespace, which are read as [`Core::CoreNamespace`] and
+//! [`Core::CoreNoNamespace`]. The methods of [`Core`] work the same on both.
+//!
+//! ```rust
+//! use docx_rust::{core::Core, field::DateTime};
+//! use hard_xml::XmlWrite;
+//!
+//! let mut core = Core::default();
+//! core.set_title("Annual Report")
+//!     .set_language("en-GB")
+//!     .set_created(DateTime::new(2024, 3, 1, 9, 30, 0));
+//!
+//! assert_eq!(core.title(), Some("Annual Report"));
+//! assert_eq!(core.created(), Some(DateTime::new(2024, 3, 1, 9, 30, 0)));
+//! assert!(core
+//!     .to_string()
+//!     .unwrap()
+//!     .contains(r#"<dcterms:created xsi:type="dcterms:W3CDTF">2024-03-01T09:30:00Z</dcterms:created>"#));
+//! ```
 
 use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
-use crate::schema::{SCHEMA_CORE_2, SCHEMA_DC, SCHEMA_XML};
+use crate::field::DateTime;
+use crate::schema::{
+    SCHEMA_CORE_2, SCHEMA_DC, SCHEMA_DCMI_TYPE, SCHEMA_DC_TERMS, SCHEMA_XML, SCHEMA_XSI,
+};
 
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 pub enum Core<'a> {
@@ -16,6 +40,12 @@ pub enum Core<'a> {
     CoreNoNamespace(CoreNoNamespace<'a>),
 }
 
+impl Default for Core<'_> {
+    fn default() -> Self {
+        Core::CoreNamespace(CoreNamespace::default())
+    }
+}
+
 #[derive(Debug, Default, XmlRead, Clone)]
 #[xml(tag = "cp:coreProperties")]
 pub struct CoreNamespace<'a> {
@@ -33,6 +63,22 @@ pub struct CoreNamespace<'a> {
     pub last_modified_by: Option<Cow<'a, str>>,
     #[xml(flatten_text = "cp:revision")]
     pub revision: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "cp:lastPrinted")]
+    pub last_printed: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dcterms:created")]
+    pub created: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dcterms:modified")]
+    pub modified: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "cp:category")]
+    pub category: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "cp:contentStatus")]
+    pub content_status: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dc:language")]
+    pub language: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dc:identifier")]
+    pub identifier: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "cp:version")]
+    pub version: Option<Cow<'a, str>>,
 }
 
 #[derive(Debug, Default, XmlRead, Clone)]
@@ -52,128 +98,254 @@ pub struct CoreNoNamespace<'a> {
     pub last_modified_by: Option<Cow<'a, str>>,
     #[xml(flatten_text = "revision")]
     pub revision: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "lastPrinted")]
+    pub last_printed: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dcterms:created")]
+    pub created: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dcterms:modified")]
+    pub modified: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "category")]
+    pub category: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "contentStatus")]
+    pub content_status: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dc:language")]
+    pub language: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "dc:identifier")]
+    pub identifier: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "version")]
+    pub version: Option<Cow<'a, str>>,
 }
 
-impl<'a> XmlWrite for CoreNamespace<'a> {
-    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
-        let CoreNamespace {
-            title,
-            subject,
-            creator,
-            keywords,
-            description,
-            last_modified_by,
-            revision,
-        } = self;
-
-        log::debug!("[Core] Started writing.");
-        let _ = write!(writer.inner, "{}", SCHEMA_XML);
-
-        writer.write_element_start("cp:coreProperties")?;
-
-        writer.write_attribute("xmlns:cp", SCHEMA_CORE_2)?;
-
-        writer.write_attribute("xmlns:dc", SCHEMA_DC)?;
-
-        if title.is_none()
-            && subject.is_none()
-            && creator.is_none()
-            && keywords.is_none()
-            && description.is_none()
-            && last_modified_by.is_none()
-            && revision.is_none()
-        {
-            writer.write_element_end_empty()?;
-        } else {
-            writer.write_element_end_open()?;
-            if let Some(val) = title {
-                writer.write_flatten_text("dc:title", val, false)?;
-            }
-            if let Some(val) = subject {
-                writer.write_flatten_text("dc:subject", val, false)?;
-            }
-            if let Some(val) = creator {
-                writer.write_flatten_text("dc:creator", val, false)?;
-            }
-            if let Some(val) = keywords {
-                writer.write_flatten_text("cp:keywords", val, false)?;
+macro_rules! __core_field {
+    ($core:expr, $field:ident) => {
+        match $core {
+            Core::CoreNamespace(c) => &c.$field,
+            Core::CoreNoNamespace(c) => &c.$field,
+        }
+    };
+    (mut $core:expr, $field:ident) => {
+        match $core {
+            Core::CoreNamespace(c) => &mut c.$field,
+            Core::CoreNoNamespace(c) => &mut c.$field,
+        }
+    };
+}
+
+macro_rules! __core_text {
+    ($($(#[$doc:meta])* $field:ident, $setter:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $field(&self) -> Option<&str> {
+                __core_field!(self, $field).as_deref()
             }
-            if let Some(val) = description {
-                writer.write_flatten_text("dc:description", val, false)?;
+
+            pub fn $setter<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+                *__core_field!(mut self, $field) = Some(value.into());
+                self
             }
-            if let Some(val) = last_modified_by {
-                writer.write_flatten_text("cp:lastModifiedBy", val, false)?;
+        )*
+    };
+}
+
+macro_rules! __core_date {
+    ($($(#[$doc:meta])* $field:ident, $setter:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $field(&self) -> Option<DateTime> {
+                __core_field!(self, $field).as_deref()?.trim().parse().ok()
             }
-            if let Some(val) = revision {
-                writer.write_flatten_text("cp:revision", val, false)?;
+
+            pub fn $setter(&mut self, value: DateTime) -> &mut Self {
+                *__core_field!(mut self, $field) = Some(value.to_iso8601().into());
+                self
             }
-            writer.write_element_end_close("cp:coreProperties")?;
-        }
+        )*
+    };
+}
 
-        log::debug!("[Core] Finished writing.");
+impl<'a> Core<'a> {
+    __core_text! {
+        title, set_title;
+        subject, set_subject;
+        /// Author of the document
+        creator, set_creator;
+        keywords, set_keywords;
+        /// Comments on the document
+        description, set_description;
+        last_modified_by, set_last_modified_by;
+        /// Number of times the document was saved
+        revision, set_revision;
+        category, set_category;
+        /// Status like `Draft` or `Final`
+        content_status, set_content_status;
+        /// Language of the content, like `en-US`
+        language, set_language;
+        identifier, set_identifier;
+        version, set_version;
+    }
 
-        Ok(())
+    __core_date! {
+        last_printed, set_last_printed;
+        created, set_created;
+        modified, set_modified;
     }
 }
 
-impl<'a> XmlWrite for CoreNoNamespace<'a> {
-    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
-        let CoreNoNamespace {
-            title,
-            subject,
-            creator,
-            keywords,
-            description,
-            last_modified_by,
-            revision,
-        } = self;
-
-        log::debug!("[Core] Started writing.");
-        let _ = write!(writer.inner, "{}", SCHEMA_XML);
-
-        writer.write_element_start("cp:coreProperties")?;
-
-        writer.write_attribute("xmlns:cp", SCHEMA_CORE_2)?;
-
-        writer.write_attribute("xmlns:dc", SCHEMA_DC)?;
-
-        if title.is_none()
-            && subject.is_none()
-            && creator.is_none()
-            && keywords.is_none()
-            && description.is_none()
-            && last_modified_by.is_none()
-            && revision.is_none()
-        {
-            writer.write_element_end_empty()?;
-        } else {
-            writer.write_element_end_open()?;
-            if let Some(val) = title {
-                writer.write_flatten_text("dc:title", val, false)?;
-            }
-            if let Some(val) = subject {
-                writer.write_flatten_text("dc:subject", val, false)?;
-            }
-            if let Some(val) = creator {
-                writer.write_flatten_text("dc:creator", val, false)?;
-            }
-            if let Some(val) = keywords {
-                writer.write_flatten_text("cp:keywords", val, false)?;
-            }
-            if let Some(val) = description {
-                writer.write_flatten_text("dc:description", val, false)?;
-            }
-            if let Some(val) = last_modified_by {
-                writer.write_flatten_text("cp:lastModifiedBy", val, false)?;
-            }
-            if let Some(val) = revision {
-                writer.write_flatten_text("cp:revision", val, false)?;
+macro_rules! __core_write {
+    ($name:ident) => {
+        impl<'a> XmlWrite for $name<'a> {
+            fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+                let $name {
+                    title,
+                    subject,
+                    creator,
+                    keywords,
+                    description,
+                    last_modified_by,
+                    revision,
+                    last_printed,
+                    created,
+                    modified,
+                    category,
+                    content_status,
+                    language,
+                    identifier,
+                    version,
+                } = self;
+
+                log::debug!("[Core] Started writing.");
+                let _ = write!(writer.inner, "{}", SCHEMA_XML);
+
+                writer.write_element_start("cp:coreProperties")?;
+
+                writer.write_attribute("xmlns:cp", SCHEMA_CORE_2)?;
+
+                writer.write_attribute("xmlns:dc", SCHEMA_DC)?;
+
+                writer.write_attribute("xmlns:dcterms", SCHEMA_DC_TERMS)?;
+
+                writer.write_attribute("xmlns:dcmitype", SCHEMA_DCMI_TYPE)?;
+
+                writer.write_attribute("xmlns:xsi", SCHEMA_XSI)?;
+
+                let texts = [
+                    ("dc:title", title),
+                    ("dc:subject", subject),
+                    ("dc:creator", creator),
+                    ("cp:keywords", keywords),
+                    ("dc:description", description),
+                    ("cp:lastModifiedBy", last_modified_by),
+                    ("cp:revision", revision),
+                ];
+                let more_texts = [
+                    ("cp:category", category),
+                    ("cp:contentStatus", content_status),
+                    ("dc:language", language),
+                    ("dc:identifier", identifier),
+                    ("cp:version", version),
+                ];
+                let dates = [
+                    ("cp:lastPrinted", last_printed),
+                    ("dcterms:created", created),
+                    ("dcterms:modified", modified),
+                ];
+
+                if texts.iter().chain(&more_texts).all(|(_, v)| v.is_none())
+                    && dates.iter().all(|(_, v)| v.is_none())
+                {
+                    writer.write_element_end_empty()?;
+                } else {
+                    writer.write_element_end_open()?;
+                    for (tag, val) in texts {
+                        if let Some(val) = val {
+                            writer.write_flatten_text(tag, val, false)?;
+                        }
+                    }
+                    for (tag, val) in dates {
+                        if let Some(val) = val {
+                            write_date(writer, tag, val)?;
+                        }
+                    }
+                    for (tag, val) in more_texts {
+                        if let Some(val) = val {
+                            writer.write_flatten_text(tag, val, false)?;
+                        }
+                    }
+                    writer.write_element_end_close("cp:coreProperties")?;
+                }
+
+                log::debug!("[Core] Finished writing.");
+
+                Ok(())
             }
-            writer.write_element_end_close("cp:coreProperties")?;
         }
+    };
+}
 
-        log::debug!("[Core] Finished writing.");
+__core_write!(CoreNamespace);
+__core_write!(CoreNoNamespace);
 
-        Ok(())
+/// Writes a date as it is stored, saying the created and modified dates are W3CDTF as they must.
+fn write_date<W: Write>(writer: &mut XmlWriter<W>, tag: &str, date: &str) -> XmlResult<()> {
+    writer.write_element_start(tag)?;
+    if tag.starts_with("dcterms:") {
+        writer.write_attribute("xsi:type", "dcterms:W3CDTF")?;
     }
+    writer.write_element_end_open()?;
+    writer.write_text(date)?;
+    writer.write_element_end_close(tag)?;
+    Ok(())
+}
+
+#[test]
+fn core_test() -> XmlResult<()> {
+    let core = Core::from_str(concat!(
+        r#"<coreProperties xmlns="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" "#,
+        r#"xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" "#,
+        r#"xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">"#,
+        r#"<dc:title>Report</dc:title><category>Finance</category>"#,
+        r#"<dcterms:created xsi:type="dcterms:W3CDTF">2024-03-01T10:30:00+01:00</dcterms:created>"#,
+        r#"<lastPrinted>2024-03-02T08:00:00Z</lastPrinted>"#,
+        r#"</coreProperties>"#
+    ))?;
+    assert!(matches!(core, Core::CoreNoNamespace(_)));
+    assert_eq!(core.title(), Some("Report"));
+    assert_eq!(core.category(), Some("Finance"));
+    assert_eq!(core.created(), Some(DateTime::new(2024, 3, 1, 9, 30, 0)));
+    assert_eq!(
+        core.last_printed(),
+        Some(DateTime::new(2024, 3, 2, 8, 0, 0))
+    );
+    assert_eq!(core.modified(), None);
+
+    let xml = core.to_string()?;
+    assert!(xml.contains("<cp:category>Finance</cp:category>"));
+    assert!(xml.contains(
+        r#"<dcterms:created xsi:type="dcterms:W3CDTF">2024-03-01T10:30:00+01:00</dcterms:created>"#
+    ));
+    assert!(xml.contains("<cp:lastPrinted>2024-03-02T08:00:00Z</cp:lastPrinted>"));
+
+    let core = Core::from_str(&xml)?;
+    assert!(matches!(core, Core::CoreNamespace(_)));
+    assert_eq!(core.category(), Some("Finance"));
+    assert_eq!(core.created(), Some(DateTime::new(2024, 3, 1, 9, 30, 0)));
+
+    // dates that aren't W3CDTF don't fail the parse, they just read as `None`
+    let mut core = Core::from_str(concat!(
+        r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" "#,
+        r#"xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">"#,
+        r#"<dcterms:created xsi:type="dcterms:W3CDTF"></dcterms:created>"#,
+        r#"<dcterms:modified xsi:type="dcterms:W3CDTF">2024-03-01 10:00</dcterms:modified>"#,
+        r#"</cp:coreProperties>"#
+    ))?;
+    assert_eq!(core.created(), None);
+    assert_eq!(core.modified(), None);
+    assert!(core.to_string()?.contains(
+        r#"<dcterms:modified xsi:type="dcterms:W3CDTF">2024-03-01 10:00</dcterms:modified>"#
+    ));
+
+    core.set_modified(DateTime::new(2024, 3, 1, 10, 0, 0));
+    assert_eq!(core.modified(), Some(DateTime::new(2024, 3, 1, 10, 0, 0)));
+    assert!(core.to_string()?.contains("2024-03-01T10:00:00Z"));
+    Ok(())
 }
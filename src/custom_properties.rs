@@ -0,0 +1,387 @@
+//! Custom File Properties part
+//!
+//! The corresponding ZIP item is `/docProps/custom.xml`. It holds named properties
+//! defined by users or applications, like a case number or a classification label,
+//! which `DOCPROPERTY` fields can show in the document.
+//!
+//! ```rust
+//! use docx_rust::{custom_properties::CustomValue, field::DateTime, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.set_custom_property("CaseNumber", "2024-0042");
+//! docx.set_custom_property("Pages", 12);
+//! docx.set_custom_property("Signed", false);
+//! docx.set_custom_property("Due", DateTime::new(2024, 3, 1, 0, 0, 0));
+//!
+//! let properties = docx.custom_properties.as_ref().unwrap();
+//! assert_eq!(properties.get("casenumber").unwrap().to_string(), "2024-0042");
+//! assert_eq!(properties.get("Pages").unwrap().as_i64(), Some(12));
+//! assert_eq!(properties.properties[3].pid, 5);
+//! ```
+
+use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
+use std::borrow::Cow;
+use std::fmt;
+use std::io::Write;
+
+use crate::{
+    field::DateTime,
+    schema::{SCHEMAS_CUSTOM, SCHEMA_DOC_PROPS_V_TYPES, SCHEMA_XML},
+    Docx,
+};
+
+/// Format id Office gives to all custom properties
+pub const FMTID_USER_DEFINED_PROPERTIES: &str = "{D5CDD505-2E9C-101B-9397-08002B2CF9AE}";
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "Properties")]
+pub struct CustomProperties<'a> {
+    #[xml(child = "property")]
+    pub properties: Vec<CustomProperty<'a>>,
+}
+
+impl<'a> CustomProperties<'a> {
+    /// Returns the value of a property, looking its name up regardless of case like
+    /// Word does.
+    pub fn get(&self, name: &str) -> Option<&CustomValue<'a>> {
+        self.property(name)?.value.as_ref()
+    }
+
+    fn property(&self, name: &str) -> Option<&CustomProperty<'a>> {
+        self.properties.iter().find(|p| p.has_name(name))
+    }
+
+    /// Sets the value of a property, adding it if there is none by that name.
+    pub fn set<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: Into<Cow<'a, str>>,
+        V: Into<CustomValue<'a>>,
+    {
+        let name = name.into();
+        let value = Some(value.into());
+        if let Some(property) = self.properties.iter_mut().find(|p| p.has_name(&name)) {
+            property.value = value;
+            return self;
+        }
+        // ids 0 and 1 are reserved
+        let pid = self.properties.iter().map(|p| p.pid + 1).fold(2, u32::max);
+        self.properties.push(CustomProperty {
+            fmtid: FMTID_USER_DEFINED_PROPERTIES.into(),
+            pid,
+            name: Some(name),
+            link_target: None,
+            value,
+        });
+        self
+    }
+
+    /// Removes a property, and returns its value.
+    pub fn remove(&mut self, name: &str) -> Option<CustomValue<'a>> {
+        let index = self.properties.iter().position(|p| p.has_name(name))?;
+        self.properties.remove(index).value
+    }
+
+    /// Returns the names and values of the properties.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &CustomValue<'a>)> {
+        self.properties
+            .iter()
+            .filter_map(|p| Some((p.name.as_deref()?, p.value.as_ref()?)))
+    }
+}
+
+impl<'a> XmlWrite for CustomProperties<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let CustomProperties { properties } = self;
+
+        log::debug!("[CustomProperties] Started writing.");
+        let _ = write!(writer.inner, "{}", SCHEMA_XML);
+
+        writer.write_element_start("Properties")?;
+
+        writer.write_attribute("xmlns", SCHEMAS_CUSTOM)?;
+        writer.write_attribute("xmlns:vt", SCHEMA_DOC_PROPS_V_TYPES)?;
+
+        if properties.is_empty() {
+            writer.write_element_end_empty()?;
+        } else {
+            writer.write_element_end_open()?;
+            for ele in properties {
+                ele.to_writer(writer)?;
+            }
+            writer.write_element_end_close("Properties")?;
+        }
+
+        log::debug!("[CustomProperties] Finished writing.");
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "property")]
+pub struct CustomProperty<'a> {
+    #[xml(attr = "fmtid")]
+    pub fmtid: Cow<'a, str>,
+    /// Unique id of the property, from 2
+    #[xml(attr = "pid")]
+    pub pid: u32,
+    #[xml(attr = "name")]
+    pub name: Option<Cow<'a, str>>,
+    /// Name of the bookmark the property is linked to
+    #[xml(attr = "linkTarget")]
+    pub link_target: Option<Cow<'a, str>>,
+    /// Value of the property, missing if of a type other than those supported
+    #[xml(
+        child = "vt:lpwstr",
+        child = "vt:lpstr",
+        child = "vt:i4",
+        child = "vt:i8",
+        child = "vt:r8",
+        child = "vt:bool",
+        child = "vt:filetime"
+    )]
+    pub value: Option<CustomValue<'a>>,
+}
+
+impl<'a> CustomProperty<'a> {
+    fn has_name(&self, name: &str) -> bool {
+        self.name
+            .as_deref()
+            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Value of a custom property
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum CustomValue<'a> {
+    #[xml(tag = "vt:lpwstr")]
+    Text(Lpwstr<'a>),
+    /// Text written by older applications
+    #[xml(tag = "vt:lpstr")]
+    AnsiText(Lpstr<'a>),
+    #[xml(tag = "vt:i4")]
+    Integer(I4),
+    #[xml(tag = "vt:i8")]
+    Long(I8),
+    #[xml(tag = "vt:r8")]
+    Number(R8),
+    #[xml(tag = "vt:bool")]
+    Bool(Bool),
+    #[xml(tag = "vt:filetime")]
+    DateTime(Filetime<'a>),
+}
+
+impl<'a> CustomValue<'a> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CustomValue::Text(Lpwstr { value }) | CustomValue::AnsiText(Lpstr { value }) => {
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            CustomValue::Integer(I4 { value }) => Some(*value as i64),
+            CustomValue::Long(I8 { value }) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            CustomValue::Number(R8 { value }) => Some(*value),
+            _ => self.as_i64().map(|n| n as f64),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CustomValue::Bool(Bool { value }) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_date_time(&self) -> Option<DateTime> {
+        match self {
+            CustomValue::DateTime(Filetime { value }) => DateTime::parse_iso8601(value),
+            _ => None,
+        }
+    }
+}
+
+/// Shows the value like a `DOCPROPERTY` field does.
+impl fmt::Display for CustomValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomValue::Text(Lpwstr { value }) | CustomValue::AnsiText(Lpstr { value }) => {
+                write!(f, "{}", value)
+            }
+            CustomValue::Integer(I4 { value }) => write!(f, "{}", value),
+            CustomValue::Long(I8 { value }) => write!(f, "{}", value),
+            CustomValue::Number(R8 { value }) => write!(f, "{}", value),
+            CustomValue::Bool(Bool { value }) => write!(f, "{}", if *value { "Y" } else { "N" }),
+            CustomValue::DateTime(Filetime { value }) => match DateTime::parse_iso8601(value) {
+                Some(date) => write!(f, "{}", date.format("M/d/yyyy")),
+                None => write!(f, "{}", value),
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a str> for CustomValue<'a> {
+    fn from(value: &'a str) -> Self {
+        CustomValue::Text(Lpwstr {
+            value: value.into(),
+        })
+    }
+}
+
+impl From<String> for CustomValue<'_> {
+    fn from(value: String) -> Self {
+        CustomValue::Text(Lpwstr {
+            value: value.into(),
+        })
+    }
+}
+
+impl From<i32> for CustomValue<'_> {
+    fn from(value: i32) -> Self {
+        CustomValue::Integer(I4 { value })
+    }
+}
+
+impl From<i64> for CustomValue<'_> {
+    fn from(value: i64) -> Self {
+        CustomValue::Long(I8 { value })
+    }
+}
+
+impl From<f64> for CustomValue<'_> {
+    fn from(value: f64) -> Self {
+        CustomValue::Number(R8 { value })
+    }
+}
+
+impl From<bool> for CustomValue<'_> {
+    fn from(value: bool) -> Self {
+        CustomValue::Bool(Bool { value })
+    }
+}
+
+impl From<DateTime> for CustomValue<'_> {
+    fn from(value: DateTime) -> Self {
+        CustomValue::DateTime(Filetime {
+            value: value.to_iso8601().into(),
+        })
+    }
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:lpwstr")]
+pub struct Lpwstr<'a> {
+    #[xml(text)]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:lpstr")]
+pub struct Lpstr<'a> {
+    #[xml(text)]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:i4")]
+pub struct I4 {
+    #[xml(text)]
+    pub value: i32,
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:i8")]
+pub struct I8 {
+    #[xml(text)]
+    pub value: i64,
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:r8")]
+pub struct R8 {
+    #[xml(text)]
+    pub value: f64,
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:bool")]
+pub struct Bool {
+    #[xml(text)]
+    pub value: bool,
+}
+
+/// A date and time in UTC, like `2024-03-01T09:30:00Z`
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:filetime")]
+pub struct Filetime<'a> {
+    #[xml(text)]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the value of a custom property.
+    pub fn custom_property(&self, name: &str) -> Option<&CustomValue<'a>> {
+        self.custom_properties.as_ref()?.get(name)
+    }
+
+    /// Sets the value of a custom property, adding the custom properties part if there
+    /// is none.
+    pub fn set_custom_property<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: Into<Cow<'a, str>>,
+        V: Into<CustomValue<'a>>,
+    {
+        self.custom_properties
+            .get_or_insert_with(Default::default)
+            .set(name, value);
+        self
+    }
+}
+
+#[cfg(test)]
+use crate::__xml_test_suites;
+
+#[cfg(test)]
+__xml_test_suites!(
+    CustomProperties,
+    CustomProperties::default(),
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        "\r\n",
+        r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes"/>"#
+    ),
+    CustomProperties {
+        properties: vec![CustomProperty {
+            fmtid: FMTID_USER_DEFINED_PROPERTIES.into(),
+            pid: 2,
+            name: Some("Signed".into()),
+            link_target: None,
+            value: Some(true.into()),
+        }],
+    },
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        "\r\n",
+        r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes"><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Signed"><vt:bool>true</vt:bool></property></Properties>"#
+    ),
+);
@@ -0,0 +1,550 @@
+//! Custom XML parts
+//!
+//! The corresponding ZIP items are `/customXml/item{n}.xml`, with their properties in
+//! `/customXml/itemProps{n}.xml`. Content controls with a `w:dataBinding` show the
+//! value of a node in one of these parts.
+//!
+//! ```rust
+//! use docx_rust::{document::*, Docx};
+//!
+//! let mut docx = Docx::default();
+//! let id = docx.add_custom_xml("<order><customer>Acme</customer></order>");
+//! docx.document.push(
+//!     SDT::default().property(
+//!         SDTProperty::default()
+//!             .tag("customer")
+//!             .data_binding(DataBinding::new("/order[1]/customer[1]").store_item_id(id.clone())),
+//!     ),
+//! );
+//!
+//! let count = docx
+//!     .set_custom_xml(&id, "<order><customer>Globex</customer></order>")
+//!     .unwrap();
+//! assert_eq!(count, 1);
+//! assert_eq!(docx.document.body.text(), "Globex");
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use hard_xml::{
+    utils::{xml_escape, xml_unescape},
+    xmlparser::{ElementEnd, Token, Tokenizer},
+    XmlError, XmlResult,
+};
+
+use crate::{Docx, DocxResult};
+
+/// A custom XML part along with its properties
+#[derive(Debug, Default, Clone)]
+pub struct CustomXmlPart<'a> {
+    /// Id data bindings refer to in their `w:storeItemID`, like `{8E3F1A2B-...}`
+    pub item_id: Option<String>,
+    /// Content of `/customXml/item{n}.xml`
+    pub xml: Cow<'a, str>,
+    /// Content of `/customXml/itemProps{n}.xml`, generated from `item_id` if `None`
+    pub properties: Option<Cow<'a, str>>,
+}
+
+impl<'a> CustomXmlPart<'a> {
+    /// Creates a part with a new item id.
+    pub fn new<T: Into<Cow<'a, str>>>(xml: T) -> Self {
+        CustomXmlPart {
+            item_id: Some(new_guid()),
+            xml: xml.into(),
+            properties: None,
+        }
+    }
+
+    /// Reads the item id from the properties part.
+    pub(crate) fn with_properties(xml: Cow<'a, str>, properties: Option<Cow<'a, str>>) -> Self {
+        let item_id = properties
+            .as_deref()
+            .and_then(|p| XmlDocument::parse(p).ok())
+            .and_then(|p| {
+                p.root
+                    .attributes
+                    .iter()
+                    .find(|(name, _)| local_name(name) == "itemID")
+                    .map(|(_, value)| value.clone())
+            });
+        CustomXmlPart {
+            item_id,
+            xml,
+            properties,
+        }
+    }
+
+    /// Returns the properties part to write.
+    pub(crate) fn properties_xml(&self) -> Cow<'_, str> {
+        match &self.properties {
+            Some(properties) => Cow::Borrowed(properties.as_ref()),
+            None => Cow::Owned(format!(
+                "{}<ds:datastoreItem ds:itemID=\"{}\" xmlns:ds=\"{}\"><ds:schemaRefs/></ds:datastoreItem>",
+                crate::schema::SCHEMA_XML,
+                xml_escape(self.item_id.as_deref().unwrap_or_default()),
+                crate::schema::SCHEMA_CUSTOM_XML_DATA_STORE
+            )),
+        }
+    }
+
+    /// Returns the text of the node an XPath like `/ns0:order[1]/ns0:customer[1]` selects.
+    ///
+    /// `prefix_mappings` declares the namespace prefixes of the XPath,
+    /// like `xmlns:ns0='urn:orders'`.
+    pub fn get(&self, xpath: &str, prefix_mappings: &str) -> Option<String> {
+        let doc = XmlDocument::parse(&self.xml).ok()?;
+        doc.get(xpath, &parse_mappings(prefix_mappings))
+    }
+
+    /// Changes the text of the node an XPath selects, returns `false` if there is no such node.
+    pub fn set(&mut self, xpath: &str, prefix_mappings: &str, value: &str) -> DocxResult<bool> {
+        let mut doc = XmlDocument::parse(&self.xml)?;
+        if !doc.set(xpath, &parse_mappings(prefix_mappings), value) {
+            return Ok(false);
+        }
+        self.xml = Cow::Owned(doc.to_string());
+        Ok(true)
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Adds a custom XML part and returns its item id.
+    pub fn add_custom_xml<T: Into<Cow<'a, str>>>(&mut self, xml: T) -> String {
+        let part = CustomXmlPart::new(xml);
+        let id = part.item_id.clone().unwrap_or_default();
+        self.custom_xml.push(part);
+        id
+    }
+
+    /// Returns the custom XML part with an item id, ignoring case.
+    pub fn custom_xml_part(&self, item_id: &str) -> Option<&CustomXmlPart<'a>> {
+        self.custom_xml.iter().find(|p| same_id(p, item_id))
+    }
+
+    /// Replaces the XML of a custom XML part, or adds a part with this id, then
+    /// updates the content controls bound to it.
+    ///
+    /// Returns the number of updated controls.
+    pub fn set_custom_xml<T: Into<Cow<'a, str>>>(
+        &mut self,
+        item_id: &str,
+        xml: T,
+    ) -> DocxResult<usize> {
+        let xml = xml.into();
+        XmlDocument::parse(&xml)?;
+        match self.custom_xml.iter_mut().find(|p| same_id(p, item_id)) {
+            Some(part) => part.xml = xml,
+            None => self.custom_xml.push(CustomXmlPart {
+                item_id: Some(item_id.to_string()),
+                xml,
+                properties: None,
+            }),
+        }
+        Ok(self.update_bound_controls())
+    }
+}
+
+fn same_id(part: &CustomXmlPart, item_id: &str) -> bool {
+    part.item_id
+        .as_deref()
+        .is_some_and(|id| id.eq_ignore_ascii_case(item_id))
+}
+
+/// Parses `xmlns:ns0='urn:a' xmlns:ns1="urn:b"` into prefixes and namespaces.
+pub(crate) fn parse_mappings(mappings: &str) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    let mut rest = mappings;
+    while let Some(start) = rest.find("xmlns:") {
+        rest = &rest[start + 6..];
+        let Some((prefix, after)) = rest.split_once('=') else {
+            break;
+        };
+        let after = after.trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+            break;
+        };
+        let Some(end) = after[1..].find(quote) else {
+            break;
+        };
+        result.insert(prefix.trim().to_string(), after[1..end + 1].to_string());
+        rest = &after[end + 2..];
+    }
+    result
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn prefix(name: &str) -> &str {
+    name.split_once(':').map_or("", |(p, _)| p)
+}
+
+/// A parsed XML document, which keeps everything before the root element as is
+#[derive(Debug, Clone)]
+pub(crate) struct XmlDocument {
+    prolog: String,
+    root: Element,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Element {
+    /// Qualified name, like `ns0:order`
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+    /// Comments, processing instructions and CDATA sections, as written
+    Raw(String),
+}
+
+impl Element {
+    fn text(&self) -> String {
+        self.children
+            .iter()
+            .map(|c| match c {
+                Node::Element(e) => e.text(),
+                Node::Text(t) => t.clone(),
+                Node::Raw(r) => r
+                    .strip_prefix("<![CDATA[")
+                    .and_then(|r| r.strip_suffix("]]>"))
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+            .collect()
+    }
+
+    /// Adds the namespace declarations of the element to `scope`.
+    fn declare(&self, scope: &mut HashMap<String, String>) {
+        for (name, value) in &self.attributes {
+            if name == "xmlns" {
+                scope.insert(String::new(), value.clone());
+            } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+                scope.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        out.push('<');
+        out.push_str(&self.name);
+        for (name, value) in &self.attributes {
+            out.push(' ');
+            out.push_str(name);
+            out.push_str("=\"");
+            out.push_str(&xml_escape(value));
+            out.push('"');
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+        for child in &self.children {
+            match child {
+                Node::Element(e) => e.write(out),
+                Node::Text(t) => out.push_str(&xml_escape(t)),
+                Node::Raw(r) => out.push_str(r),
+            }
+        }
+        out.push_str("</");
+        out.push_str(&self.name);
+        out.push('>');
+    }
+}
+
+impl XmlDocument {
+    pub(crate) fn parse(xml: &str) -> XmlResult<Self> {
+        let mut prolog = None;
+        let mut stack: Vec<Element> = Vec::new();
+        let mut root = None;
+
+        for token in Tokenizer::from(xml) {
+            match token? {
+                Token::ElementStart {
+                    prefix,
+                    local,
+                    span,
+                } => {
+                    if prolog.is_none() {
+                        prolog = Some(xml[..span.start()].to_string());
+                    }
+                    let name = if prefix.is_empty() {
+                        local.to_string()
+                    } else {
+                        format!("{}:{}", prefix, local)
+                    };
+                    stack.push(Element {
+                        name,
+                        attributes: Vec::new(),
+                        children: Vec::new(),
+                    });
+                }
+                Token::Attribute {
+                    prefix,
+                    local,
+                    value,
+                    ..
+                } => {
+                    if let Some(element) = stack.last_mut() {
+                        let name = if prefix.is_empty() {
+                            local.to_string()
+                        } else {
+                            format!("{}:{}", prefix, local)
+                        };
+                        element
+                            .attributes
+                            .push((name, xml_unescape(&value)?.into_owned()));
+                    }
+                }
+                Token::ElementEnd { end, .. } => {
+                    if let ElementEnd::Close(close_prefix, local) = end {
+                        let name = stack.last().map(|e| e.name.as_str()).unwrap_or_default();
+                        if local_name(name) != local.as_str()
+                            || prefix(name) != close_prefix.as_str()
+                        {
+                            return Err(XmlError::TagMismatch {
+                                expected: name.to_string(),
+                                found: local.to_string(),
+                            });
+                        }
+                    }
+                    if matches!(end, ElementEnd::Open) {
+                        continue;
+                    }
+                    let Some(element) = stack.pop() else {
+                        return Err(XmlError::UnexpectedEof);
+                    };
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(Node::Element(element)),
+                        None => root = Some(element),
+                    }
+                }
+                Token::Text { text } => {
+                    if let Some(element) = stack.last_mut() {
+                        element
+                            .children
+                            .push(Node::Text(xml_unescape(&text)?.into_owned()));
+                    }
+                }
+                Token::Cdata { span, .. }
+                | Token::Comment { span, .. }
+                | Token::ProcessingInstruction { span, .. } => {
+                    if let Some(element) = stack.last_mut() {
+                        element.children.push(Node::Raw(span.to_string()));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(XmlDocument {
+            prolog: prolog.unwrap_or_default(),
+            root: root.ok_or(XmlError::UnexpectedEof)?,
+        })
+    }
+
+    /// Finds the child indices leading to the element an XPath selects,
+    /// and the attribute name if it selects an attribute.
+    fn resolve(
+        &self,
+        xpath: &str,
+        mappings: &HashMap<String, String>,
+    ) -> Option<(Vec<usize>, Option<String>)> {
+        let steps: Vec<&str> = xpath.strip_prefix('/')?.split('/').collect();
+        let mut path = Vec::new();
+        let mut scope = HashMap::new();
+        let mut current: Option<&Element> = None;
+
+        for (i, step) in steps.iter().enumerate() {
+            let last = i == steps.len() - 1;
+            if last && *step == "text()" {
+                break;
+            }
+            if let Some(attribute) = step.strip_prefix('@').filter(|_| last) {
+                let element = current?;
+                let (namespace, local) = split_step(attribute, mappings)?;
+                let name = element.attributes.iter().find_map(|(name, _)| {
+                    let ns = match prefix(name) {
+                        "" => None,
+                        p => scope.get(p).map(String::as_str),
+                    };
+                    (local_name(name) == local && ns == namespace).then(|| name.clone())
+                })?;
+                return Some((path, Some(name)));
+            }
+
+            let (name, position) = match step.split_once('[') {
+                Some((name, predicate)) => {
+                    (name, predicate.strip_suffix(']')?.trim().parse().ok()?)
+                }
+                None => (*step, 1usize),
+            };
+            let (namespace, local) = split_step(name, mappings)?;
+
+            let candidates: Vec<(usize, &Element)> = match current {
+                None => vec![(0, &self.root)],
+                Some(parent) => parent
+                    .children
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, c)| match c {
+                        Node::Element(e) => Some((i, e)),
+                        _ => None,
+                    })
+                    .collect(),
+            };
+            let mut matched = 0;
+            let mut found = None;
+            for (index, element) in candidates {
+                let mut element_scope = scope.clone();
+                element.declare(&mut element_scope);
+                let ns = element_scope.get(prefix(&element.name)).map(String::as_str);
+                if (local == "*" || local_name(&element.name) == local) && ns == namespace {
+                    matched += 1;
+                    if matched == position {
+                        found = Some((index, element, element_scope));
+                        break;
+                    }
+                }
+            }
+            let (index, element, element_scope) = found?;
+            if current.is_some() {
+                path.push(index);
+            }
+            scope = element_scope;
+            current = Some(element);
+        }
+        current.map(|_| (path, None))
+    }
+
+    fn element(&self, path: &[usize]) -> Option<&Element> {
+        path.iter()
+            .try_fold(&self.root, |e, i| match e.children.get(*i) {
+                Some(Node::Element(e)) => Some(e),
+                _ => None,
+            })
+    }
+
+    fn element_mut(&mut self, path: &[usize]) -> Option<&mut Element> {
+        path.iter()
+            .try_fold(&mut self.root, |e, i| match e.children.get_mut(*i) {
+                Some(Node::Element(e)) => Some(e),
+                _ => None,
+            })
+    }
+
+    pub(crate) fn get(&self, xpath: &str, mappings: &HashMap<String, String>) -> Option<String> {
+        let (path, attribute) = self.resolve(xpath, mappings)?;
+        let element = self.element(&path)?;
+        match attribute {
+            Some(name) => element
+                .attributes
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.clone()),
+            None => Some(element.text()),
+        }
+    }
+
+    pub(crate) fn set(
+        &mut self,
+        xpath: &str,
+        mappings: &HashMap<String, String>,
+        value: &str,
+    ) -> bool {
+        let Some((path, attribute)) = self.resolve(xpath, mappings) else {
+            return false;
+        };
+        let Some(element) = self.element_mut(&path) else {
+            return false;
+        };
+        match attribute {
+            Some(name) => match element.attributes.iter_mut().find(|(n, _)| *n == name) {
+                Some(attribute) => attribute.1 = value.to_string(),
+                None => return false,
+            },
+            None => element.children = vec![Node::Text(value.to_string())],
+        }
+        true
+    }
+}
+
+impl std::fmt::Display for XmlDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = self.prolog.clone();
+        self.root.write(&mut out);
+        f.write_str(&out)
+    }
+}
+
+/// Splits a step like `ns0:customer` into its namespace and local name.
+fn split_step<'s, 'm>(
+    step: &'s str,
+    mappings: &'m HashMap<String, String>,
+) -> Option<(Option<&'m str>, &'s str)> {
+    match step.split_once(':') {
+        Some((prefix, local)) => Some((Some(mappings.get(prefix)?.as_str()), local)),
+        None => Some((None, step)),
+    }
+}
+
+/// Returns a random (version 4) GUID in braces, like `{8E3F1A2B-0C4D-4E5F-9A6B-7C8D9E0F1A2B}`.
+fn new_guid() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("no random source available for the item id");
+    bytes[6] = (bytes[6] & 0x0F) | 0x40;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    format!(
+        "{{{}-{}-{}-{}-{}}}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[test]
+fn custom_xml_test() {
+    let mut part = CustomXmlPart::new(
+        r#"<?xml version="1.0"?><o:order xmlns:o="urn:orders" id="7"><o:line>A &amp; B</o:line><o:line>C</o:line></o:order>"#,
+    );
+    let mappings = "xmlns:ns0='urn:orders'";
+    assert_eq!(
+        part.get("/ns0:order[1]/ns0:line[2]", mappings),
+        Some("C".to_string())
+    );
+    assert_eq!(
+        part.get("/ns0:order/ns0:line[1]/text()", mappings),
+        Some("A & B".to_string())
+    );
+    assert_eq!(
+        part.get("/ns0:order[1]/@id", mappings),
+        Some("7".to_string())
+    );
+    assert_eq!(part.get("/order[1]", ""), None);
+    assert_eq!(part.get("/ns0:order[1]/ns0:line[3]", mappings), None);
+
+    assert!(part
+        .set("/ns0:order[1]/ns0:line[2]", mappings, "<D>")
+        .unwrap());
+    assert!(part.set("/ns0:order[1]/@id", mappings, "8").unwrap());
+    assert!(!part.set("/ns0:order[1]/@missing", mappings, "8").unwrap());
+    assert_eq!(
+        part.xml,
+        r#"<?xml version="1.0"?><o:order xmlns:o="urn:orders" id="8"><o:line>A &amp; B</o:line><o:line>&lt;D&gt;</o:line></o:order>"#
+    );
+    let id = part.item_id.unwrap();
+    assert_eq!(id.len(), 38);
+    assert_eq!(&id[15..16], "4");
+    assert!(matches!(&id[20..21], "8" | "9" | "A" | "B"));
+    assert_ne!(Some(id), CustomXmlPart::new("").item_id);
+}
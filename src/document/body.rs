@@ -3,7 +3,9 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Borrow;
 
 use crate::__xml_test_suites;
-use crate::document::{Paragraph, Run, Table, TableCell};
+use crate::document::{
+    BookmarkEnd, BookmarkStart, Paragraph, PermEnd, PermStart, Run, Table, TableCell,
+};
 use crate::formatting::SectionProperty;
 
 use super::SDT;
@@ -16,7 +18,16 @@ use super::SDT;
 #[xml(tag = "w:body")]
 pub struct Body<'a> {
     /// Specifies the contents of the body of the document.
-    #[xml(child = "w:p", child = "w:tbl", child = "w:sectPr", child = "w:sdt")]
+    #[xml(
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:sdt",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:permStart",
+        child = "w:permEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
@@ -26,6 +37,9 @@ impl<'a> Body<'a> {
         self
     }
 
+    /// Joins the text of the paragraphs with `\r\n`, leaving out tables.
+    ///
+    /// See [`Docx::to_text`](crate::Docx::to_text) for more control over the output.
     pub fn text(&self) -> String {
         let v: Vec<_> = self
             .content
@@ -37,6 +51,10 @@ impl<'a> Body<'a> {
                 BodyContent::Sdt(sdt) => Some(sdt.text()),
                 BodyContent::TableCell(_) => None,
                 BodyContent::Run(_) => None,
+                BodyContent::BookmarkStart(_)
+                | BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => None,
             })
             .collect();
         v.join("\r\n")
@@ -67,6 +85,10 @@ impl<'a> Body<'a> {
                 BodyContent::Sdt(_) => {}
                 BodyContent::TableCell(_) => {}
                 BodyContent::Run(_) => {}
+                BodyContent::BookmarkStart(_)
+                | BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => {}
             }
         }
         Ok(())
@@ -94,6 +116,7 @@ impl<'a> Body<'a> {
 /// A set of elements that can be contained in the body
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum BodyContent<'a> {
     #[xml(tag = "w:p")]
     Paragraph(Paragraph<'a>),
@@ -107,6 +130,14 @@ pub enum BodyContent<'a> {
     TableCell(TableCell<'a>),
     #[xml(tag = "w:r")]
     Run(Run<'a>),
+    #[xml(tag = "w:bookmarkStart")]
+    BookmarkStart(BookmarkStart<'a>),
+    #[xml(tag = "w:bookmarkEnd")]
+    BookmarkEnd(BookmarkEnd<'a>),
+    #[xml(tag = "w:permStart")]
+    PermStart(PermStart<'a>),
+    #[xml(tag = "w:permEnd")]
+    PermEnd(PermEnd<'a>),
 }
 
 __xml_test_suites!(
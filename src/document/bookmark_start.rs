@@ -14,11 +14,19 @@ pub struct BookmarkStart<'a> {
     /// Specifies the bookmark name.
     #[xml(attr = "w:name")]
     pub name: Option<Cow<'a, str>>,
+    /// Specifies the first table column of a bookmark covering table cells.
+    #[xml(attr = "w:colFirst")]
+    pub col_first: Option<usize>,
+    /// Specifies the last table column of a bookmark covering table cells.
+    #[xml(attr = "w:colLast")]
+    pub col_last: Option<usize>,
 }
 
 impl<'a> BookmarkStart<'a> {
     __setter!(id: Option<Cow<'a, str>>);
     __setter!(name: Option<Cow<'a, str>>);
+    __setter!(col_first: Option<usize>);
+    __setter!(col_last: Option<usize>);
 }
 
 __xml_test_suites!(
@@ -29,4 +37,10 @@ __xml_test_suites!(
     r#"<w:bookmarkStart w:id="id"/>"#,
     BookmarkStart::default().name("name"),
     r#"<w:bookmarkStart w:name="name"/>"#,
+    BookmarkStart::default()
+        .id("0")
+        .name("cells")
+        .col_first(1usize)
+        .col_last(2usize),
+    r#"<w:bookmarkStart w:id="0" w:name="cells" w:colFirst="1" w:colLast="2"/>"#,
 );
@@ -7,7 +7,9 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::io::Write;
 
 use crate::__xml_test_suites;
-use crate::schema::{SCHEMA_MAIN, SCHEMA_RELATIONSHIPS_DOCUMENT, SCHEMA_WORDML_14, SCHEMA_WP};
+use crate::schema::{
+    SCHEMA_MAIN, SCHEMA_RELATIONSHIPS_DOCUMENT, SCHEMA_WORDML_14, SCHEMA_WORDML_15, SCHEMA_WP,
+};
 
 use crate::document::{Body, BodyContent};
 
@@ -33,6 +35,22 @@ impl<'a> XmlWrite for Document<'a> {
         let Document { body } = self;
 
         log::debug!("[Document] Started writing.");
+
+        Document::write_start(writer)?;
+
+        body.to_writer(writer)?;
+
+        writer.write_element_end_close("w:document")?;
+
+        log::debug!("[Document] Finished writing.");
+
+        Ok(())
+    }
+}
+
+impl Document<'_> {
+    /// Writes the declaration and the start tag of the root element.
+    pub(crate) fn write_start<W: Write>(writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let _ = write!(writer.inner, "{}", crate::schema::SCHEMA_XML);
 
         writer.write_element_start("w:document")?;
@@ -41,18 +59,14 @@ impl<'a> XmlWrite for Document<'a> {
 
         writer.write_attribute("xmlns:w14", SCHEMA_WORDML_14)?;
 
+        writer.write_attribute("xmlns:w15", SCHEMA_WORDML_15)?;
+
         writer.write_attribute("xmlns:wp", SCHEMA_WP)?;
 
         writer.write_attribute("xmlns:r", SCHEMA_RELATIONSHIPS_DOCUMENT)?;
 
         writer.write_element_end_open()?;
 
-        body.to_writer(writer)?;
-
-        writer.write_element_end_close("w:document")?;
-
-        log::debug!("[Document] Finished writing.");
-
         Ok(())
     }
 }
@@ -61,10 +75,11 @@ __xml_test_suites!(
     Document,
     Document::default(),
     format!(
-        r#"{}<w:document xmlns:w="{}" xmlns:w14="{}" xmlns:wp="{}" xmlns:r="{}"><w:body/></w:document>"#,
+        r#"{}<w:document xmlns:w="{}" xmlns:w14="{}" xmlns:w15="{}" xmlns:wp="{}" xmlns:r="{}"><w:body/></w:document>"#,
         crate::schema::SCHEMA_XML,
         SCHEMA_MAIN,
         SCHEMA_WORDML_14,
+        SCHEMA_WORDML_15,
         SCHEMA_WP,
         SCHEMA_RELATIONSHIPS_DOCUMENT
     )
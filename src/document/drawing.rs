@@ -5,7 +5,11 @@ use std::borrow::Cow;
 use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__define_enum, __string_enum};
+use crate::{
+    __define_enum, __string_enum,
+    formatting::Emu,
+    schema::{SCHEMA_DRAWINGML, SCHEMA_PICTURE},
+};
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -18,6 +22,83 @@ pub struct Drawing<'a> {
     pub inline: Option<Inline<'a>>,
 }
 
+impl<'a> Drawing<'a> {
+    /// Creates an inline picture of the image referenced by relationship `embed`.
+    ///
+    /// `id` must be unique among the drawings of the document,
+    /// `cx` and `cy` are the displayed size, in EMUs unless typed otherwise.
+    pub fn inline_picture<N, E, X, Y>(id: isize, name: N, embed: E, cx: X, cy: Y) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        E: Into<Cow<'a, str>>,
+        X: Into<Emu>,
+        Y: Into<Emu>,
+    {
+        let Extent { cx, cy } = Extent::new(cx, cy);
+        let name = name.into();
+        let picture = Picture {
+            a: SCHEMA_PICTURE.into(),
+            nv_pic_pr: NvPicPr {
+                c_nv_pr: Some(CNvPr {
+                    id: Some(id),
+                    name: Some(name.clone()),
+                    descr: None,
+                }),
+                c_nv_pic_pr: Some(CNvPicPr {}),
+            },
+            fill: BlipFill {
+                blip: Blip {
+                    embed: embed.into(),
+                    cstate: None,
+                },
+                stretch: Some(Stretch {
+                    fill_rect: Some(FillRect {}),
+                }),
+            },
+            sp_pr: SpPr {
+                xfrm: Some(Xfrm {
+                    offset: Some(Offset {
+                        x: Some(0),
+                        y: Some(0),
+                    }),
+                    ext: Some(Ext {
+                        cx: Some(cx as isize),
+                        cy: Some(cy as isize),
+                    }),
+                }),
+                prst_geom: Some(PrstGeom {
+                    prst: Some("rect".into()),
+                    av_lst: Some(AvList {}),
+                }),
+            },
+        };
+
+        Drawing {
+            anchor: None,
+            inline: Some(Inline {
+                dist_t: Some(0),
+                dist_b: Some(0),
+                dist_l: Some(0),
+                dist_r: Some(0),
+                extent: Some(Extent { cx, cy }),
+                doc_property: DocPr {
+                    id: Some(id),
+                    name: Some(name),
+                    descr: None,
+                },
+                graphic: Some(Graphic {
+                    a: SCHEMA_DRAWINGML.into(),
+                    data: GraphicData {
+                        uri: SCHEMA_PICTURE.into(),
+                        children: vec![picture],
+                    },
+                }),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "wp:anchor")]
@@ -421,3 +502,12 @@ pub struct Extent {
     #[xml(default, attr = "cy")]
     pub cy: u64,
 }
+
+impl Extent {
+    pub fn new<X: Into<Emu>, Y: Into<Emu>>(cx: X, cy: Y) -> Self {
+        Extent {
+            cx: cx.into().into(),
+            cy: cy.into().into(),
+        }
+    }
+}
@@ -30,7 +30,16 @@ pub struct EndNote<'a> {
     pub ty: Option<NoteSeparator>,
     #[xml(attr = "w:id")]
     pub id: Option<isize>,
-    #[xml(child = "w:sdt", child = "w:p", child = "w:tbl", child = "w:sectPr")]
+    #[xml(
+        child = "w:sdt",
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:permStart",
+        child = "w:permEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
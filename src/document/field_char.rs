@@ -1,6 +1,8 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__string_enum, __xml_test_suites};
+use crate::{__setter, __string_enum, __xml_test_suites};
+
+use super::FormFieldData;
 
 /// Break
 ///
@@ -12,15 +14,31 @@ use crate::{__string_enum, __xml_test_suites};
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:fldChar")]
-pub struct FieldChar {
+pub struct FieldChar<'a> {
     /// Specifies the break type of this break.
     #[xml(attr = "w:fldCharType")]
     pub ty: Option<CharType>,
+    /// Specifies that the result of the field must not be recalculated.
+    #[xml(attr = "w:fldLock")]
+    pub lock: Option<bool>,
+    /// Specifies that the result of the field is outdated.
+    #[xml(attr = "w:dirty")]
+    pub dirty: Option<bool>,
+    /// Properties of a legacy form field, in its `begin` character
+    #[xml(child = "w:ffData")]
+    pub form_data: Option<FormFieldData<'a>>,
+}
+
+impl<'a> FieldChar<'a> {
+    __setter!(form_data: Option<FormFieldData<'a>>);
 }
 
-impl<T: Into<Option<CharType>>> From<T> for FieldChar {
+impl<T: Into<Option<CharType>>> From<T> for FieldChar<'_> {
     fn from(val: T) -> Self {
-        FieldChar { ty: val.into() }
+        FieldChar {
+            ty: val.into(),
+            ..Default::default()
+        }
     }
 }
 
@@ -50,4 +68,12 @@ __xml_test_suites!(
     FieldChar,
     FieldChar::from(CharType::Begin),
     r#"<w:fldChar w:fldCharType="begin"/>"#,
+    FieldChar {
+        ty: Some(CharType::Begin),
+        dirty: Some(true),
+        ..Default::default()
+    },
+    r#"<w:fldChar w:fldCharType="begin" w:dirty="true"/>"#,
+    FieldChar::from(CharType::Begin).form_data(FormFieldData::new("Name")),
+    r#"<w:fldChar w:fldCharType="begin"><w:ffData><w:name w:val="Name"/></w:ffData></w:fldChar>"#,
 );
@@ -7,7 +7,7 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::io::Write;
 
 use crate::__xml_test_suites;
-use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14};
+use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14, SCHEMA_WORDML_15};
 
 use crate::document::BodyContent;
 
@@ -16,7 +16,16 @@ use crate::document::BodyContent;
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:ftr")]
 pub struct Footer<'a> {
-    #[xml(child = "w:sdt", child = "w:p", child = "w:tbl", child = "w:sectPr")]
+    #[xml(
+        child = "w:sdt",
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:permStart",
+        child = "w:permEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
@@ -40,6 +49,8 @@ impl<'a> XmlWrite for Footer<'a> {
 
         writer.write_attribute("xmlns:w14", SCHEMA_WORDML_14)?;
 
+        writer.write_attribute("xmlns:w15", SCHEMA_WORDML_15)?;
+
         writer.write_element_end_open()?;
 
         for c in content {
@@ -58,10 +69,11 @@ __xml_test_suites!(
     Footer,
     Footer::default(),
     format!(
-        r#"{}<w:ftr xmlns:w="{}" xmlns:w14="{}"></w:ftr>"#,
+        r#"{}<w:ftr xmlns:w="{}" xmlns:w14="{}" xmlns:w15="{}"></w:ftr>"#,
         crate::schema::SCHEMA_XML,
         SCHEMA_MAIN,
-        SCHEMA_WORDML_14
+        SCHEMA_WORDML_14,
+        SCHEMA_WORDML_15
     )
     .as_str(),
 );
@@ -0,0 +1,406 @@
+use hard_xml::{XmlRead, XmlWrite};
+use std::borrow::Cow;
+
+use crate::{__define_enum, __setter, __xml_test_suites};
+
+/// Form field properties of a `FORMTEXT`, `FORMCHECKBOX` or `FORMDROPDOWN` field
+///
+/// They are stored in the `begin` field character of the field.
+///
+/// ```rust
+/// use docx_rust::document::*;
+///
+/// let data = FormFieldData::new("Country").drop_down_list(
+///     FormDropDownList::default()
+///         .push("Germany")
+///         .push("France"),
+/// );
+/// ```
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:ffData")]
+pub struct FormFieldData<'a> {
+    /// Name of the field, also used for the bookmark around it
+    #[xml(child = "w:name")]
+    pub name: Option<FormFieldName<'a>>,
+    #[xml(child = "w:enabled")]
+    pub enabled: Option<FormFieldEnabled>,
+    /// Recalculates the fields of the document when leaving this one.
+    #[xml(child = "w:calcOnExit")]
+    pub calc_on_exit: Option<FormFieldCalcOnExit>,
+    #[xml(child = "w:entryMacro")]
+    pub entry_macro: Option<FormFieldEntryMacro<'a>>,
+    #[xml(child = "w:exitMacro")]
+    pub exit_macro: Option<FormFieldExitMacro<'a>>,
+    #[xml(child = "w:helpText")]
+    pub help_text: Option<FormFieldHelpText<'a>>,
+    #[xml(child = "w:statusText")]
+    pub status_text: Option<FormFieldStatusText<'a>>,
+    #[xml(child = "w:checkBox")]
+    pub check_box: Option<FormCheckBox>,
+    #[xml(child = "w:ddList")]
+    pub drop_down_list: Option<FormDropDownList<'a>>,
+    #[xml(child = "w:textInput")]
+    pub text_input: Option<FormTextInput<'a>>,
+}
+
+impl<'a> FormFieldData<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(name: T) -> Self {
+        FormFieldData {
+            name: Some(FormFieldName { value: name.into() }),
+            ..Default::default()
+        }
+    }
+
+    __setter!(enabled: Option<FormFieldEnabled>);
+    __setter!(help_text: Option<FormFieldHelpText<'a>>);
+    __setter!(status_text: Option<FormFieldStatusText<'a>>);
+    __setter!(check_box: Option<FormCheckBox>);
+    __setter!(drop_down_list: Option<FormDropDownList<'a>>);
+    __setter!(text_input: Option<FormTextInput<'a>>);
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|n| n.value.as_ref())
+    }
+
+    /// Returns whether the field can be filled in, `true` by default.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().is_none_or(|e| e.value != Some(false))
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:name")]
+pub struct FormFieldName<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for FormFieldName<'a> {
+    fn from(val: T) -> Self {
+        FormFieldName { value: val.into() }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:enabled")]
+pub struct FormFieldEnabled {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl From<bool> for FormFieldEnabled {
+    fn from(val: bool) -> Self {
+        FormFieldEnabled { value: Some(val) }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:calcOnExit")]
+pub struct FormFieldCalcOnExit {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:entryMacro")]
+pub struct FormFieldEntryMacro<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:exitMacro")]
+pub struct FormFieldExitMacro<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+/// Text shown when pressing F1 in the field
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:helpText")]
+pub struct FormFieldHelpText<'a> {
+    /// `text`, or `autoText` if the value names a building block
+    #[xml(attr = "w:type")]
+    pub ty: Option<Cow<'a, str>>,
+    #[xml(attr = "w:val")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+/// Text shown in the status bar while in the field
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:statusText")]
+pub struct FormFieldStatusText<'a> {
+    /// `text`, or `autoText` if the value names a building block
+    #[xml(attr = "w:type")]
+    pub ty: Option<Cow<'a, str>>,
+    #[xml(attr = "w:val")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+/// Check box of a `FORMCHECKBOX` field
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:checkBox")]
+pub struct FormCheckBox {
+    /// Size in half-points
+    #[xml(child = "w:size")]
+    pub size: Option<FormCheckBoxSize>,
+    /// Sizes the check box like the text around it.
+    #[xml(child = "w:sizeAuto")]
+    pub size_auto: Option<FormCheckBoxSizeAuto>,
+    #[xml(child = "w:default")]
+    pub default: Option<FormCheckBoxDefault>,
+    /// Current state, the default state if omitted
+    #[xml(child = "w:checked")]
+    pub checked: Option<FormCheckBoxChecked>,
+}
+
+impl FormCheckBox {
+    __setter!(checked: Option<FormCheckBoxChecked>);
+
+    pub fn default_checked(mut self, checked: bool) -> Self {
+        self.default = Some(checked.into());
+        self
+    }
+
+    pub fn is_checked(&self) -> bool {
+        match (&self.checked, &self.default) {
+            (Some(checked), _) => checked.value != Some(false),
+            (None, Some(default)) => default.value != Some(false),
+            (None, None) => false,
+        }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:size")]
+pub struct FormCheckBoxSize {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:sizeAuto")]
+pub struct FormCheckBoxSizeAuto {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:default")]
+pub struct FormCheckBoxDefault {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl From<bool> for FormCheckBoxDefault {
+    fn from(val: bool) -> Self {
+        FormCheckBoxDefault { value: Some(val) }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:checked")]
+pub struct FormCheckBoxChecked {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl From<bool> for FormCheckBoxChecked {
+    fn from(val: bool) -> Self {
+        FormCheckBoxChecked { value: Some(val) }
+    }
+}
+
+/// Drop-down list of a `FORMDROPDOWN` field
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:ddList")]
+pub struct FormDropDownList<'a> {
+    /// Index of the selected entry, the default entry if omitted
+    #[xml(child = "w:result")]
+    pub result: Option<FormDropDownResult>,
+    /// Index of the default entry, the first one if omitted
+    #[xml(child = "w:default")]
+    pub default: Option<FormDropDownDefault>,
+    #[xml(child = "w:listEntry")]
+    pub entries: Vec<FormListEntry<'a>>,
+}
+
+impl<'a> FormDropDownList<'a> {
+    pub fn push<T: Into<FormListEntry<'a>>>(mut self, entry: T) -> Self {
+        self.entries.push(entry.into());
+        self
+    }
+
+    /// Returns the position of the selected entry.
+    pub fn selected_index(&self) -> usize {
+        self.result
+            .as_ref()
+            .map(|r| r.value)
+            .or(self.default.as_ref().map(|d| d.value))
+            .unwrap_or_default()
+    }
+
+    /// Returns the text of the selected entry.
+    pub fn selected(&self) -> Option<&str> {
+        self.entries
+            .get(self.selected_index())
+            .map(|e| e.value.as_ref())
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:result")]
+pub struct FormDropDownResult {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:default")]
+pub struct FormDropDownDefault {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:listEntry")]
+pub struct FormListEntry<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for FormListEntry<'a> {
+    fn from(val: T) -> Self {
+        FormListEntry { value: val.into() }
+    }
+}
+
+/// Text box of a `FORMTEXT` field
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:textInput")]
+pub struct FormTextInput<'a> {
+    #[xml(child = "w:type")]
+    pub ty: Option<FormTextInputType>,
+    /// Text shown until the field is filled in
+    #[xml(child = "w:default")]
+    pub default: Option<FormTextInputDefault<'a>>,
+    /// Maximum number of characters
+    #[xml(child = "w:maxLength")]
+    pub max_length: Option<FormTextInputMaxLength>,
+    /// Format of the text, like `UPPERCASE` or `0.00` for numbers
+    #[xml(child = "w:format")]
+    pub format: Option<FormTextInputFormat<'a>>,
+}
+
+impl<'a> FormTextInput<'a> {
+    __setter!(ty: Option<FormTextInputType>);
+    pub fn default_text<T: Into<Cow<'a, str>>>(mut self, text: T) -> Self {
+        self.default = Some(FormTextInputDefault { value: text.into() });
+        self
+    }
+
+    __setter!(max_length: Option<FormTextInputMaxLength>);
+    __setter!(format: Option<FormTextInputFormat<'a>>);
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:type")]
+pub struct FormTextInputType {
+    #[xml(attr = "w:val")]
+    pub value: Option<FormTextType>,
+}
+
+impl From<FormTextType> for FormTextInputType {
+    fn from(val: FormTextType) -> Self {
+        FormTextInputType { value: Some(val) }
+    }
+}
+
+__define_enum! {
+    FormTextType {
+        Regular = "regular", // Any text
+        Number = "number", // A number
+        Date = "date", // A date
+        CurrentDate = "currentDate", // The current date, can't be filled in
+        CurrentTime = "currentTime", // The current time, can't be filled in
+        Calculated = "calculated", // Result of an expression, can't be filled in
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:default")]
+pub struct FormTextInputDefault<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for FormTextInputDefault<'a> {
+    fn from(val: T) -> Self {
+        FormTextInputDefault { value: val.into() }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:maxLength")]
+pub struct FormTextInputMaxLength {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+impl From<usize> for FormTextInputMaxLength {
+    fn from(val: usize) -> Self {
+        FormTextInputMaxLength { value: val }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:format")]
+pub struct FormTextInputFormat<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for FormTextInputFormat<'a> {
+    fn from(val: T) -> Self {
+        FormTextInputFormat { value: val.into() }
+    }
+}
+
+__xml_test_suites!(
+    FormFieldData,
+    FormFieldData::new("Name").text_input(
+        FormTextInput::default()
+            .default_text("Your name")
+            .max_length(20)
+    ),
+    r#"<w:ffData><w:name w:val="Name"/><w:textInput><w:default w:val="Your name"/><w:maxLength w:val="20"/></w:textInput></w:ffData>"#,
+    FormFieldData::new("Agree")
+        .enabled(false)
+        .check_box(FormCheckBox::default().default_checked(true)),
+    r#"<w:ffData><w:name w:val="Agree"/><w:enabled w:val="false"/><w:checkBox><w:default w:val="true"/></w:checkBox></w:ffData>"#,
+    FormFieldData::new("Country")
+        .drop_down_list(FormDropDownList::default().push("Germany").push("France")),
+    r#"<w:ffData><w:name w:val="Country"/><w:ddList><w:listEntry w:val="Germany"/><w:listEntry w:val="France"/></w:ddList></w:ffData>"#,
+);
@@ -8,7 +8,7 @@ use std::borrow::Borrow;
 use std::io::Write;
 
 use crate::__xml_test_suites;
-use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14};
+use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14, SCHEMA_WORDML_15};
 
 use crate::document::BodyContent;
 
@@ -17,7 +17,16 @@ use crate::document::BodyContent;
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:hdr")]
 pub struct Header<'a> {
-    #[xml(child = "w:p", child = "w:tbl", child = "w:sectPr", child = "w:sdt")]
+    #[xml(
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:sdt",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:permStart",
+        child = "w:permEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
@@ -49,6 +58,10 @@ impl<'a> Header<'a> {
                 BodyContent::Sdt(_) => {}
                 BodyContent::TableCell(_) => {}
                 BodyContent::Run(_) => {}
+                BodyContent::BookmarkStart(_)
+                | BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => {}
             }
         }
         Ok(())
@@ -68,6 +81,8 @@ impl<'a> XmlWrite for Header<'a> {
 
         writer.write_attribute("xmlns:w14", SCHEMA_WORDML_14)?;
 
+        writer.write_attribute("xmlns:w15", SCHEMA_WORDML_15)?;
+
         writer.write_element_end_open()?;
 
         for c in content {
@@ -86,10 +101,11 @@ __xml_test_suites!(
     Header,
     Header::default(),
     format!(
-        r#"{}<w:hdr xmlns:w="{}" xmlns:w14="{}"></w:hdr>"#,
+        r#"{}<w:hdr xmlns:w="{}" xmlns:w14="{}" xmlns:w15="{}"></w:hdr>"#,
         crate::schema::SCHEMA_XML,
         SCHEMA_MAIN,
-        SCHEMA_WORDML_14
+        SCHEMA_WORDML_14,
+        SCHEMA_WORDML_15
     )
     .as_str(),
 );
@@ -3,7 +3,11 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__setter, __xml_test_suites, document::bidir::BidirectionalEmbedding, document::Run};
+use crate::{
+    __setter, __xml_test_suites,
+    document::bidir::BidirectionalEmbedding,
+    document::{Run, Text},
+};
 
 /// The root element of a hyperlink within the paragraph
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -29,6 +33,17 @@ impl<'a> Hyperlink<'a> {
     __setter!(anchor: Option<Cow<'a, str>>);
     __setter!(content: Option<Run<'a>>);
 
+    /// Creates a link to a bookmark of the document.
+    pub fn to_bookmark<N, T>(name: N, text: T) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        T: Into<Text<'a>>,
+    {
+        Hyperlink::default()
+            .anchor(name)
+            .content(Run::default().push_text(text))
+    }
+
     pub fn text(&self) -> String {
         self.iter_text()
             .map(|c| c.to_string())
@@ -70,4 +85,6 @@ __xml_test_suites!(
     r#"<w:hyperlink w:anchor="anchor"/>"#,
     Hyperlink::default().content(Run::default()),
     r#"<w:hyperlink><w:r/></w:hyperlink>"#,
+    Hyperlink::to_bookmark("anchor", "text"),
+    r#"<w:hyperlink w:anchor="anchor"><w:r><w:t>text</w:t></w:r></w:hyperlink>"#,
 );
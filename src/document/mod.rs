@@ -6,12 +6,14 @@ mod r#break;
 mod comment_range;
 mod comments;
 mod date;
+#[allow(clippy::module_inception)]
 mod document;
 mod drawing;
 mod endnotes;
 mod field_char;
 mod footer;
 mod footnotes;
+mod form_field;
 mod grid_column;
 mod header;
 mod header_footer_reference;
@@ -19,8 +21,10 @@ mod hyperlink;
 mod instrtext;
 mod numbering;
 mod paragraph;
+mod permission;
 mod run;
 mod sdt;
+mod simple_field;
 mod sym;
 mod tab;
 mod table;
@@ -30,9 +34,11 @@ mod table_row;
 mod text;
 mod theme;
 
+pub use self::instrtext::{DelInstrText, InstrText};
 pub use self::{
     body::*, bookmark_end::*, bookmark_start::*, comment_range::*, comments::*, document::*,
-    drawing::*, endnotes::*, field_char::*, footer::*, footnotes::*, grid_column::*, header::*,
-    header_footer_reference::*, hyperlink::*, numbering::*, paragraph::*, r#break::*, run::*,
-    sdt::*, tab::*, table::*, table_cell::*, table_grid::*, table_row::*, text::*, theme::*,
+    drawing::*, endnotes::*, field_char::*, footer::*, footnotes::*, form_field::*, grid_column::*,
+    header::*, header_footer_reference::*, hyperlink::*, numbering::*, paragraph::*, permission::*,
+    r#break::*, run::*, sdt::*, simple_field::*, tab::*, table::*, table_cell::*, table_grid::*,
+    table_row::*, text::*, theme::*,
 };
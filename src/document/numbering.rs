@@ -160,7 +160,7 @@ impl<'a> Numbering<'a> {
     /// This helper function takes an numbering id that is provided in a paragraph, looks up
     /// the details in the numbering section and merges it with the abstract numbering to get
     /// a complete AbstractNum object.
-    pub fn numbering_details(&self, id: isize) -> Option<AbstractNum> {
+    pub fn numbering_details(&self, id: isize) -> Option<AbstractNum<'_>> {
         self.numberings.iter().find_map(|n| {
             if n.num_id != Some(id) || n.abstract_num_id.is_none() {
                 None
@@ -182,7 +182,7 @@ impl<'a> Numbering<'a> {
                                     an.levels.iter_mut().find(|level| level.i_level == *i_level)
                                 {
                                     level.start = Some(LevelStart {
-                                        value: start_override.as_ref().unwrap().value.clone(),
+                                        value: start_override.as_ref().unwrap().value,
                                     });
                                 }
                             }
@@ -197,6 +197,78 @@ impl<'a> Numbering<'a> {
     }
 }
 
+impl Numbering<'_> {
+    /// Adds a nine-level list definition and returns the id of a new numbering using it,
+    /// which paragraphs can refer to in their [`NumberingProperty`](crate::formatting::NumberingProperty).
+    ///
+    /// Ordered lists cycle through `decimal`, `lowerLetter` and `lowerRoman` numbers,
+    /// bullet lists through `•`, `◦` and `▪`.
+    pub fn add_list(&mut self, ordered: bool) -> isize {
+        let abstract_num_id = self
+            .abstract_numberings
+            .iter()
+            .filter_map(|an| an.abstract_num_id)
+            .max()
+            .map_or(0, |id| id + 1);
+        let num_id = self
+            .numberings
+            .iter()
+            .filter_map(|n| n.num_id)
+            .max()
+            .map_or(1, |id| id + 1);
+
+        let levels = (0..9)
+            .map(|i| {
+                let (format, text) = if ordered {
+                    let format = ["decimal", "lowerLetter", "lowerRoman"][i % 3];
+                    (format, format!("%{}.", i + 1))
+                } else {
+                    ("bullet", ["•", "◦", "▪"][i % 3].to_string())
+                };
+                Level {
+                    i_level: Some(i as isize),
+                    start: Some(LevelStart { value: Some(1) }),
+                    number_format: Some(NumFmt {
+                        value: format.into(),
+                    }),
+                    level_text: Some(LevelText { value: text.into() }),
+                    justification: Some(LevelJustification {
+                        value: JustificationVal::Left,
+                    }),
+                    p_pr: Some(PPr {
+                        indent: Some(Indent {
+                            left: Some(720 * (i as isize + 1)),
+                            hanging: Some(360),
+                            ..Default::default()
+                        }),
+                    }),
+                    r_pr: Vec::new(),
+                }
+            })
+            .collect();
+
+        self.abstract_numberings.push(AbstractNum {
+            abstract_num_id: Some(abstract_num_id),
+            nsid: Nsid {
+                value: format!("{:08X}", 0x1D0C_0000 + abstract_num_id).into(),
+            },
+            multi_level_type: MultiLevelType {
+                value: "hybridMultilevel".into(),
+            },
+            levels,
+        });
+        self.numberings.push(Num {
+            num_id: Some(num_id),
+            abstract_num_id: Some(AbstractNumId {
+                value: Some(abstract_num_id),
+            }),
+            level_overrides: Vec::new(),
+        });
+
+        num_id
+    }
+}
+
 impl<'a> XmlWrite for Numbering<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let Numbering {
@@ -233,7 +305,6 @@ impl<'a> XmlWrite for Numbering<'a> {
 }
 
 #[cfg(test)]
-
 const NUMBERING_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
     <w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
         <w:abstractNum w:abstractNumId="990">
@@ -326,6 +397,21 @@ fn xml_parsing() {
     );
 }
 
+#[test]
+fn add_list() {
+    let mut numbering = Numbering::from_str(NUMBERING_XML).unwrap();
+    let id = numbering.add_list(true);
+    assert_eq!(id, 1002);
+
+    let details = numbering.numbering_details(id).unwrap();
+    assert_eq!(details.abstract_num_id, Some(99412));
+    assert_eq!(details.levels.len(), 9);
+    assert_eq!(details.levels[1].level_text.as_ref().unwrap().value, "%2.");
+
+    let id = Numbering::default().add_list(false);
+    assert_eq!(id, 1);
+}
+
 #[test]
 fn find_numbering_details() {
     let numbering = Numbering::from_str(NUMBERING_XML).unwrap();
@@ -374,7 +460,7 @@ fn xml_writing() {
                 }
             } else {
                 result.push(c);
-                last_was_whitespace_or_bracket = if c == '>' || c == '"' { true } else { false };
+                last_was_whitespace_or_bracket = c == '>' || c == '"';
             }
         }
 
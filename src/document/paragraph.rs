@@ -6,8 +6,8 @@ use std::borrow::{Borrow, Cow};
 use crate::{
     __setter, __xml_test_suites,
     document::{
-        BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart, Hyperlink, Run, RunContent,
-        Text, SDT,
+        BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart, Hyperlink, PermEnd,
+        PermStart, Run, RunContent, SimpleField, Text, SDT,
     },
     formatting::ParagraphProperty,
 };
@@ -55,7 +55,10 @@ pub struct Paragraph<'a> {
         child = "w:hyperlink",
         child = "w:bookmarkStart",
         child = "w:bookmarkEnd",
-        child = "w:sdt"
+        child = "w:permStart",
+        child = "w:permEnd",
+        child = "w:sdt",
+        child = "w:fldSimple"
     )]
     pub content: Vec<ParagraphContent<'a>>,
 }
@@ -93,6 +96,7 @@ impl<'a> Paragraph<'a> {
                     ParagraphContent::Run(run) => Some(run.iter_text()),
                     ParagraphContent::Link(link) => Some(link.iter_text()),
                     ParagraphContent::SDT(sdt) => Some(sdt.iter_text()),
+                    ParagraphContent::SimpleField(field) => Some(Box::new(field.iter_text())),
                     _ => None,
                 })
                 .flatten(),
@@ -117,11 +121,8 @@ impl<'a> Paragraph<'a> {
         I: Borrow<(S, S)>,
     {
         for content in self.content.iter_mut() {
-            match content {
-                ParagraphContent::Run(r) => {
-                    r.replace_text(dic)?;
-                }
-                _ => {}
+            if let ParagraphContent::Run(r) = content {
+                r.replace_text(dic)?;
             }
         }
 
@@ -130,6 +131,7 @@ impl<'a> Paragraph<'a> {
 }
 
 /// A set of elements that can be contained as the content of a paragraph.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum ParagraphContent<'a> {
@@ -145,8 +147,14 @@ pub enum ParagraphContent<'a> {
     BookmarkStart(BookmarkStart<'a>),
     #[xml(tag = "w:bookmarkEnd")]
     BookmarkEnd(BookmarkEnd<'a>),
+    #[xml(tag = "w:permStart")]
+    PermStart(PermStart<'a>),
+    #[xml(tag = "w:permEnd")]
+    PermEnd(PermEnd<'a>),
     #[xml(tag = "w:sdt")]
     SDT(SDT<'a>),
+    #[xml(tag = "w:fldSimple")]
+    SimpleField(SimpleField<'a>),
 }
 
 __xml_test_suites!(
@@ -0,0 +1,84 @@
+use hard_xml::{XmlRead, XmlWrite};
+use std::borrow::Cow;
+
+use crate::{__setter, __string_enum, __xml_test_suites};
+
+/// Beginning of a range that stays editable in a protected document
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:permStart")]
+pub struct PermStart<'a> {
+    /// Specifies a unique identifier for the range.
+    #[xml(attr = "w:id")]
+    pub id: Option<Cow<'a, str>>,
+    /// Specifies the group of users allowed to edit the range.
+    #[xml(attr = "w:edGrp")]
+    pub editor_group: Option<EditorGroup>,
+    /// Specifies the single user allowed to edit the range.
+    #[xml(attr = "w:ed")]
+    pub editor: Option<Cow<'a, str>>,
+    /// Specifies the first table column of a range covering table cells.
+    #[xml(attr = "w:colFirst")]
+    pub col_first: Option<usize>,
+    /// Specifies the last table column of a range covering table cells.
+    #[xml(attr = "w:colLast")]
+    pub col_last: Option<usize>,
+}
+
+impl<'a> PermStart<'a> {
+    __setter!(id: Option<Cow<'a, str>>);
+    __setter!(editor_group: Option<EditorGroup>);
+    __setter!(editor: Option<Cow<'a, str>>);
+    __setter!(col_first: Option<usize>);
+    __setter!(col_last: Option<usize>);
+}
+
+/// End of a range that stays editable in a protected document
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:permEnd")]
+pub struct PermEnd<'a> {
+    /// Specifies the identifier of the range.
+    #[xml(attr = "w:id")]
+    pub id: Option<Cow<'a, str>>,
+}
+
+impl<'a> PermEnd<'a> {
+    __setter!(id: Option<Cow<'a, str>>);
+}
+
+/// Users allowed to edit a range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorGroup {
+    None,
+    Everyone,
+    Administrators,
+    Contributors,
+    Editors,
+    Owners,
+    Current,
+}
+
+__string_enum! {
+    EditorGroup {
+        None = "none",
+        Everyone = "everyone",
+        Administrators = "administrators",
+        Contributors = "contributors",
+        Editors = "editors",
+        Owners = "owners",
+        Current = "current",
+    }
+}
+
+__xml_test_suites!(
+    PermStart,
+    PermStart::default(),
+    r#"<w:permStart/>"#,
+    PermStart::default()
+        .id("0")
+        .editor_group(EditorGroup::Everyone),
+    r#"<w:permStart w:id="0" w:edGrp="everyone"/>"#,
+    PermStart::default().id("1").editor("DOMAIN\\alice"),
+    r#"<w:permStart w:id="1" w:ed="DOMAIN\alice"/>"#,
+);
@@ -4,13 +4,13 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::{Borrow, Cow};
 
 use crate::{
-    __setter, __xml_test_suites,
+    __define_enum, __define_struct, __setter, __xml_test_suites,
     document::{
         drawing::Drawing, field_char::FieldChar, instrtext::InstrText, r#break::Break,
         r#break::LastRenderedPageBreak, tab::Tab, text::Text,
     },
     formatting::CharacterProperty,
-    DocxResult, __define_enum, __define_struct,
+    DocxResult,
 };
 
 use super::{
@@ -161,15 +161,12 @@ impl<'a> Run<'a> {
         I: Borrow<(S, S)>,
     {
         for c in self.content.iter_mut() {
-            match c {
-                RunContent::Text(t) => {
-                    let mut tc = t.text.to_string();
-                    for p in dic {
-                        tc = tc.replace(p.borrow().0.as_ref(), p.borrow().1.as_ref());
-                    }
-                    t.text = tc.into();
+            if let RunContent::Text(t) = c {
+                let mut tc = t.text.to_string();
+                for p in dic {
+                    tc = tc.replace(p.borrow().0.as_ref(), p.borrow().1.as_ref());
                 }
-                _ => {}
+                t.text = tc.into();
             }
         }
 
@@ -180,6 +177,7 @@ impl<'a> Run<'a> {
 /// A set of elements that can be contained as the content of a run.
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum RunContent<'a> {
     #[xml(tag = "w:br")]
     Break(Break),
@@ -230,7 +228,7 @@ pub enum RunContent<'a> {
     //#[xml(tag = "w:pict")]
     //Pict(Pict<'a>),
     #[xml(tag = "w:fldChar")]
-    FieldChar(FieldChar),
+    FieldChar(FieldChar<'a>),
     //#[xml(tag = "w:ruby")]
     //Ruby(Ruby<'a>),
     #[xml(tag = "w:footnoteReference")]
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites};
+use crate::{__define_enum, __setter, __xml_test_suites, formatting::CharacterProperty};
 
 use super::BodyContent;
 
@@ -36,6 +36,29 @@ impl<'a> SDT<'a> {
     __setter!(end_property: Option<SDTEndProperty>);
     __setter!(content: Option<SDTContent<'a>>);
 
+    /// Returns the tag of the control.
+    pub fn tag(&self) -> Option<&str> {
+        self.property
+            .as_ref()
+            .and_then(|p| p.tag.as_ref())
+            .map(|t| t.value.as_ref())
+    }
+
+    /// Returns the friendly name of the control.
+    pub fn alias(&self) -> Option<&str> {
+        self.property
+            .as_ref()
+            .and_then(|p| p.alias.as_ref())
+            .map(|a| a.value.as_ref())
+    }
+
+    /// Returns the type of the control, rich text if it has no properties.
+    pub fn control_type(&self) -> SDTType {
+        self.property
+            .as_ref()
+            .map_or(SDTType::RichText, |p| p.control_type())
+    }
+
     pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
         Box::new(
             self.content
@@ -54,16 +77,152 @@ impl<'a> SDT<'a> {
     }
 }
 
-/// Section Property
+/// Properties of a content control
+///
+/// At most one of the type elements, like `text` or `drop_down_list`, is present.
+/// Without any of them the control holds rich text.
 ///
+/// ```rust
+/// use docx_rust::document::*;
+///
+/// let property = SDTProperty::default()
+///     .alias("Customer name")
+///     .tag("customer")
+///     .text(SDTText::default())
+///     .data_binding(DataBinding::new("/ns0:order[1]/ns0:customer[1]"));
+/// ```
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:sdtPr")]
 pub struct SDTProperty<'a> {
+    /// Formatting of the content that replaces the placeholder
+    #[xml(child = "w:rPr")]
+    pub run_property: Option<CharacterProperty<'a>>,
+    /// Friendly name shown by Word
+    #[xml(child = "w:alias")]
+    pub alias: Option<SDTAlias<'a>>,
+    /// Name used by programs to find the control
+    #[xml(child = "w:tag")]
+    pub tag: Option<SDTTag<'a>>,
     #[xml(child = "w:id")]
     pub id: Option<STDId>,
+    #[xml(child = "w:lock")]
+    pub lock: Option<SDTLock>,
+    #[xml(child = "w:placeholder")]
+    pub placeholder: Option<SDTPlaceholder<'a>>,
+    /// The control is removed once its content is edited.
+    #[xml(child = "w:temporary")]
+    pub temporary: Option<SDTTemporary>,
+    /// The content is the placeholder text.
+    #[xml(child = "w:showingPlcHdr")]
+    pub showing_placeholder: Option<SDTShowingPlaceholder>,
+    #[xml(child = "w:dataBinding")]
+    pub data_binding: Option<DataBinding<'a>>,
+    #[xml(child = "w:equation")]
+    pub equation: Option<SDTEquation>,
+    #[xml(child = "w:comboBox")]
+    pub combo_box: Option<SDTComboBox<'a>>,
+    #[xml(child = "w:date")]
+    pub date: Option<SDTDate<'a>>,
     #[xml(child = "w:docPartObj")]
     pub doc_part_obj: Option<DocPartObj<'a>>,
+    #[xml(child = "w:docPartList")]
+    pub doc_part_list: Option<DocPartList<'a>>,
+    #[xml(child = "w:dropDownList")]
+    pub drop_down_list: Option<SDTDropDownList<'a>>,
+    #[xml(child = "w:picture")]
+    pub picture: Option<SDTPicture>,
+    #[xml(child = "w:richText")]
+    pub rich_text: Option<SDTRichText>,
+    #[xml(child = "w:text")]
+    pub text: Option<SDTText>,
+    #[xml(child = "w:citation")]
+    pub citation: Option<SDTCitation>,
+    #[xml(child = "w:group")]
+    pub group: Option<SDTGroup>,
+    #[xml(child = "w:bibliography")]
+    pub bibliography: Option<SDTBibliography>,
+    #[xml(child = "w14:checkbox")]
+    pub checkbox: Option<SDTCheckbox<'a>>,
+    #[xml(child = "w15:repeatingSection")]
+    pub repeating_section: Option<RepeatingSection<'a>>,
+    #[xml(child = "w15:repeatingSectionItem")]
+    pub repeating_section_item: Option<RepeatingSectionItem>,
+}
+
+impl<'a> SDTProperty<'a> {
+    __setter!(run_property: Option<CharacterProperty<'a>>);
+    __setter!(alias: Option<SDTAlias<'a>>);
+    __setter!(tag: Option<SDTTag<'a>>);
+    __setter!(id: Option<STDId>);
+    __setter!(lock: Option<SDTLock>);
+    __setter!(placeholder: Option<SDTPlaceholder<'a>>);
+    __setter!(showing_placeholder: Option<SDTShowingPlaceholder>);
+    __setter!(data_binding: Option<DataBinding<'a>>);
+    __setter!(combo_box: Option<SDTComboBox<'a>>);
+    __setter!(date: Option<SDTDate<'a>>);
+    __setter!(doc_part_obj: Option<DocPartObj<'a>>);
+    __setter!(drop_down_list: Option<SDTDropDownList<'a>>);
+    __setter!(picture: Option<SDTPicture>);
+    __setter!(rich_text: Option<SDTRichText>);
+    __setter!(text: Option<SDTText>);
+    __setter!(group: Option<SDTGroup>);
+    __setter!(checkbox: Option<SDTCheckbox<'a>>);
+    __setter!(repeating_section: Option<RepeatingSection<'a>>);
+    __setter!(repeating_section_item: Option<RepeatingSectionItem>);
+
+    /// Returns the type of the control.
+    pub fn control_type(&self) -> SDTType {
+        if self.text.is_some() {
+            SDTType::PlainText
+        } else if self.drop_down_list.is_some() {
+            SDTType::DropDownList
+        } else if self.combo_box.is_some() {
+            SDTType::ComboBox
+        } else if self.date.is_some() {
+            SDTType::Date
+        } else if self.checkbox.is_some() {
+            SDTType::Checkbox
+        } else if self.picture.is_some() {
+            SDTType::Picture
+        } else if self.repeating_section.is_some() {
+            SDTType::RepeatingSection
+        } else if self.repeating_section_item.is_some() {
+            SDTType::RepeatingSectionItem
+        } else if self.group.is_some() {
+            SDTType::Group
+        } else if self.doc_part_obj.is_some() || self.doc_part_list.is_some() {
+            SDTType::DocPart
+        } else if self.equation.is_some() {
+            SDTType::Equation
+        } else if self.citation.is_some() {
+            SDTType::Citation
+        } else if self.bibliography.is_some() {
+            SDTType::Bibliography
+        } else {
+            SDTType::RichText
+        }
+    }
+}
+
+/// The kind of content a control accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SDTType {
+    RichText,
+    PlainText,
+    DropDownList,
+    ComboBox,
+    Date,
+    Checkbox,
+    Picture,
+    RepeatingSection,
+    RepeatingSectionItem,
+    Group,
+    /// Building block, like a table of contents
+    DocPart,
+    Equation,
+    Citation,
+    Bibliography,
 }
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -74,16 +233,412 @@ pub struct STDId {
     pub id: Option<isize>,
 }
 
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:alias")]
+pub struct SDTAlias<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SDTAlias<'a> {
+    fn from(val: T) -> Self {
+        SDTAlias { value: val.into() }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tag")]
+pub struct SDTTag<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SDTTag<'a> {
+    fn from(val: T) -> Self {
+        SDTTag { value: val.into() }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:lock")]
+pub struct SDTLock {
+    #[xml(attr = "w:val")]
+    pub value: Option<SDTLockType>,
+}
+
+impl From<SDTLockType> for SDTLock {
+    fn from(val: SDTLockType) -> Self {
+        SDTLock { value: Some(val) }
+    }
+}
+
+__define_enum! {
+    SDTLockType {
+        SdtLocked = "sdtLocked", // The control cannot be deleted
+        ContentLocked = "contentLocked", // The content cannot be edited
+        Unlocked = "unlocked", // No locking
+        SdtContentLocked = "sdtContentLocked", // Neither the control nor its content can be changed
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:placeholder")]
+pub struct SDTPlaceholder<'a> {
+    /// Building block holding the placeholder text
+    #[xml(child = "w:docPart")]
+    pub doc_part: Option<DocPart<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:docPart")]
+pub struct DocPart<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:temporary")]
+pub struct SDTTemporary {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:showingPlcHdr")]
+pub struct SDTShowingPlaceholder {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+/// Binds the content of a control to a node of a custom XML part
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:dataBinding")]
+pub struct DataBinding<'a> {
+    /// Namespace prefixes used in the XPath, like `xmlns:ns0='urn:orders'`
+    #[xml(attr = "w:prefixMappings")]
+    pub prefix_mappings: Option<Cow<'a, str>>,
+    #[xml(attr = "w:xpath")]
+    pub xpath: Cow<'a, str>,
+    /// Item id of the custom XML part, like `{8E3F1A2B-...}`
+    #[xml(attr = "w:storeItemID")]
+    pub store_item_id: Option<Cow<'a, str>>,
+}
+
+impl<'a> DataBinding<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(xpath: T) -> Self {
+        DataBinding {
+            xpath: xpath.into(),
+            ..Default::default()
+        }
+    }
+
+    __setter!(prefix_mappings: Option<Cow<'a, str>>);
+    __setter!(store_item_id: Option<Cow<'a, str>>);
+}
+
+/// Plain text control
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:text")]
+pub struct SDTText {
+    /// Allows line breaks.
+    #[xml(attr = "w:multiLine")]
+    pub multi_line: Option<bool>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:richText")]
+pub struct SDTRichText {}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:picture")]
+pub struct SDTPicture {}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:group")]
+pub struct SDTGroup {}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:equation")]
+pub struct SDTEquation {}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:citation")]
+pub struct SDTCitation {}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:bibliography")]
+pub struct SDTBibliography {}
+
+/// Drop-down list control, only one of the items can be chosen
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:dropDownList")]
+pub struct SDTDropDownList<'a> {
+    #[xml(attr = "w:lastValue")]
+    pub last_value: Option<Cow<'a, str>>,
+    #[xml(child = "w:listItem")]
+    pub items: Vec<SDTListItem<'a>>,
+}
+
+impl<'a> SDTDropDownList<'a> {
+    pub fn push(mut self, item: SDTListItem<'a>) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// Combo box control, one of the items or any other text can be entered
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:comboBox")]
+pub struct SDTComboBox<'a> {
+    #[xml(attr = "w:lastValue")]
+    pub last_value: Option<Cow<'a, str>>,
+    #[xml(child = "w:listItem")]
+    pub items: Vec<SDTListItem<'a>>,
+}
+
+impl<'a> SDTComboBox<'a> {
+    pub fn push(mut self, item: SDTListItem<'a>) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:listItem")]
+pub struct SDTListItem<'a> {
+    /// Text shown in the document, defaults to the value
+    #[xml(attr = "w:displayText")]
+    pub display_text: Option<Cow<'a, str>>,
+    /// Value stored in bound custom XML
+    #[xml(attr = "w:value")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+impl<'a> SDTListItem<'a> {
+    pub fn new<D: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(display_text: D, value: V) -> Self {
+        SDTListItem {
+            display_text: Some(display_text.into()),
+            value: Some(value.into()),
+        }
+    }
+
+    /// Returns the text shown when the item is chosen.
+    pub fn display(&self) -> &str {
+        self.display_text
+            .as_deref()
+            .or(self.value.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Date picker control
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:date")]
+pub struct SDTDate<'a> {
+    /// The chosen date, like `2024-03-01T00:00:00Z`
+    #[xml(attr = "w:fullDate")]
+    pub full_date: Option<Cow<'a, str>>,
+    /// Picture of the shown date, like `M/d/yyyy`
+    #[xml(child = "w:dateFormat")]
+    pub date_format: Option<SDTDateFormat<'a>>,
+    #[xml(child = "w:lid")]
+    pub lid: Option<SDTLanguageId<'a>>,
+    #[xml(child = "w:storeMappedDataAs")]
+    pub store_mapped_data_as: Option<SDTStoreMappedDataAs<'a>>,
+    #[xml(child = "w:calendar")]
+    pub calendar: Option<SDTCalendar<'a>>,
+}
+
+impl<'a> SDTDate<'a> {
+    __setter!(full_date: Option<Cow<'a, str>>);
+    __setter!(date_format: Option<SDTDateFormat<'a>>);
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:dateFormat")]
+pub struct SDTDateFormat<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SDTDateFormat<'a> {
+    fn from(val: T) -> Self {
+        SDTDateFormat { value: val.into() }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:lid")]
+pub struct SDTLanguageId<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:storeMappedDataAs")]
+pub struct SDTStoreMappedDataAs<'a> {
+    /// `dateTime`, `date` or `text`
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:calendar")]
+pub struct SDTCalendar<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+/// Check box control
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w14:checkbox")]
+pub struct SDTCheckbox<'a> {
+    #[xml(child = "w14:checked")]
+    pub checked: Option<CheckboxChecked<'a>>,
+    #[xml(child = "w14:checkedState")]
+    pub checked_state: Option<CheckboxCheckedState<'a>>,
+    #[xml(child = "w14:uncheckedState")]
+    pub unchecked_state: Option<CheckboxUncheckedState<'a>>,
+}
+
+impl<'a> SDTCheckbox<'a> {
+    pub fn is_checked(&self) -> bool {
+        self.checked
+            .as_ref()
+            .is_some_and(|c| matches!(c.value.as_ref(), "1" | "true" | "on"))
+    }
+
+    /// Returns the symbol shown for a state, ☒ and ☐ by default.
+    pub fn symbol(&self, checked: bool) -> char {
+        let (state, default) = if checked {
+            (self.checked_state.as_ref().map(|s| &s.value), '\u{2612}')
+        } else {
+            (self.unchecked_state.as_ref().map(|s| &s.value), '\u{2610}')
+        };
+        state
+            .and_then(|code| u32::from_str_radix(code, 16).ok())
+            .and_then(char::from_u32)
+            .unwrap_or(default)
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w14:checked")]
+pub struct CheckboxChecked<'a> {
+    #[xml(attr = "w14:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w14:checkedState")]
+pub struct CheckboxCheckedState<'a> {
+    /// Hexadecimal code of the symbol
+    #[xml(attr = "w14:val")]
+    pub value: Cow<'a, str>,
+    #[xml(attr = "w14:font")]
+    pub font: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w14:uncheckedState")]
+pub struct CheckboxUncheckedState<'a> {
+    /// Hexadecimal code of the symbol
+    #[xml(attr = "w14:val")]
+    pub value: Cow<'a, str>,
+    #[xml(attr = "w14:font")]
+    pub font: Option<Cow<'a, str>>,
+}
+
+/// Repeating section control, its content are repeating section items
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:repeatingSection")]
+pub struct RepeatingSection<'a> {
+    #[xml(child = "w15:sectionTitle")]
+    pub section_title: Option<RepeatingSectionTitle<'a>>,
+    #[xml(child = "w15:doNotAllowInsertDeleteSection")]
+    pub do_not_allow_insert_delete_section: Option<DoNotAllowInsertDeleteSection>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:sectionTitle")]
+pub struct RepeatingSectionTitle<'a> {
+    #[xml(attr = "w15:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:doNotAllowInsertDeleteSection")]
+pub struct DoNotAllowInsertDeleteSection {
+    #[xml(attr = "w15:val")]
+    pub value: Option<bool>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:repeatingSectionItem")]
+pub struct RepeatingSectionItem {}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:docPartObj")]
 pub struct DocPartObj<'a> {
     #[xml(child = "w:docPartGallery")]
     pub doc_part_gallery: Option<DocPartGallery<'a>>,
+    #[xml(child = "w:docPartCategory")]
+    pub doc_part_category: Option<DocPartCategory<'a>>,
     #[xml(child = "w:docPartUnique")]
     pub doc_part_unique: Option<DocPartUnique>,
 }
 
+/// Building blocks a control can choose from
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:docPartList")]
+pub struct DocPartList<'a> {
+    #[xml(child = "w:docPartGallery")]
+    pub doc_part_gallery: Option<DocPartGallery<'a>>,
+    #[xml(child = "w:docPartCategory")]
+    pub doc_part_category: Option<DocPartCategory<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:docPartCategory")]
+pub struct DocPartCategory<'a> {
+    #[xml(attr = "w:val")]
+    pub name: Option<Cow<'a, str>>,
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:docPartGallery")]
@@ -112,12 +667,21 @@ pub struct SDTContent<'a> {
         child = "w:tbl",
         child = "w:sectPr",
         child = "w:sdt",
-        child = "w:r"
+        child = "w:r",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:permStart",
+        child = "w:permEnd"
     )]
     pub content: Vec<BodyContent<'a>>,
 }
 
 impl<'a> SDTContent<'a> {
+    pub fn push<T: Into<BodyContent<'a>>>(mut self, content: T) -> Self {
+        self.content.push(content.into());
+        self
+    }
+
     pub fn text(&self) -> String {
         self.iter_text()
             .map(|c| c.to_string())
@@ -136,10 +700,56 @@ impl<'a> SDTContent<'a> {
                     BodyContent::Sdt(sdt) => Some(sdt.iter_text()),
                     BodyContent::TableCell(_) => None,
                     BodyContent::Run(run) => Some(run.iter_text()),
+                    BodyContent::BookmarkStart(_)
+                    | BodyContent::BookmarkEnd(_)
+                    | BodyContent::PermStart(_)
+                    | BodyContent::PermEnd(_) => None,
                 })
                 .flatten(),
         )
     }
 }
 
-__xml_test_suites!(SDT, SDT::default(), "<w:sdt/>",);
+__xml_test_suites!(
+    SDT,
+    SDT::default(),
+    "<w:sdt/>",
+    SDT::default().property(
+        SDTProperty::default()
+            .alias("Name")
+            .tag("name")
+            .lock(SDTLockType::SdtLocked)
+            .text(SDTText::default())
+    ),
+    r#"<w:sdt><w:sdtPr><w:alias w:val="Name"/><w:tag w:val="name"/><w:lock w:val="sdtLocked"/><w:text/></w:sdtPr></w:sdt>"#,
+    SDT::default().property(
+        SDTProperty::default().drop_down_list(
+            SDTDropDownList::default()
+                .push(SDTListItem::new("Yes", "Y"))
+                .push(SDTListItem::new("No", "N"))
+        )
+    ),
+    r#"<w:sdt><w:sdtPr><w:dropDownList><w:listItem w:displayText="Yes" w:value="Y"/><w:listItem w:displayText="No" w:value="N"/></w:dropDownList></w:sdtPr></w:sdt>"#,
+    SDT::default().property(
+        SDTProperty::default()
+            .data_binding(
+                DataBinding::new("/root[1]/date[1]")
+                    .store_item_id("{1E2D3C4B-0000-0000-0000-000000000000}")
+            )
+            .date(
+                SDTDate::default()
+                    .full_date("2024-03-01T00:00:00Z")
+                    .date_format("d MMMM yyyy")
+            )
+    ),
+    r#"<w:sdt><w:sdtPr><w:dataBinding w:xpath="/root[1]/date[1]" w:storeItemID="{1E2D3C4B-0000-0000-0000-000000000000}"/><w:date w:fullDate="2024-03-01T00:00:00Z"><w:dateFormat w:val="d MMMM yyyy"/></w:date></w:sdtPr></w:sdt>"#,
+    SDT::default().property(SDTProperty::default().checkbox(SDTCheckbox {
+        checked: Some(CheckboxChecked { value: "1".into() }),
+        checked_state: Some(CheckboxCheckedState {
+            value: "2612".into(),
+            font: Some("MS Gothic".into()),
+        }),
+        unchecked_state: None,
+    })),
+    r#"<w:sdt><w:sdtPr><w14:checkbox><w14:checked w14:val="1"/><w14:checkedState w14:val="2612" w14:font="MS Gothic"/></w14:checkbox></w:sdtPr></w:sdt>"#,
+);
@@ -0,0 +1,58 @@
+use hard_xml::{XmlRead, XmlWrite};
+use std::borrow::Cow;
+
+use crate::{__setter, __xml_test_suites, document::Run};
+
+/// A field whose instruction is stored in an attribute, and whose result are the runs inside
+///
+/// ```rust
+/// use docx_rust::document::*;
+///
+/// let field = SimpleField::default()
+///     .instruction(" NUMPAGES ")
+///     .push(Run::default().push_text("1"));
+/// ```
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:fldSimple")]
+pub struct SimpleField<'a> {
+    /// Specifies the field codes.
+    #[xml(attr = "w:instr")]
+    pub instruction: Cow<'a, str>,
+    /// Specifies that the result must not be recalculated.
+    #[xml(attr = "w:fldLock")]
+    pub lock: Option<bool>,
+    /// Specifies that the result is outdated.
+    #[xml(attr = "w:dirty")]
+    pub dirty: Option<bool>,
+    /// Specifies the current field result.
+    #[xml(child = "w:r")]
+    pub runs: Vec<Run<'a>>,
+}
+
+impl<'a> SimpleField<'a> {
+    __setter!(instruction: Cow<'a, str>);
+    __setter!(lock: Option<bool>);
+    __setter!(dirty: Option<bool>);
+
+    pub fn push(mut self, run: Run<'a>) -> Self {
+        self.runs.push(run);
+        self
+    }
+
+    pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
+        self.runs.iter().flat_map(|run| run.iter_text())
+    }
+}
+
+__xml_test_suites!(
+    SimpleField,
+    SimpleField::default(),
+    r#"<w:fldSimple w:instr=""/>"#,
+    SimpleField::default().instruction(" PAGE ").lock(true),
+    r#"<w:fldSimple w:instr=" PAGE " w:fldLock="true"/>"#,
+    SimpleField::default()
+        .instruction(" PAGE ")
+        .push(Run::default().push_text("1")),
+    r#"<w:fldSimple w:instr=" PAGE "><w:r><w:t>1</w:t></w:r></w:fldSimple>"#,
+);
@@ -22,13 +22,13 @@ use crate::{__setter, __xml_test_suites, document::Paragraph, formatting::TableC
 #[xml(tag = "w:tc")]
 pub struct TableCell<'a> {
     #[xml(default, child = "w:tcPr")]
-    pub property: TableCellProperty,
+    pub property: TableCellProperty<'a>,
     #[xml(child = "w:p")]
     pub content: Vec<TableCellContent<'a>>,
 }
 
 impl<'a> TableCell<'a> {
-    __setter!(property: TableCellProperty);
+    __setter!(property: TableCellProperty<'a>);
 
     pub fn paragraph<T: Into<Paragraph<'a>>>(par: T) -> Self {
         TableCell {
@@ -38,21 +38,15 @@ impl<'a> TableCell<'a> {
     }
 
     pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
-        self.content
-            .iter()
-            .filter_map(|content| match content {
-                TableCellContent::Paragraph(p) => Some(p.iter_text()),
-            })
-            .flatten()
+        self.content.iter().flat_map(|content| match content {
+            TableCellContent::Paragraph(p) => p.iter_text(),
+        })
     }
 
     pub fn iter_text_mut(&mut self) -> impl Iterator<Item = &mut Cow<'a, str>> {
-        self.content
-            .iter_mut()
-            .filter_map(|content| match content {
-                TableCellContent::Paragraph(p) => Some(p.iter_text_mut()),
-            })
-            .flatten()
+        self.content.iter_mut().flat_map(|content| match content {
+            TableCellContent::Paragraph(p) => p.iter_text_mut(),
+        })
     }
 
     pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
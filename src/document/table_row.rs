@@ -33,6 +33,7 @@ pub struct TableRow<'a> {
 
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum TableRowContent<'a> {
     #[xml(tag = "w:tc")]
     TableCell(TableCell<'a>),
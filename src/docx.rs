@@ -1,16 +1,25 @@
 use hard_xml::{XmlRead, XmlWrite, XmlWriter};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Read, Seek, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::OnceLock;
 use zip::write::SimpleFileOptions;
 use zip::{result::ZipError, CompressionMethod, ZipArchive, ZipWriter};
 
+use crate::custom_properties::CustomProperties;
+use crate::custom_xml::CustomXmlPart;
 use crate::document::{Comments, EndNotes, FootNotes, Footer, Header, Numbering, Theme};
+use crate::field::DateTime;
 use crate::media::MediaType;
+#[cfg(feature = "parallel")]
+use crate::parallel::parse_document;
+use crate::parse::{parse_option, parse_part, recover_part, ParseWarning};
 use crate::schema::{
-    SCHEMA_COMMENTS, SCHEMA_ENDNOTES, SCHEMA_FOOTNOTES, SCHEMA_HEADER, SCHEMA_NUMBERING,
-    SCHEMA_SETTINGS, SCHEMA_THEME, SCHEMA_WEB_SETTINGS,
+    SCHEMA_COMMENTS, SCHEMA_CUSTOM, SCHEMA_CUSTOM_XML, SCHEMA_CUSTOM_XML_PROPS, SCHEMA_ENDNOTES,
+    SCHEMA_FOOTNOTES, SCHEMA_HEADER, SCHEMA_HYPERLINK, SCHEMA_NUMBERING, SCHEMA_SETTINGS,
+    SCHEMA_THEME, SCHEMA_WEB_SETTINGS,
 };
 use crate::settings::Settings;
 use crate::web_settings::WebSettings;
@@ -19,7 +28,7 @@ use crate::{
     content_type::ContentTypes,
     core::Core,
     document::Document,
-    error::DocxResult,
+    error::{DocxError, DocxResult},
     font_table::FontTable,
     rels::Relationships,
     schema::{
@@ -35,6 +44,8 @@ pub struct Docx<'a> {
     pub app: Option<App<'a>>,
     /// Specifies core properties part
     pub core: Option<Core<'a>>,
+    /// Specifies custom properties part
+    pub custom_properties: Option<CustomProperties<'a>>,
     /// Specifies the content type of relationship parts and the main document part.
     pub content_types: ContentTypes<'a>,
     /// Specifies the main document part.
@@ -51,22 +62,94 @@ pub struct Docx<'a> {
     pub headers: HashMap<String, Header<'a>>,
     pub footers: HashMap<String, Footer<'a>>,
     pub themes: HashMap<String, Theme<'a>>,
-    pub media: HashMap<String, (MediaType, &'a Vec<u8>)>,
+    pub media: HashMap<String, (MediaType, Cow<'a, [u8]>)>,
     pub footnotes: Option<FootNotes<'a>>,
     pub endnotes: Option<EndNotes<'a>>,
     pub settings: Option<Settings<'a>>,
     pub web_settings: Option<WebSettings>,
     pub comments: Option<Comments<'a>>,
     pub numbering: Option<Numbering<'a>>,
+    /// Specifies the custom XML parts, written as `customXml/item{n}.xml` in this order
+    pub custom_xml: Vec<CustomXmlPart<'a>>,
+    /// Sets the modification date of the core properties to the current time when
+    /// writing, adding them if there are none
+    pub stamp_modified: bool,
+    /// Recomputes the statistics of the extended properties when writing,
+    /// adding them if there are none
+    pub refresh_statistics: bool,
 }
 
 impl<'a> Docx<'a> {
+    /// Adds an image to `word/media` and returns the id of its relationship,
+    /// to be used as the `r:embed` of a picture.
+    ///
+    /// Returns `None` if the file name doesn't have a supported image extension.
+    /// A numeric suffix is appended to the name if it's already taken.
+    pub fn add_image<B: Into<Cow<'a, [u8]>>>(
+        &mut self,
+        file_name: &str,
+        bytes: B,
+    ) -> Option<String> {
+        let mt = crate::media::get_media_type(&file_name.to_ascii_lowercase())?;
+        let content_type = crate::media::get_media_content_type(file_name)?;
+        let (stem, ext) = file_name.rsplit_once('.')?;
+
+        let mut name = format!("media/{}", file_name);
+        let mut n = 1;
+        while self.media.contains_key(&name) {
+            name = format!("media/{}{}.{}", stem, n, ext);
+            n += 1;
+        }
+
+        self.content_types
+            .add_default(ext.to_ascii_lowercase(), content_type);
+        let id = self
+            .document_rels
+            .get_or_insert(Relationships::default())
+            .add_rel(
+                crate::media::get_media_type_relation_type(&mt),
+                name.clone(),
+            );
+        self.media.insert(name, (mt, bytes.into()));
+
+        Some(id)
+    }
+
+    /// Adds an external hyperlink relationship and returns its id,
+    /// to be used as the `r:id` of a [`Hyperlink`](crate::document::Hyperlink).
+    pub fn add_hyperlink<T: Into<Cow<'a, str>>>(&mut self, url: T) -> String {
+        self.document_rels
+            .get_or_insert(Relationships::default())
+            .add_rel_with_target_mode(SCHEMA_HYPERLINK, url, Some("External"))
+    }
+
     pub fn write<W: Write + Seek>(&'a mut self, writer: W) -> DocxResult<W> {
         let mut writer = XmlWriter::new(ZipWriter::new(writer));
+        self.write_parts(&mut writer, true)?;
+        Ok(writer.inner.finish()?)
+    }
 
-        let opt = SimpleFileOptions::default()
-            .compression_method(CompressionMethod::Deflated)
-            .unix_permissions(0o755);
+    /// Writes every part of the package, leaving out the main document part
+    /// unless `document` is `true`.
+    pub(crate) fn write_parts<W: Write + Seek>(
+        &mut self,
+        writer: &mut XmlWriter<ZipWriter<W>>,
+        document: bool,
+    ) -> DocxResult<()> {
+        let opt = zip_file_options();
+
+        if self.stamp_modified {
+            self.core
+                .get_or_insert_with(Default::default)
+                .set_modified(DateTime::now());
+        }
+
+        if self.refresh_statistics {
+            let statistics = self.compute_statistics();
+            self.app
+                .get_or_insert_with(Default::default)
+                .set_statistics(&statistics);
+        }
 
         // ==== Add Relationships ====
 
@@ -78,6 +161,10 @@ impl<'a> Docx<'a> {
             self.rels.add_rel(SCHEMA_CORE, "docProps/core.xml");
         }
 
+        if self.custom_properties.is_some() {
+            self.rels.add_rel(SCHEMA_CUSTOM, "docProps/custom.xml");
+        }
+
         self.rels
             .add_rel(SCHEMA_OFFICE_DOCUMENT, "word/document.xml");
 
@@ -130,26 +217,63 @@ impl<'a> Docx<'a> {
         for hd in &self.headers {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_HEADER, hd.0);
+                .add_rel(SCHEMA_HEADER, hd.0.clone());
         }
 
         for ft in &self.footers {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_HEADER, ft.0);
+                .add_rel(SCHEMA_HEADER, ft.0.clone());
         }
 
         for theme in &self.themes {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_THEME, theme.0);
+                .add_rel(SCHEMA_THEME, theme.0.clone());
         }
 
         for media in &self.media {
             let rel = crate::media::get_media_type_relation_type(&media.1 .0);
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(rel, media.0);
+                .add_rel(rel, media.0.clone());
+        }
+
+        for i in 1..=self.custom_xml.len() {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_CUSTOM_XML, format!("../customXml/item{}.xml", i));
+            self.content_types
+                .add_part(format!("/customXml/itemProps{}.xml", i));
+        }
+
+        // ==== Add Content Types ====
+
+        let parts = [
+            (self.app.is_some(), "/docProps/app.xml"),
+            (self.core.is_some(), "/docProps/core.xml"),
+            (self.custom_properties.is_some(), "/docProps/custom.xml"),
+            (true, "/word/document.xml"),
+            (true, "/word/styles.xml"),
+            (self.font_table.is_some(), "/word/fontTable.xml"),
+            (self.footnotes.is_some(), "/word/footnotes.xml"),
+            (self.endnotes.is_some(), "/word/endnotes.xml"),
+            (self.settings.is_some(), "/word/settings.xml"),
+            (self.web_settings.is_some(), "/word/webSettings.xml"),
+            (self.comments.is_some(), "/word/comments.xml"),
+            (self.numbering.is_some(), "/word/numbering.xml"),
+        ];
+        for (_, part) in parts.iter().filter(|(present, _)| *present) {
+            self.content_types.add_part(*part);
+        }
+
+        for name in self
+            .headers
+            .keys()
+            .chain(self.footers.keys())
+            .chain(self.themes.keys())
+        {
+            self.content_types.add_part(format!("/word/{}", name));
         }
 
         // ==== Write Zip Item ====
@@ -166,7 +290,7 @@ impl<'a> Docx<'a> {
             };
             ($xml:expr => $name:tt) => {
                 writer.inner.start_file($name, opt)?;
-                $xml.to_writer(&mut writer)?;
+                $xml.to_writer(writer)?;
             };
             ($xml:expr => $name:tt $($rest:tt)*) => {
                 write_xml!($xml => $name);
@@ -178,8 +302,8 @@ impl<'a> Docx<'a> {
             self.content_types        => "[Content_Types].xml"
             Some(self.app)            => "docProps/app.xml"
             Some(self.core)           => "docProps/core.xml"
+            Some(self.custom_properties) => "docProps/custom.xml"
             self.rels                 => "_rels/.rels"
-            self.document             => "word/document.xml"
             self.styles               => "word/styles.xml"
             Some(self.font_table)     => "word/fontTable.xml"
             Some(self.footnotes)      => "word/footnotes.xml"
@@ -192,6 +316,10 @@ impl<'a> Docx<'a> {
             Some(self.settings_rels)  => "word/_rels/settings.xml.rels"
         );
 
+        if document {
+            write_xml!(self.document => "word/document.xml");
+        }
+
         for hd in self.headers.iter() {
             let file_path = format!("word/{}", hd.0);
             let content = hd.1;
@@ -219,10 +347,29 @@ impl<'a> Docx<'a> {
         for media in self.media.iter() {
             let file_path = format!("word/{}", media.0);
             writer.inner.start_file(file_path, opt)?;
-            writer.inner.write_all(media.1 .1)?;
+            writer.inner.write_all(&media.1 .1)?;
         }
 
-        Ok(writer.inner.finish()?)
+        for (i, part) in self.custom_xml.iter().enumerate() {
+            let n = i + 1;
+            writer
+                .inner
+                .start_file(format!("customXml/item{}.xml", n), opt)?;
+            writer.inner.write_all(part.xml.as_bytes())?;
+            writer
+                .inner
+                .start_file(format!("customXml/itemProps{}.xml", n), opt)?;
+            writer.inner.write_all(part.properties_xml().as_bytes())?;
+
+            let mut rels = Relationships::default();
+            rels.add_rel(SCHEMA_CUSTOM_XML_PROPS, format!("itemProps{}.xml", n));
+            let file_path = format!("customXml/_rels/item{}.xml.rels", n);
+            write_xml!(
+                rels => file_path
+            );
+        }
+
+        Ok(())
     }
 
     pub fn write_file<P: AsRef<Path>>(&'a mut self, path: P) -> DocxResult<File> {
@@ -234,11 +381,47 @@ impl<'a> Docx<'a> {
     }
 }
 
+pub(crate) fn zip_file_options() -> SimpleFileOptions {
+    SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o755)
+}
+
+/// Signature of OLE compound files, which encrypted packages are stored in
+const COMPOUND_FILE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+pub(crate) fn is_compound_file<T: Read + Seek>(reader: &mut T) -> std::io::Result<bool> {
+    let start = reader.stream_position()?;
+    let mut signature = [0; 8];
+    let found = reader.read_exact(&mut signature).is_ok() && signature == COMPOUND_FILE_SIGNATURE;
+    reader.seek(SeekFrom::Start(start))?;
+    Ok(found)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parse_document(xml: &str) -> DocxResult<Document<'_>> {
+    parse_part("word/document.xml", xml)
+}
+
+/// Parses parts like headers, naming them relative to `word/`.
+fn parse_parts<'a, T: XmlRead<'a>>(
+    parts: &'a [(String, String)],
+) -> DocxResult<HashMap<String, T>> {
+    parts
+        .iter()
+        .map(|(name, xml)| Ok((name.replace("word/", ""), parse_part(name, xml)?)))
+        .collect()
+}
+
 /// An extracted docx file
+///
+/// All the parts are read into memory, see [`LazyDocxFile`](crate::LazyDocxFile)
+/// to read them on demand.
 pub struct DocxFile {
     app: Option<String>,
     content_types: String,
     core: Option<String>,
+    custom_properties: Option<String>,
     document: String,
     document_rels: Option<String>,
     settings_rels: Option<String>,
@@ -255,11 +438,21 @@ pub struct DocxFile {
     endnotes: Option<String>,
     comments: Option<String>,
     numbering: Option<String>,
+    /// Custom XML items along with their properties
+    custom_xml: Vec<(String, Option<String>)>,
+    /// Parts fixed by [`parse_lenient`](DocxFile::parse_lenient), without the media
+    recovered: OnceLock<Box<DocxFile>>,
 }
 
 impl DocxFile {
     /// Extracts from reader
-    pub fn from_reader<T: Read + Seek>(reader: T) -> DocxResult<Self> {
+    ///
+    /// Fails with [`DocxError::Encrypted`] on files that need a password to open.
+    pub fn from_reader<T: Read + Seek>(mut reader: T) -> DocxResult<Self> {
+        if is_compound_file(&mut reader)? {
+            return Err(DocxError::Encrypted);
+        }
+
         let mut zip = ZipArchive::new(reader)?;
 
         macro_rules! read {
@@ -324,6 +517,7 @@ impl DocxFile {
         let app = option_read!(App, "docProps/app.xml");
         let content_types = read!(ContentTypes, "[Content_Types].xml");
         let core = option_read!(Core, "docProps/core.xml");
+        let custom_properties = option_read!(CustomProperties, "docProps/custom.xml");
         let document_rels = option_read!(Relationships, "word/_rels/document.xml.rels");
         let settings_rels = option_read!(Relationships, "word/_rels/settings.xml.rels");
         let document = read!(Document, "word/document.xml");
@@ -342,10 +536,29 @@ impl DocxFile {
         let themes = option_read_multiple!(Themes, "word/theme/theme");
         let medias = option_read_multiple_files!(Medias, "word/media");
 
+        let mut items: Vec<(usize, String)> = zip
+            .file_names()
+            .filter_map(|name| {
+                let n = name.strip_prefix("customXml/item")?.strip_suffix(".xml")?;
+                Some((n.parse().ok()?, name.to_string()))
+            })
+            .collect();
+        items.sort();
+        let mut custom_xml = Vec::new();
+        for (n, name) in items {
+            let xml = read!(CustomXml, &name);
+            let properties = option_read!(
+                CustomXmlProperties,
+                &format!("customXml/itemProps{}.xml", n)
+            );
+            custom_xml.push((xml, properties));
+        }
+
         Ok(DocxFile {
             app,
             content_types,
             core,
+            custom_properties,
             document_rels,
             settings_rels,
             document,
@@ -362,6 +575,8 @@ impl DocxFile {
             endnotes,
             comments,
             numbering,
+            custom_xml,
+            recovered: OnceLock::new(),
         })
     }
 
@@ -372,60 +587,56 @@ impl DocxFile {
     }
 
     /// Parses content into `Docx` struct
+    ///
+    /// With the `parallel` feature, the parts are parsed on the thread pool of
+    /// rayon, and so are the paragraphs and tables of the main document part.
     pub fn parse(&self) -> DocxResult<Docx<'_>> {
-        let app = if let Some(content) = &self.app {
-            Some(App::from_str(content)?)
-        } else {
-            None
-        };
-
-        let document = Document::from_str(&self.document)?;
-
-        let mut headers = HashMap::new();
-        for f in self.headers.iter() {
-            let hd = Header::from_str(&f.1)?;
-            let name = f.0.replace("word/", "");
-            headers.insert(name, hd);
-        }
-
-        let mut footers = HashMap::new();
-        for f in self.footers.iter() {
-            let ft = Footer::from_str(&f.1)?;
-            let name = f.0.replace("word/", "");
-            footers.insert(name, ft);
-        }
-
-        let mut media = HashMap::new();
-        for m in self.medias.iter() {
-            let mt = crate::media::get_media_type(&m.0);
-            if let Some(mt) = mt {
-                let name = m.0.replace("word/", "");
-                let m = (mt, &m.1);
-                media.insert(name, m);
-            }
+        // the parts don't depend on each other
+        macro_rules! parse {
+            ($($name:ident = $parse:expr;)*) => {
+                $(
+                    #[cfg(feature = "parallel")]
+                    let mut $name = None;
+                )*
+                #[cfg(feature = "parallel")]
+                rayon::scope(|s| {
+                    $(s.spawn(|_| $name = Some($parse));)*
+                });
+                $(
+                    #[cfg(feature = "parallel")]
+                    let $name = $name.unwrap()?;
+                    #[cfg(not(feature = "parallel"))]
+                    let $name = $parse?;
+                )*
+            };
         }
 
-        let mut themes = HashMap::new();
-        // turn off for now
-        for t in self.themes.iter() {
-            let th = Theme::from_str(&t.1)?;
-            let name = t.0.replace("word/", "");
-            themes.insert(name, th);
+        parse! {
+            app = parse_option("docProps/app.xml", &self.app);
+            document = parse_document(&self.document);
+            headers = parse_parts::<Header>(&self.headers);
+            footers = parse_parts::<Footer>(&self.footers);
+            themes = parse_parts::<Theme>(&self.themes);
+            content_types = parse_part("[Content_Types].xml", &self.content_types);
+            core = parse_option("docProps/core.xml", &self.core);
+            custom_properties = parse_option("docProps/custom.xml", &self.custom_properties);
+            document_rels = parse_option::<Relationships>("word/_rels/document.xml.rels", &self.document_rels);
+            settings_rels = parse_option("word/_rels/settings.xml.rels", &self.settings_rels);
+            font_table = parse_option("word/fontTable.xml", &self.font_table);
+            footnotes = parse_option("word/footnotes.xml", &self.footnotes);
+            endnotes = parse_option("word/endnotes.xml", &self.endnotes);
+            settings = parse_option("word/settings.xml", &self.settings);
+            web_settings = self
+                .web_settings
+                .as_deref()
+                .map(|xml| parse_part("word/webSettings.xml", &xml.replace("ns0:", "w:")))
+                .transpose();
+            comments = parse_option("word/comments.xml", &self.comments);
+            numbering = parse_option("word/numbering.xml", &self.numbering);
+            rels = parse_part::<Relationships>("_rels/.rels", &self.rels);
+            styles = parse_option::<Styles>("word/styles.xml", &self.styles);
         }
 
-        let content_types = ContentTypes::from_str(&self.content_types)?;
-
-        let core = if let Some(content) = &self.core {
-            Some(Core::from_str(content)?)
-        } else {
-            None
-        };
-
-        let document_rels: Option<Relationships> = if let Some(content) = &self.document_rels {
-            Some(Relationships::from_str(content)?)
-        } else {
-            None
-        };
         let document_rels = document_rels.map(|rel: Relationships| {
             let rrr: Vec<_> = rel
                 .relationships
@@ -453,57 +664,6 @@ impl DocxFile {
             Relationships { relationships: rrr }
         });
 
-        let settings_rels = self
-            .settings_rels
-            .as_deref()
-            .map(Relationships::from_str)
-            .transpose()?;
-
-        let font_table = if let Some(content) = &self.font_table {
-            Some(FontTable::from_str(content)?)
-        } else {
-            None
-        };
-
-        let footnotes = if let Some(content) = &self.footnotes {
-            Some(FootNotes::from_str(content)?)
-        } else {
-            None
-        };
-
-        let endnotes = if let Some(content) = &self.endnotes {
-            Some(EndNotes::from_str(content)?)
-        } else {
-            None
-        };
-
-        let settings = if let Some(content) = &self.settings {
-            Some(Settings::from_str(content)?)
-        } else {
-            None
-        };
-
-        let web_settings = if let Some(content) = &self.web_settings {
-            Some(WebSettings::from_str(
-                &content.replace("ns0:", "w:").to_string(),
-            )?)
-        } else {
-            None
-        };
-
-        let comments = if let Some(content) = &self.comments {
-            Some(Comments::from_str(content)?)
-        } else {
-            None
-        };
-
-        let numbering = if let Some(content) = &self.numbering {
-            Some(Numbering::from_str(content)?)
-        } else {
-            None
-        };
-
-        let rels = Relationships::from_str(&self.rels)?;
         let rels = {
             let rrr: Vec<_> = rels
                 .relationships
@@ -513,6 +673,7 @@ impl DocxFile {
                         r2.ty.to_string().as_str(),
                         crate::schema::SCHEMA_CORE
                             | crate::schema::SCHEMA_REL_EXTENDED
+                            | crate::schema::SCHEMA_CUSTOM
                             | crate::schema::SCHEMA_OFFICE_DOCUMENT
                     )
                 })
@@ -521,17 +682,13 @@ impl DocxFile {
             Relationships { relationships: rrr }
         };
 
-        let styles = self
-            .styles
-            .as_ref()
-            .map(|content| Styles::from_str(content))
-            .transpose()?
-            .unwrap_or_default();
+        let styles = styles.unwrap_or_default();
 
         Ok(Docx {
             app,
             content_types,
             core,
+            custom_properties,
             document,
             document_rels,
             settings_rels,
@@ -541,13 +698,152 @@ impl DocxFile {
             headers,
             footers,
             themes,
-            media,
+            media: self.media(),
             footnotes,
             endnotes,
             settings,
             web_settings,
             comments,
             numbering,
+            custom_xml: self.custom_xml_parts(),
+            stamp_modified: false,
+            refresh_statistics: false,
         })
     }
+
+    /// Parses content into `Docx` struct, recovering from malformed elements.
+    ///
+    /// Missing required children like `w:nsid` or `w:multiLevelType` are added
+    /// with default values, other elements that can't be read are left out, see
+    /// [`parse`](crate::parse) for details. The parts are fixed on copies, which
+    /// are kept in the file for the returned `Docx` to borrow from.
+    pub fn parse_lenient(&self) -> DocxResult<(Docx<'_>, Vec<ParseWarning>)> {
+        let mut warnings = Vec::new();
+
+        macro_rules! recover {
+            ($ty:ty, $name:expr, $xml:expr) => {
+                recover_part($name, $xml, &mut warnings, |part, xml| {
+                    parse_part::<$ty>(part, xml).map(drop)
+                })?
+            };
+        }
+        macro_rules! recover_option {
+            ($ty:ty, $name:expr, $xml:expr) => {
+                match $xml.as_deref() {
+                    Some(xml) => Some(recover!($ty, $name, xml)),
+                    None => None,
+                }
+            };
+        }
+        macro_rules! recover_parts {
+            ($ty:ty, $parts:expr) => {{
+                let mut parts = Vec::with_capacity($parts.len());
+                for (name, xml) in &$parts {
+                    parts.push((name, recover!($ty, name, xml)));
+                }
+                parts
+            }};
+        }
+
+        let web_settings = self
+            .web_settings
+            .as_deref()
+            .map(|xml| xml.replace("ns0:", "w:"));
+
+        let app = recover_option!(App, "docProps/app.xml", self.app);
+        let document = recover!(Document, "word/document.xml", &self.document);
+        let headers = recover_parts!(Header, self.headers);
+        let footers = recover_parts!(Footer, self.footers);
+        let themes = recover_parts!(Theme, self.themes);
+        let content_types = recover!(ContentTypes, "[Content_Types].xml", &self.content_types);
+        let core = recover_option!(Core, "docProps/core.xml", self.core);
+        let custom_properties = recover_option!(
+            CustomProperties,
+            "docProps/custom.xml",
+            self.custom_properties
+        );
+        let document_rels = recover_option!(
+            Relationships,
+            "word/_rels/document.xml.rels",
+            self.document_rels
+        );
+        let settings_rels = recover_option!(
+            Relationships,
+            "word/_rels/settings.xml.rels",
+            self.settings_rels
+        );
+        let font_table = recover_option!(FontTable, "word/fontTable.xml", self.font_table);
+        let footnotes = recover_option!(FootNotes, "word/footnotes.xml", self.footnotes);
+        let endnotes = recover_option!(EndNotes, "word/endnotes.xml", self.endnotes);
+        let settings = recover_option!(Settings, "word/settings.xml", self.settings);
+        let web_settings = recover_option!(WebSettings, "word/webSettings.xml", web_settings);
+        let comments = recover_option!(Comments, "word/comments.xml", self.comments);
+        let numbering = recover_option!(Numbering, "word/numbering.xml", self.numbering);
+        let rels = recover!(Relationships, "_rels/.rels", &self.rels);
+        let styles = recover_option!(Styles, "word/styles.xml", self.styles);
+
+        if warnings.is_empty() {
+            return Ok((self.parse()?, warnings));
+        }
+
+        let owned = |xml: Option<Cow<str>>| xml.map(Cow::into_owned);
+        let owned_parts = |parts: Vec<(&String, Cow<str>)>| {
+            parts
+                .into_iter()
+                .map(|(name, xml)| (name.clone(), xml.into_owned()))
+                .collect()
+        };
+        let recovered = DocxFile {
+            app: owned(app),
+            content_types: content_types.into_owned(),
+            core: owned(core),
+            custom_properties: owned(custom_properties),
+            document: document.into_owned(),
+            document_rels: owned(document_rels),
+            settings_rels: owned(settings_rels),
+            font_table: owned(font_table),
+            rels: rels.into_owned(),
+            styles: owned(styles),
+            settings: owned(settings),
+            web_settings: owned(web_settings),
+            headers: owned_parts(headers),
+            footers: owned_parts(footers),
+            themes: owned_parts(themes),
+            medias: Vec::new(),
+            footnotes: owned(footnotes),
+            endnotes: owned(endnotes),
+            comments: owned(comments),
+            numbering: owned(numbering),
+            custom_xml: Vec::new(),
+            recovered: OnceLock::new(),
+        };
+        // fixing the same parts again gives the same copies
+        let recovered = self.recovered.get_or_init(|| Box::new(recovered));
+        let mut docx = recovered.parse()?;
+        docx.media = self.media();
+        docx.custom_xml = self.custom_xml_parts();
+        Ok((docx, warnings))
+    }
+
+    fn media(&self) -> HashMap<String, (MediaType, Cow<'_, [u8]>)> {
+        let mut media = HashMap::new();
+        for m in self.medias.iter() {
+            let mt = crate::media::get_media_type(&m.0);
+            if let Some(mt) = mt {
+                let name = m.0.replace("word/", "");
+                let m = (mt, Cow::Borrowed(m.1.as_slice()));
+                media.insert(name, m);
+            }
+        }
+        media
+    }
+
+    fn custom_xml_parts(&self) -> Vec<CustomXmlPart<'_>> {
+        self.custom_xml
+            .iter()
+            .map(|(xml, properties)| {
+                CustomXmlPart::with_properties(xml.into(), properties.as_deref().map(Into::into))
+            })
+            .collect()
+    }
 }
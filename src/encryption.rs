@@ -0,0 +1,711 @@
+//! Password-encrypted packages
+//!
+//! Documents that need a password to open aren't ZIP archives, but OLE compound files
+//! holding the package encrypted with AES, as described in [MS-OFFCRYPTO]. Both the
+//! Agile encryption of Word 2010 and later, and its integrity check, are supported.
+//!
+//! ```rust
+//! use docx_rust::{document::Paragraph, Docx, DocxError, DocxFile};
+//! use std::io::Cursor;
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(Paragraph::default().push_text("Confidential"));
+//! let buf = docx.write_encrypted(Vec::new(), "secret").unwrap();
+//!
+//! assert!(matches!(
+//!     DocxFile::from_reader(Cursor::new(&buf)),
+//!     Err(DocxError::Encrypted)
+//! ));
+//! assert!(matches!(
+//!     DocxFile::from_reader_with_password(Cursor::new(&buf), "guess"),
+//!     Err(DocxError::WrongPassword)
+//! ));
+//! let file = DocxFile::from_reader_with_password(Cursor::new(&buf), "secret").unwrap();
+//! assert_eq!(file.parse().unwrap().document.body.text(), "Confidential");
+//! ```
+//!
+//! This module is only available with the `encryption` feature.
+//!
+//! [MS-OFFCRYPTO]: https://learn.microsoft.com/en-us/openspecs/office_file_formats/ms-offcrypto
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{Cursor, Error as IOError, ErrorKind, Read, Seek, Write};
+use std::path::Path;
+
+use aes::cipher::{
+    block_padding::NoPadding, BlockCipher, BlockDecrypt, BlockDecryptMut, BlockEncrypt,
+    BlockEncryptMut, KeyInit, KeyIvInit,
+};
+use aes::{Aes128, Aes192, Aes256};
+use hard_xml::{XmlRead, XmlWrite};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha384, Sha512};
+
+use crate::{base64, docx::is_compound_file, protection::HashAlgorithm};
+use crate::{Docx, DocxError, DocxFile, DocxResult};
+
+const ENCRYPTION_INFO: &str = "EncryptionInfo";
+const ENCRYPTED_PACKAGE: &str = "EncryptedPackage";
+
+const KEY_ENCRYPTOR_PASSWORD: &str =
+    "http://schemas.microsoft.com/office/2006/keyEncryptor/password";
+
+// block keys, which derive a key or an initialization vector for each purpose
+const VERIFIER_HASH_INPUT_BLOCK: [u8; 8] = [0xFE, 0xA7, 0xD2, 0x76, 0x3B, 0x4B, 0x9E, 0x79];
+const VERIFIER_HASH_VALUE_BLOCK: [u8; 8] = [0xD7, 0xAA, 0x0F, 0x6D, 0x30, 0x61, 0x34, 0x4E];
+const KEY_VALUE_BLOCK: [u8; 8] = [0x14, 0x6E, 0x0B, 0xE7, 0xAB, 0xAC, 0xD0, 0xD6];
+const HMAC_KEY_BLOCK: [u8; 8] = [0x5F, 0xB2, 0xAD, 0x01, 0x0C, 0xB9, 0xE1, 0xF6];
+const HMAC_VALUE_BLOCK: [u8; 8] = [0xA0, 0x67, 0x7F, 0x02, 0xB2, 0x2C, 0x84, 0x33];
+
+/// The package is encrypted in segments of this size, each with its own
+/// initialization vector
+const SEGMENT_LENGTH: usize = 4096;
+
+const SPIN_COUNT: u32 = 100_000;
+const SALT_SIZE: usize = 16;
+const BLOCK_SIZE: usize = 16;
+const KEY_BITS: usize = 256;
+const HASH_SIZE: usize = 64;
+
+#[derive(Debug, XmlRead, XmlWrite)]
+#[xml(tag = "encryption")]
+struct Encryption<'a> {
+    #[xml(attr = "xmlns")]
+    xmlns: Option<Cow<'a, str>>,
+    #[xml(attr = "xmlns:p")]
+    xmlns_p: Option<Cow<'a, str>>,
+    #[xml(child = "keyData")]
+    key_data: KeyData<'a>,
+    #[xml(child = "dataIntegrity")]
+    data_integrity: Option<DataIntegrity<'a>>,
+    #[xml(child = "keyEncryptors")]
+    key_encryptors: KeyEncryptors<'a>,
+}
+
+/// How the package itself is encrypted
+#[derive(Debug, XmlRead, XmlWrite)]
+#[xml(tag = "keyData")]
+struct KeyData<'a> {
+    #[xml(attr = "saltSize")]
+    salt_size: usize,
+    #[xml(attr = "blockSize")]
+    block_size: usize,
+    #[xml(attr = "keyBits")]
+    key_bits: usize,
+    #[xml(attr = "hashSize")]
+    hash_size: usize,
+    #[xml(attr = "cipherAlgorithm")]
+    cipher_algorithm: Cow<'a, str>,
+    #[xml(attr = "cipherChaining")]
+    cipher_chaining: Cow<'a, str>,
+    #[xml(attr = "hashAlgorithm")]
+    hash_algorithm: Cow<'a, str>,
+    #[xml(attr = "saltValue")]
+    salt_value: Cow<'a, str>,
+}
+
+#[derive(Debug, XmlRead, XmlWrite)]
+#[xml(tag = "dataIntegrity")]
+struct DataIntegrity<'a> {
+    #[xml(attr = "encryptedHmacKey")]
+    encrypted_hmac_key: Cow<'a, str>,
+    #[xml(attr = "encryptedHmacValue")]
+    encrypted_hmac_value: Cow<'a, str>,
+}
+
+#[derive(Debug, XmlRead, XmlWrite)]
+#[xml(tag = "keyEncryptors")]
+struct KeyEncryptors<'a> {
+    #[xml(child = "keyEncryptor")]
+    content: Vec<KeyEncryptor<'a>>,
+}
+
+#[derive(Debug, XmlRead, XmlWrite)]
+#[xml(tag = "keyEncryptor")]
+struct KeyEncryptor<'a> {
+    #[xml(attr = "uri")]
+    uri: Cow<'a, str>,
+    /// Missing from the encryptors of certificates
+    #[xml(child = "p:encryptedKey")]
+    encrypted_key: Option<EncryptedKey<'a>>,
+}
+
+/// How the key of the package is encrypted with the password
+#[derive(Debug, XmlRead, XmlWrite)]
+#[xml(tag = "p:encryptedKey")]
+struct EncryptedKey<'a> {
+    #[xml(attr = "spinCount")]
+    spin_count: u32,
+    #[xml(attr = "saltSize")]
+    salt_size: usize,
+    #[xml(attr = "blockSize")]
+    block_size: usize,
+    #[xml(attr = "keyBits")]
+    key_bits: usize,
+    #[xml(attr = "hashSize")]
+    hash_size: usize,
+    #[xml(attr = "cipherAlgorithm")]
+    cipher_algorithm: Cow<'a, str>,
+    #[xml(attr = "cipherChaining")]
+    cipher_chaining: Cow<'a, str>,
+    #[xml(attr = "hashAlgorithm")]
+    hash_algorithm: Cow<'a, str>,
+    #[xml(attr = "saltValue")]
+    salt_value: Cow<'a, str>,
+    #[xml(attr = "encryptedVerifierHashInput")]
+    encrypted_verifier_hash_input: Cow<'a, str>,
+    #[xml(attr = "encryptedVerifierHashValue")]
+    encrypted_verifier_hash_value: Cow<'a, str>,
+    #[xml(attr = "encryptedKeyValue")]
+    encrypted_key_value: Cow<'a, str>,
+}
+
+impl DocxFile {
+    /// Extracts from a reader, decrypting the package with this password if it is
+    /// encrypted.
+    ///
+    /// Fails with [`DocxError::WrongPassword`] if the password doesn't fit, and with
+    /// [`DocxError::UnsupportedEncryption`] on encryption other than the Agile one.
+    pub fn from_reader_with_password<T: Read + Seek>(
+        mut reader: T,
+        password: &str,
+    ) -> DocxResult<Self> {
+        if !is_compound_file(&mut reader)? {
+            return Self::from_reader(reader);
+        }
+        let package = decrypt(reader, password)?;
+        Self::from_reader(Cursor::new(package))
+    }
+
+    /// Extracts from a file, decrypting it with this password if it is encrypted.
+    #[inline]
+    pub fn from_file_with_password<P: AsRef<Path>>(path: P, password: &str) -> DocxResult<Self> {
+        Self::from_reader_with_password(File::open(path)?, password)
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Writes the package encrypted with a password, which is then needed to open it.
+    ///
+    /// The package is encrypted with AES-256, and the password hashed with SHA-512.
+    pub fn write_encrypted<W: Write>(&'a mut self, mut writer: W, password: &str) -> DocxResult<W> {
+        let package = self.write(Cursor::new(Vec::new()))?.into_inner();
+        writer.write_all(&encrypt(&package, password)?)?;
+        Ok(writer)
+    }
+
+    pub fn write_encrypted_file<P: AsRef<Path>>(
+        &'a mut self,
+        path: P,
+        password: &str,
+    ) -> DocxResult<File> {
+        if let Some(p) = path.as_ref().parent() {
+            std::fs::create_dir_all(p)?;
+        }
+        let file = File::create(path)?;
+        self.write_encrypted(file, password)
+    }
+}
+
+fn malformed(message: &str) -> DocxError {
+    IOError::new(
+        ErrorKind::InvalidData,
+        format!("malformed encrypted package: {message}"),
+    )
+    .into()
+}
+
+fn unsupported(message: impl Into<String>) -> DocxError {
+    DocxError::UnsupportedEncryption(message.into())
+}
+
+fn decode(value: &str) -> DocxResult<Vec<u8>> {
+    base64::decode(value).ok_or_else(|| malformed("invalid base64"))
+}
+
+fn hash_algorithm(name: &str) -> DocxResult<HashAlgorithm> {
+    HashAlgorithm::from_name(name).ok_or_else(|| unsupported(format!("hash algorithm {name}")))
+}
+
+fn check_cipher(
+    algorithm: &str,
+    chaining: &str,
+    key_bits: usize,
+    block_size: usize,
+) -> DocxResult<()> {
+    if algorithm != "AES" || chaining != "ChainingModeCBC" {
+        return Err(unsupported(format!("cipher {algorithm} in {chaining}")));
+    }
+    if !matches!(key_bits, 128 | 192 | 256) || block_size != BLOCK_SIZE {
+        return Err(unsupported(format!("AES with {key_bits}-bit keys")));
+    }
+    Ok(())
+}
+
+/// Cuts or pads a hash to a given length, as keys and initialization vectors
+fn fit(mut hash: Vec<u8>, length: usize) -> Vec<u8> {
+    hash.resize(length, 0x36);
+    hash
+}
+
+/// Pads data to a whole number of blocks.
+fn pad(data: &[u8]) -> Vec<u8> {
+    let mut data = data.to_vec();
+    data.resize(data.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+    data
+}
+
+/// Hashes the password with the salt, `spin_count` times over.
+fn hash_password(
+    algorithm: HashAlgorithm,
+    password: &str,
+    salt: &[u8],
+    spin_count: u32,
+) -> Vec<u8> {
+    let password: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut hash = algorithm.digest(&[salt, &password]);
+    for i in 0..spin_count {
+        hash = algorithm.digest(&[&i.to_le_bytes(), &hash]);
+    }
+    hash
+}
+
+fn aes_cbc<C>(key: &[u8], iv: &[u8], data: &mut [u8], encrypt: bool) -> DocxResult<()>
+where
+    C: BlockCipher + BlockEncrypt + BlockDecrypt + KeyInit,
+{
+    let length = data.len();
+    let done = if encrypt {
+        cbc::Encryptor::<C>::new_from_slices(key, iv)
+            .ok()
+            .and_then(|c| c.encrypt_padded_mut::<NoPadding>(data, length).ok())
+            .is_some()
+    } else {
+        cbc::Decryptor::<C>::new_from_slices(key, iv)
+            .ok()
+            .and_then(|c| c.decrypt_padded_mut::<NoPadding>(data).ok())
+            .is_some()
+    };
+    if done {
+        Ok(())
+    } else {
+        Err(malformed("data isn't a whole number of blocks"))
+    }
+}
+
+fn cipher(key: &[u8], iv: &[u8], data: &mut [u8], encrypt: bool) -> DocxResult<()> {
+    match key.len() {
+        16 => aes_cbc::<Aes128>(key, iv, data, encrypt),
+        24 => aes_cbc::<Aes192>(key, iv, data, encrypt),
+        32 => aes_cbc::<Aes256>(key, iv, data, encrypt),
+        _ => Err(unsupported(format!("AES with {}-byte keys", key.len()))),
+    }
+}
+
+fn hmac(algorithm: HashAlgorithm, key: &[u8], data: &[u8]) -> Vec<u8> {
+    fn hmac<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC takes keys of any length");
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    match algorithm {
+        HashAlgorithm::Sha1 => hmac::<Hmac<Sha1>>(key, data),
+        HashAlgorithm::Sha256 => hmac::<Hmac<Sha256>>(key, data),
+        HashAlgorithm::Sha384 => hmac::<Hmac<Sha384>>(key, data),
+        HashAlgorithm::Sha512 => hmac::<Hmac<Sha512>>(key, data),
+    }
+}
+
+fn random<const N: usize>() -> DocxResult<[u8; N]> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).map_err(IOError::from)?;
+    Ok(bytes)
+}
+
+/// Encrypts or decrypts the package, segment by segment.
+fn cipher_package(
+    key: &[u8],
+    key_data: &KeyData,
+    algorithm: HashAlgorithm,
+    salt: &[u8],
+    data: &mut [u8],
+    encrypt: bool,
+) -> DocxResult<()> {
+    for (i, segment) in data.chunks_mut(SEGMENT_LENGTH).enumerate() {
+        let iv = algorithm.digest(&[salt, &(i as u32).to_le_bytes()]);
+        cipher(key, &fit(iv, key_data.block_size), segment, encrypt)?;
+    }
+    Ok(())
+}
+
+fn decrypt<T: Read + Seek>(reader: T, password: &str) -> DocxResult<Vec<u8>> {
+    let mut file = cfb::CompoundFile::open(reader)?;
+    if !file.is_stream(ENCRYPTION_INFO) || !file.is_stream(ENCRYPTED_PACKAGE) {
+        return Err(unsupported("the compound file holds no encrypted package"));
+    }
+
+    let mut info = Vec::new();
+    file.open_stream(ENCRYPTION_INFO)?.read_to_end(&mut info)?;
+    if info.len() < 8 {
+        return Err(malformed("truncated encryption info"));
+    }
+    match (
+        u16::from_le_bytes([info[0], info[1]]),
+        u16::from_le_bytes([info[2], info[3]]),
+    ) {
+        (4, 4) => {}
+        (2..=4, 2) => return Err(unsupported("Standard encryption")),
+        (3 | 4, 3) => return Err(unsupported("Extensible encryption")),
+        (major, minor) => return Err(unsupported(format!("encryption version {major}.{minor}"))),
+    }
+    let xml =
+        std::str::from_utf8(&info[8..]).map_err(|_| malformed("encryption info isn't UTF-8"))?;
+    let encryption = Encryption::from_str(xml)?;
+
+    let key_data = &encryption.key_data;
+    check_cipher(
+        &key_data.cipher_algorithm,
+        &key_data.cipher_chaining,
+        key_data.key_bits,
+        key_data.block_size,
+    )?;
+    let algorithm = hash_algorithm(&key_data.hash_algorithm)?;
+    let salt = decode(&key_data.salt_value)?;
+
+    let encryptor = encryption
+        .key_encryptors
+        .content
+        .iter()
+        .find(|e| e.uri == KEY_ENCRYPTOR_PASSWORD)
+        .and_then(|e| e.encrypted_key.as_ref())
+        .ok_or_else(|| unsupported("the package is encrypted with a certificate"))?;
+    let key = decrypt_key(encryptor, password)?;
+
+    let mut package = Vec::new();
+    file.open_stream(ENCRYPTED_PACKAGE)?
+        .read_to_end(&mut package)?;
+    if package.len() < 8 {
+        return Err(malformed("truncated package"));
+    }
+
+    if let Some(integrity) = &encryption.data_integrity {
+        let mut hmac_key = decode(&integrity.encrypted_hmac_key)?;
+        let iv = fit(
+            algorithm.digest(&[&salt, &HMAC_KEY_BLOCK]),
+            key_data.block_size,
+        );
+        cipher(&key, &iv, &mut hmac_key, false)?;
+        let mut hmac_value = decode(&integrity.encrypted_hmac_value)?;
+        let iv = fit(
+            algorithm.digest(&[&salt, &HMAC_VALUE_BLOCK]),
+            key_data.block_size,
+        );
+        cipher(&key, &iv, &mut hmac_value, false)?;
+
+        let hmac_key = hmac_key.get(..key_data.hash_size).unwrap_or(&hmac_key);
+        let expected = hmac(algorithm, hmac_key, &package);
+        if hmac_value.get(..expected.len()) != Some(&expected[..]) {
+            return Err(malformed("the package failed its integrity check"));
+        }
+    }
+
+    let size = u64::from_le_bytes(package[..8].try_into().unwrap());
+    let mut data = package.split_off(8);
+    // the stream size is untrusted, so bound it before rounding it up;
+    // some writers leave bytes after the last block
+    let (size, blocks) = usize::try_from(size)
+        .ok()
+        .filter(|size| *size <= data.len())
+        .and_then(|size| Some((size, size.checked_next_multiple_of(key_data.block_size)?)))
+        .filter(|(_, blocks)| *blocks <= data.len())
+        .ok_or_else(|| malformed("truncated package"))?;
+    data.truncate(blocks);
+    cipher_package(&key, key_data, algorithm, &salt, &mut data, false)?;
+    data.truncate(size);
+    Ok(data)
+}
+
+/// Decrypts the key of the package with the password.
+fn decrypt_key(encryptor: &EncryptedKey, password: &str) -> DocxResult<Vec<u8>> {
+    check_cipher(
+        &encryptor.cipher_algorithm,
+        &encryptor.cipher_chaining,
+        encryptor.key_bits,
+        encryptor.block_size,
+    )?;
+    let algorithm = hash_algorithm(&encryptor.hash_algorithm)?;
+    let salt = decode(&encryptor.salt_value)?;
+    let iv = fit(salt.clone(), encryptor.block_size);
+    let hash = hash_password(algorithm, password, &salt, encryptor.spin_count);
+    let key = |block: &[u8]| fit(algorithm.digest(&[&hash, block]), encryptor.key_bits / 8);
+
+    let mut verifier = decode(&encryptor.encrypted_verifier_hash_input)?;
+    cipher(&key(&VERIFIER_HASH_INPUT_BLOCK), &iv, &mut verifier, false)?;
+    verifier.truncate(encryptor.salt_size);
+    let mut verifier_hash = decode(&encryptor.encrypted_verifier_hash_value)?;
+    cipher(
+        &key(&VERIFIER_HASH_VALUE_BLOCK),
+        &iv,
+        &mut verifier_hash,
+        false,
+    )?;
+    verifier_hash.truncate(encryptor.hash_size);
+    if algorithm.digest(&[&verifier]) != verifier_hash {
+        return Err(DocxError::WrongPassword);
+    }
+
+    let mut package_key = decode(&encryptor.encrypted_key_value)?;
+    cipher(&key(&KEY_VALUE_BLOCK), &iv, &mut package_key, false)?;
+    package_key.truncate(encryptor.key_bits / 8);
+    Ok(package_key)
+}
+
+fn encrypt(package: &[u8], password: &str) -> DocxResult<Vec<u8>> {
+    let algorithm = HashAlgorithm::Sha512;
+    let package_key = random::<{ KEY_BITS / 8 }>()?;
+    let key_salt = random::<SALT_SIZE>()?;
+    let password_salt = random::<SALT_SIZE>()?;
+    let verifier = random::<SALT_SIZE>()?;
+    let hmac_key = random::<HASH_SIZE>()?;
+
+    // the key of the package, encrypted with the password
+    let hash = hash_password(algorithm, password, &password_salt, SPIN_COUNT);
+    let key = |block: &[u8]| fit(algorithm.digest(&[&hash, block]), KEY_BITS / 8);
+    let mut verifier_input = pad(&verifier);
+    cipher(
+        &key(&VERIFIER_HASH_INPUT_BLOCK),
+        &password_salt,
+        &mut verifier_input,
+        true,
+    )?;
+    let mut verifier_hash = pad(&algorithm.digest(&[&verifier]));
+    cipher(
+        &key(&VERIFIER_HASH_VALUE_BLOCK),
+        &password_salt,
+        &mut verifier_hash,
+        true,
+    )?;
+    let mut key_value = pad(&package_key);
+    cipher(&key(&KEY_VALUE_BLOCK), &password_salt, &mut key_value, true)?;
+
+    let key_data = KeyData {
+        salt_size: SALT_SIZE,
+        block_size: BLOCK_SIZE,
+        key_bits: KEY_BITS,
+        hash_size: HASH_SIZE,
+        cipher_algorithm: "AES".into(),
+        cipher_chaining: "ChainingModeCBC".into(),
+        hash_algorithm: "SHA512".into(),
+        salt_value: base64::encode(&key_salt).into(),
+    };
+
+    let mut data = Vec::with_capacity(package.len() + BLOCK_SIZE);
+    for segment in package.chunks(SEGMENT_LENGTH) {
+        data.extend(pad(segment));
+    }
+    cipher_package(
+        &package_key,
+        &key_data,
+        algorithm,
+        &key_salt,
+        &mut data,
+        true,
+    )?;
+    let mut encrypted_package = (package.len() as u64).to_le_bytes().to_vec();
+    encrypted_package.extend(data);
+
+    let iv = fit(algorithm.digest(&[&key_salt, &HMAC_KEY_BLOCK]), BLOCK_SIZE);
+    let mut encrypted_hmac_key = pad(&hmac_key);
+    cipher(&package_key, &iv, &mut encrypted_hmac_key, true)?;
+    let iv = fit(
+        algorithm.digest(&[&key_salt, &HMAC_VALUE_BLOCK]),
+        BLOCK_SIZE,
+    );
+    let mut encrypted_hmac_value = pad(&hmac(algorithm, &hmac_key, &encrypted_package));
+    cipher(&package_key, &iv, &mut encrypted_hmac_value, true)?;
+
+    let encryption = Encryption {
+        xmlns: Some("http://schemas.microsoft.com/office/2006/encryption".into()),
+        xmlns_p: Some("http://schemas.microsoft.com/office/2006/keyEncryptor/password".into()),
+        key_data,
+        data_integrity: Some(DataIntegrity {
+            encrypted_hmac_key: base64::encode(&encrypted_hmac_key).into(),
+            encrypted_hmac_value: base64::encode(&encrypted_hmac_value).into(),
+        }),
+        key_encryptors: KeyEncryptors {
+            content: vec![KeyEncryptor {
+                uri: KEY_ENCRYPTOR_PASSWORD.into(),
+                encrypted_key: Some(EncryptedKey {
+                    spin_count: SPIN_COUNT,
+                    salt_size: SALT_SIZE,
+                    block_size: BLOCK_SIZE,
+                    key_bits: KEY_BITS,
+                    hash_size: HASH_SIZE,
+                    cipher_algorithm: "AES".into(),
+                    cipher_chaining: "ChainingModeCBC".into(),
+                    hash_algorithm: "SHA512".into(),
+                    salt_value: base64::encode(&password_salt).into(),
+                    encrypted_verifier_hash_input: base64::encode(&verifier_input).into(),
+                    encrypted_verifier_hash_value: base64::encode(&verifier_hash).into(),
+                    encrypted_key_value: base64::encode(&key_value).into(),
+                }),
+            }],
+        },
+    };
+    // version 4.4, with the flag of Agile encryption
+    let mut info = vec![4, 0, 4, 0, 0x40, 0, 0, 0];
+    info.extend_from_slice(b"<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\r\n");
+    info.extend_from_slice(encryption.to_string()?.as_bytes());
+
+    let mut file =
+        cfb::CompoundFile::create_with_version(cfb::Version::V3, Cursor::new(Vec::new()))?;
+    file.create_stream(ENCRYPTION_INFO)?.write_all(&info)?;
+    file.create_stream(ENCRYPTED_PACKAGE)?
+        .write_all(&encrypted_package)?;
+    write_data_spaces(&mut file)?;
+    file.flush()?;
+    Ok(file.into_inner().into_inner())
+}
+
+/// Writes a length-prefixed UTF-16 string, padded to four bytes.
+fn push_string(buf: &mut Vec<u8>, s: &str) {
+    let s: Vec<u8> = s.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    buf.extend((s.len() as u32).to_le_bytes());
+    buf.extend(&s);
+    buf.resize(buf.len().next_multiple_of(4), 0);
+}
+
+/// Writes the data spaces, which tell Office the package is encrypted.
+fn write_data_spaces<F: Read + Write + Seek>(file: &mut cfb::CompoundFile<F>) -> DocxResult<()> {
+    const DATA_SPACE: &str = "StrongEncryptionDataSpace";
+    const TRANSFORM: &str = "StrongEncryptionTransform";
+    // reader, updater and writer versions, all 1.0
+    const VERSIONS: [u8; 12] = [1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0];
+
+    let root = "/\u{6}DataSpaces";
+    file.create_storage(root)?;
+    file.create_storage(format!("{root}/DataSpaceInfo"))?;
+    file.create_storage(format!("{root}/TransformInfo"))?;
+    file.create_storage(format!("{root}/TransformInfo/{TRANSFORM}"))?;
+
+    let mut version = Vec::new();
+    push_string(&mut version, "Microsoft.Container.DataSpaces");
+    version.extend(VERSIONS);
+    file.create_stream(format!("{root}/Version"))?
+        .write_all(&version)?;
+
+    let mut entry = Vec::new();
+    // one reference, to a stream
+    entry.extend(1u32.to_le_bytes());
+    entry.extend(0u32.to_le_bytes());
+    push_string(&mut entry, ENCRYPTED_PACKAGE);
+    push_string(&mut entry, DATA_SPACE);
+    let mut map = Vec::new();
+    map.extend(8u32.to_le_bytes());
+    map.extend(1u32.to_le_bytes());
+    map.extend((entry.len() as u32 + 4).to_le_bytes());
+    map.extend(entry);
+    file.create_stream(format!("{root}/DataSpaceMap"))?
+        .write_all(&map)?;
+
+    let mut definition = Vec::new();
+    definition.extend(8u32.to_le_bytes());
+    definition.extend(1u32.to_le_bytes());
+    push_string(&mut definition, TRANSFORM);
+    file.create_stream(format!("{root}/DataSpaceInfo/{DATA_SPACE}"))?
+        .write_all(&definition)?;
+
+    let mut transform = Vec::new();
+    let mut id = Vec::new();
+    push_string(&mut id, "{FF9A3F03-56EF-4613-BDD5-5A41C1D07246}");
+    // the length covers itself, the type and the id
+    transform.extend((id.len() as u32 + 8).to_le_bytes());
+    transform.extend(1u32.to_le_bytes());
+    transform.extend(id);
+    push_string(&mut transform, "Microsoft.Container.EncryptionTransform");
+    transform.extend(VERSIONS);
+    // no encryption name, block size nor cipher mode, as Agile encryption says
+    // those in the encryption info
+    transform.extend(0u32.to_le_bytes());
+    transform.extend(0u32.to_le_bytes());
+    transform.extend(0u32.to_le_bytes());
+    transform.extend(4u32.to_le_bytes());
+    file.create_stream(format!("{root}/TransformInfo/{TRANSFORM}/\u{6}Primary"))?
+        .write_all(&transform)?;
+    Ok(())
+}
+
+#[test]
+fn unsupported_test() {
+    let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+    // Standard encryption, with its binary header left out
+    file.create_stream(ENCRYPTION_INFO)
+        .unwrap()
+        .write_all(&[4, 0, 2, 0, 0x24, 0, 0, 0])
+        .unwrap();
+    file.create_stream(ENCRYPTED_PACKAGE).unwrap();
+    let buf = file.into_inner().into_inner();
+    assert!(matches!(
+        decrypt(Cursor::new(buf), "secret"),
+        Err(DocxError::UnsupportedEncryption(_))
+    ));
+
+    let file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+    let buf = file.into_inner().into_inner();
+    assert!(matches!(
+        decrypt(Cursor::new(buf), "secret"),
+        Err(DocxError::UnsupportedEncryption(_))
+    ));
+}
+
+#[test]
+fn integrity_test() {
+    let package = b"PK not quite a package".repeat(300);
+    let buf = encrypt(&package, "secret").unwrap();
+    assert_eq!(decrypt(Cursor::new(&buf), "secret").unwrap(), package);
+
+    let mut file = cfb::CompoundFile::open(Cursor::new(buf)).unwrap();
+    let mut stream = file.open_stream(ENCRYPTED_PACKAGE).unwrap();
+    stream.seek(std::io::SeekFrom::Start(100)).unwrap();
+    stream.write_all(b"tampered").unwrap();
+    drop(stream);
+    let buf = file.into_inner().into_inner();
+    assert!(matches!(
+        decrypt(Cursor::new(buf), "secret"),
+        Err(DocxError::IO(_))
+    ));
+}
+
+#[test]
+fn stream_size_test() {
+    let package = b"PK not quite a package".repeat(300);
+    for size in [u64::MAX, u64::MAX - 8, package.len() as u64 + 64] {
+        let buf = encrypt(&package, "secret").unwrap();
+        let mut file = cfb::CompoundFile::open(Cursor::new(buf)).unwrap();
+        // drop the integrity check, so the forged size reaches decryption
+        let mut info = Vec::new();
+        file.open_stream(ENCRYPTION_INFO)
+            .unwrap()
+            .read_to_end(&mut info)
+            .unwrap();
+        let xml = std::str::from_utf8(&info[8..]).unwrap();
+        let start = xml.find("<dataIntegrity").unwrap();
+        let end = start + xml[start..].find("/>").unwrap() + 2;
+        let xml = format!("{}{}", &xml[..start], &xml[end..]);
+        let mut stream = file.create_stream(ENCRYPTION_INFO).unwrap();
+        stream.write_all(&info[..8]).unwrap();
+        stream.write_all(xml.as_bytes()).unwrap();
+        drop(stream);
+        let mut stream = file.open_stream(ENCRYPTED_PACKAGE).unwrap();
+        stream.write_all(&size.to_le_bytes()).unwrap();
+        drop(stream);
+        let buf = file.into_inner().into_inner();
+        match decrypt(Cursor::new(buf), "secret") {
+            Err(DocxError::IO(err)) => assert!(err.to_string().ends_with("truncated package")),
+            _ => panic!("a forged stream size must be rejected"),
+        }
+    }
+}
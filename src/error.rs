@@ -1,4 +1,5 @@
 use std::io::Error as IOError;
+use std::ops::Range;
 
 use hard_xml::XmlError;
 use thiserror::Error;
@@ -11,8 +12,61 @@ pub enum DocxError {
     IO(#[from] IOError),
     #[error("malformed XML: {0}")]
     Xml(#[from] XmlError),
+    /// Malformed XML in a part, located
+    ///
+    /// [`DocxFile::parse`](crate::DocxFile::parse) used to report these as
+    /// [`Xml`](DocxError::Xml), see [`xml_error`](DocxError::xml_error) to match
+    /// either.
+    #[error("malformed XML in {0}")]
+    Parse(Box<ParseError>),
     #[error("unable to unpack file: {0}")]
     Zip(#[from] ZipError),
+    #[error("invalid content control value: {0}")]
+    ContentControl(String),
+    #[error("invalid form field value: {0}")]
+    FormField(String),
+    #[error("invalid bookmark: {0}")]
+    Bookmark(String),
+    #[error("the file is encrypted and needs a password to open")]
+    Encrypted,
+    #[error("wrong password")]
+    WrongPassword,
+    #[error("unsupported encryption: {0}")]
+    UnsupportedEncryption(String),
+}
+
+impl DocxError {
+    /// Returns the XML error, whether it is located or not.
+    pub fn xml_error(&self) -> Option<&XmlError> {
+        match self {
+            DocxError::Xml(error) => Some(error),
+            DocxError::Parse(error) => Some(&error.source),
+            _ => None,
+        }
+    }
+}
+
+/// An error in a part of a package, along with where it occurred
+#[derive(Debug, Error)]
+#[error("{part} at line {line}, column {column}, in {}: {source}", .path.join("/"))]
+pub struct ParseError {
+    /// Name of the part in the archive, like `word/numbering.xml`
+    pub part: String,
+    /// Offset in bytes of the element in the part
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    /// Names of the element and its ancestors, starting from the root
+    pub path: Vec<String>,
+    pub source: XmlError,
+    /// Span of the element, if its end was found
+    pub(crate) element: Option<Range<usize>>,
+}
+
+impl From<ParseError> for DocxError {
+    fn from(error: ParseError) -> Self {
+        DocxError::Parse(Box::new(error))
+    }
 }
 
 /// Specialized `Result` which the error value is `DocxError`.
@@ -0,0 +1,1493 @@
+//! Fields
+//!
+//! Word stores fields either as `w:fldSimple` elements, or as complex fields spread over
+//! runs: a `begin` field character, the instruction in `w:instrText`, a `separate` field
+//! character, the runs of the current result, and an `end` field character.
+//! [`Docx::fields`] collects both kinds into [`Field`]s, and [`Docx::update_fields`]
+//! recalculates their results, so that generated documents show the right values
+//! without asking to update fields when opened.
+//!
+//! ```rust
+//! use docx_rust::{document::*, field::{DateTime, FieldContext}, text::TextOptions, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(
+//!     Paragraph::default()
+//!         .push_text("Dear ")
+//!         .push(SimpleField::default().instruction(" MERGEFIELD Name "))
+//!         .push_text(", ")
+//!         .push(SimpleField::default().instruction(r#" DATE \@ "d MMMM yyyy" "#)),
+//! );
+//!
+//! let context = FieldContext::default()
+//!     .now(DateTime::new(2024, 3, 1, 9, 30, 0))
+//!     .merge_field("Name", "Alice");
+//! assert_eq!(docx.update_fields(&context), 2);
+//! assert_eq!(docx.to_text(&TextOptions::default()), "Dear Alice, 1 March 2024");
+//! ```
+//!
+//! [`Docx::fields`]: ../struct.Docx.html#method.fields
+//! [`Docx::update_fields`]: ../struct.Docx.html#method.update_fields
+
+use std::collections::HashMap;
+use std::mem;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, BreakType, CharType, EndNotes, FootNotes, Footer,
+        FormDropDownResult, FormFieldData, Header, Paragraph, ParagraphContent, Run, RunContent,
+        TableCellContent, TableRowContent, TextSpace,
+    },
+    formatting::CharacterProperty,
+    text::format_number,
+    Docx,
+};
+
+/// A field along with its current result
+#[derive(Debug, Clone)]
+pub struct Field<'a> {
+    /// The complete field codes, e.g. `REF _Ref123 \h`
+    pub instruction: String,
+    /// Type of the field in upper case, e.g. `REF`
+    pub name: String,
+    /// Arguments following the type, without quotes
+    pub arguments: Vec<String>,
+    pub switches: Vec<FieldSwitch>,
+    /// Runs between the `separate` and `end` field characters, or inside of `w:fldSimple`
+    pub result_runs: Vec<Run<'a>>,
+    /// The result must not be recalculated.
+    pub locked: bool,
+    /// Properties of a `FORMTEXT`, `FORMCHECKBOX` or `FORMDROPDOWN` field
+    pub form_data: Option<FormFieldData<'a>>,
+}
+
+impl<'a> Field<'a> {
+    /// Parses the instruction of a field, leaving the result empty.
+    ///
+    /// ```rust
+    /// use docx_rust::field::Field;
+    ///
+    /// let field = Field::new(r#" SEQ Figure \* ARABIC \r 3 "#);
+    /// assert_eq!(field.name, "SEQ");
+    /// assert_eq!(field.arguments, ["Figure"]);
+    /// assert_eq!(field.switch("*"), Some("ARABIC"));
+    /// assert_eq!(field.switch("r"), Some("3"));
+    /// ```
+    pub fn new<S: Into<String>>(instruction: S) -> Self {
+        let instruction = instruction.into();
+        let mut tokens = tokenize(&instruction).into_iter();
+        let name = tokens
+            .next()
+            .map(|t| t.text.to_uppercase())
+            .unwrap_or_default();
+
+        let mut arguments = Vec::new();
+        let mut switches: Vec<FieldSwitch> = Vec::new();
+        for token in tokens {
+            match token.text.strip_prefix('\\').filter(|_| !token.quoted) {
+                Some(switch) => switches.push(FieldSwitch {
+                    name: switch.to_string(),
+                    value: None,
+                }),
+                None => match switches.last_mut() {
+                    Some(switch) if switch.value.is_none() => switch.value = Some(token.text),
+                    _ => arguments.push(token.text),
+                },
+            }
+        }
+
+        Field {
+            instruction,
+            name,
+            arguments,
+            switches,
+            result_runs: Vec::new(),
+            locked: false,
+            form_data: None,
+        }
+    }
+
+    /// Returns the value of a switch, e.g. `"@"` for the date format,
+    /// or an empty string for switches without value.
+    pub fn switch(&self, name: &str) -> Option<&str> {
+        self.switches
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.value.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the text of the current result.
+    pub fn result(&self) -> String {
+        self.result_runs
+            .iter()
+            .flat_map(|r| &r.content)
+            .filter_map(|c| match c {
+                RunContent::Text(t) => Some(t.text.as_ref()),
+                RunContent::Tab(_) => Some("\t"),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A switch of a field instruction, like `\* MERGEFORMAT` or `\h`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSwitch {
+    /// Name without the backslash, e.g. `*`, `@`, `#` or `h`
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// A calendar date and time of day, used for `DATE` and `TIME` fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub year: i32,
+    /// 1 to 12
+    pub month: u32,
+    /// 1 to 31
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    pub fn new(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Self {
+        DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    /// The current time in UTC.
+    pub fn now() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        Self::from_unix_timestamp(secs)
+    }
+
+    /// Converts seconds since 1970-01-01 00:00:00 UTC.
+    pub fn from_unix_timestamp(secs: i64) -> Self {
+        let days = secs.div_euclid(86400);
+        let time = secs.rem_euclid(86400) as u32;
+
+        // civil from days, see http://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+
+        DateTime::new(year, month, day, time / 3600, time / 60 % 60, time % 60)
+    }
+
+    /// Converts to seconds since 1970-01-01 00:00:00 UTC.
+    pub fn to_unix_timestamp(&self) -> i64 {
+        // days from civil, the inverse of the above
+        let y = self.year as i64 - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+
+        days * 86400 + (self.hour * 3600 + self.minute * 60 + self.second) as i64
+    }
+
+    /// Parses a W3CDTF date and time, the profile of ISO 8601 stored in document
+    /// properties, like `2024-03-01T09:30:00Z` or `2024-03-01T10:30:00+01:00`.
+    ///
+    /// Times with an offset are converted to UTC. Fractions of a second are ignored,
+    /// and missing parts are the start of the year, month or day.
+    pub fn parse_iso8601(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (date, time) = s.split_once('T').unwrap_or((s, ""));
+        let (time, offset) = match time.strip_suffix('Z') {
+            Some(time) => (time, 0),
+            None => match time.rfind(['+', '-']) {
+                Some(i) => {
+                    let (hours, minutes) = time[i + 1..].split_once(':')?;
+                    let offset =
+                        hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+                    (
+                        &time[..i],
+                        if &time[i..=i] == "-" { -offset } else { offset },
+                    )
+                }
+                None => (time, 0),
+            },
+        };
+
+        let next = |part: Option<&str>| part.map_or(Some(0), |p| p.parse::<u32>().ok());
+        let mut date = date.splitn(3, '-');
+        let year = date.next()?.parse().ok()?;
+        let (month, day) = (next(date.next())?.max(1), next(date.next())?.max(1));
+        let mut time = time
+            .split('.')
+            .next()?
+            .splitn(3, ':')
+            .filter(|p| !p.is_empty());
+        let (hour, minute, second) = (next(time.next())?, next(time.next())?, next(time.next())?);
+        if month > 12 || day > 31 || hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let date = DateTime::new(year, month, day, hour, minute, second);
+        if offset == 0 {
+            Some(date)
+        } else {
+            Some(Self::from_unix_timestamp(date.to_unix_timestamp() - offset))
+        }
+    }
+
+    /// Formats the date and time in ISO 8601, like `2024-03-01T09:30:00Z`.
+    pub fn to_iso8601(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// 0 for Sunday to 6 for Saturday
+    fn weekday(&self) -> usize {
+        let (y, m) = if self.month <= 2 {
+            (self.year as i64 - 1, self.month as i64 + 12)
+        } else {
+            (self.year as i64, self.month as i64)
+        };
+        let d = self.day as i64;
+        // Zeller's congruence, 0 is Saturday
+        let h = (d + 13 * (m + 1) / 5 + y + y.div_euclid(4) - y.div_euclid(100)
+            + y.div_euclid(400))
+        .rem_euclid(7);
+        ((h + 6) % 7) as usize
+    }
+
+    /// Formats the date with a Word date-time picture, like `dddd, MMMM d, yyyy` or `HH:mm`.
+    ///
+    /// ```rust
+    /// use docx_rust::field::DateTime;
+    ///
+    /// let date = DateTime::new(2024, 3, 1, 14, 5, 0);
+    /// assert_eq!(date.format("dddd, MMMM d, yyyy"), "Friday, March 1, 2024");
+    /// assert_eq!(date.format("dd.MM.yy h:mm am/pm"), "01.03.24 2:05 pm");
+    /// ```
+    pub fn format(&self, picture: &str) -> String {
+        const MONTHS: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        const DAYS: [&str; 7] = [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ];
+
+        let mut out = String::new();
+        let chars: Vec<char> = picture.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let rest: String = chars[i..].iter().take(5).collect();
+            if rest.eq_ignore_ascii_case("am/pm") {
+                let pm = self.hour >= 12;
+                let marker = if pm { "pm" } else { "am" };
+                out.push_str(&if rest.starts_with('A') {
+                    marker.to_uppercase()
+                } else {
+                    marker.to_string()
+                });
+                i += 5;
+                continue;
+            }
+            if c == '\'' {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|c| *c == '\'')
+                    .map_or(chars.len(), |p| i + 1 + p);
+                out.extend(&chars[i + 1..end]);
+                i = end + 1;
+                continue;
+            }
+
+            let count = chars[i..].iter().take_while(|x| **x == c).count();
+            i += count;
+            let hour12 = match self.hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            match (c, count) {
+                ('d', 1) => out.push_str(&self.day.to_string()),
+                ('d', 2) => out.push_str(&format!("{:02}", self.day)),
+                ('d', 3) => out.push_str(&DAYS[self.weekday()][..3]),
+                ('d', _) => out.push_str(DAYS[self.weekday()]),
+                ('M', 1) => out.push_str(&self.month.to_string()),
+                ('M', 2) => out.push_str(&format!("{:02}", self.month)),
+                ('M', 3) => out.push_str(&MONTHS[(self.month as usize + 11) % 12][..3]),
+                ('M', _) => out.push_str(MONTHS[(self.month as usize + 11) % 12]),
+                ('y', 1 | 2) => out.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                ('y', _) => out.push_str(&self.year.to_string()),
+                ('h', 1) => out.push_str(&hour12.to_string()),
+                ('h', _) => out.push_str(&format!("{:02}", hour12)),
+                ('H', 1) => out.push_str(&self.hour.to_string()),
+                ('H', _) => out.push_str(&format!("{:02}", self.hour)),
+                ('m', 1) => out.push_str(&self.minute.to_string()),
+                ('m', _) => out.push_str(&format!("{:02}", self.minute)),
+                ('s', 1) => out.push_str(&self.second.to_string()),
+                ('s', _) => out.push_str(&format!("{:02}", self.second)),
+                _ => out.extend(std::iter::repeat_n(c, count)),
+            }
+        }
+        out
+    }
+}
+
+impl std::str::FromStr for DateTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_iso8601(s).ok_or_else(|| format!("invalid date and time: {}", s))
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_iso8601())
+    }
+}
+
+/// Values used by [`Docx::update_fields`]
+///
+/// [`Docx::update_fields`]: ../struct.Docx.html#method.update_fields
+///
+/// ```rust
+/// use docx_rust::field::{DateTime, FieldContext};
+///
+/// let context = FieldContext::default()
+///     .now(DateTime::new(2024, 1, 31, 12, 0, 0))
+///     .date_format("yyyy-MM-dd")
+///     .merge_field("Company", "Acme Ltd");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FieldContext {
+    /// The time `DATE` and `TIME` fields show, defaults to the current time in UTC.
+    pub now: DateTime,
+    /// The picture of `DATE` fields without `\@` switch.
+    pub date_format: String,
+    /// The picture of `TIME` fields without `\@` switch.
+    pub time_format: String,
+    /// Values of `MERGEFIELD` fields, by field name
+    pub merge_data: HashMap<String, String>,
+    /// Values of form fields, by field name: the text of `FORMTEXT` fields,
+    /// `true` or `false` for `FORMCHECKBOX`, and an entry of `FORMDROPDOWN`
+    pub form_data: HashMap<String, String>,
+}
+
+impl Default for FieldContext {
+    fn default() -> Self {
+        FieldContext {
+            now: DateTime::now(),
+            date_format: "M/d/yyyy".to_string(),
+            time_format: "h:mm am/pm".to_string(),
+            merge_data: HashMap::new(),
+            form_data: HashMap::new(),
+        }
+    }
+}
+
+impl FieldContext {
+    __setter!(now: DateTime);
+    __setter!(date_format: String);
+    __setter!(time_format: String);
+
+    /// Sets the value of a merge field.
+    pub fn merge_field<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.merge_data.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the value of a form field.
+    pub fn form_field<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.form_data.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the fields of the body, headers, footers, footnotes and endnotes.
+    ///
+    /// Fields nested in the instruction of another field come before it.
+    pub fn fields(&self) -> Vec<Field<'a>> {
+        let mut walker = FieldWalker::new(None, self);
+        let mut body = self.document.body.content.clone();
+        walker.blocks(&mut body);
+        walker.in_body = false;
+        for mut content in self.other_parts().cloned() {
+            walker.blocks(&mut content);
+        }
+        walker.fields
+    }
+
+    /// Recalculates the results of `DATE`, `TIME`, `PAGE`, `NUMPAGES`, `DOCPROPERTY`,
+    /// `DOCVARIABLE`, `REF`, `PAGEREF`, `SEQ`, `MERGEFIELD` and `IF` fields, fills in
+    /// form fields, and returns how many were updated.
+    ///
+    /// Locked fields, other field types and merge fields without data are left as they are.
+    /// Page numbers are counted from the page breaks in the document, and only
+    /// `PAGE` fields of the body are updated, as those in headers and footers
+    /// change from page to page.
+    pub fn update_fields(&mut self, context: &FieldContext) -> usize {
+        let mut walker = FieldWalker::new(Some(context), self);
+        let mut parts = other_parts_mut(
+            &mut self.headers,
+            &mut self.footers,
+            &mut self.footnotes,
+            &mut self.endnotes,
+        );
+
+        walker.blocks(&mut self.document.body.content);
+        walker.in_body = false;
+        for content in parts.iter_mut() {
+            walker.blocks(content);
+        }
+
+        // references see the updated text of their bookmarks
+        walker.bookmarks = bookmarks(&self.document.body.content, walker.rendered_breaks);
+        walker.references = true;
+        walker.in_body = true;
+        walker.page = 1;
+        walker.open.clear();
+        walker.blocks(&mut self.document.body.content);
+        walker.in_body = false;
+        for content in parts.iter_mut() {
+            walker.blocks(content);
+        }
+
+        walker.updated
+    }
+
+    /// Fills in the form fields named in `context`, leaving other fields alone.
+    pub(crate) fn fill_form_fields(&mut self, context: &FieldContext) -> usize {
+        let mut walker = FieldWalker::new(Some(context), self);
+        walker.forms_only = true;
+        walker.blocks(&mut self.document.body.content);
+        walker.in_body = false;
+        for content in other_parts_mut(
+            &mut self.headers,
+            &mut self.footers,
+            &mut self.footnotes,
+            &mut self.endnotes,
+        ) {
+            walker.blocks(content);
+        }
+        walker.updated
+    }
+
+    fn other_parts(&self) -> impl Iterator<Item = &Vec<BodyContent<'a>>> {
+        sorted(&self.headers)
+            .into_iter()
+            .map(|h| &h.content)
+            .chain(sorted(&self.footers).into_iter().map(|f| &f.content))
+            .chain(
+                self.footnotes
+                    .iter()
+                    .flat_map(|n| &n.content)
+                    .map(|n| &n.content),
+            )
+            .chain(
+                self.endnotes
+                    .iter()
+                    .flat_map(|n| &n.content)
+                    .map(|n| &n.content),
+            )
+    }
+}
+
+fn other_parts_mut<'p, 'a>(
+    headers: &'p mut HashMap<String, Header<'a>>,
+    footers: &'p mut HashMap<String, Footer<'a>>,
+    footnotes: &'p mut Option<FootNotes<'a>>,
+    endnotes: &'p mut Option<EndNotes<'a>>,
+) -> Vec<&'p mut Vec<BodyContent<'a>>> {
+    let mut parts: Vec<&mut Vec<BodyContent<'a>>> = Vec::new();
+    parts.extend(sorted_mut(headers).into_iter().map(|h| &mut h.content));
+    parts.extend(sorted_mut(footers).into_iter().map(|f| &mut f.content));
+    parts.extend(
+        footnotes
+            .iter_mut()
+            .flat_map(|n| &mut n.content)
+            .map(|n| &mut n.content),
+    );
+    parts.extend(
+        endnotes
+            .iter_mut()
+            .flat_map(|n| &mut n.content)
+            .map(|n| &mut n.content),
+    );
+    parts
+}
+
+fn sorted<T>(parts: &HashMap<String, T>) -> Vec<&T> {
+    let mut parts: Vec<_> = parts.iter().collect();
+    parts.sort_by_key(|(name, _)| (name.len(), name.as_str()));
+    parts.into_iter().map(|(_, part)| part).collect()
+}
+
+fn sorted_mut<T>(parts: &mut HashMap<String, T>) -> Vec<&mut T> {
+    let mut parts: Vec<_> = parts.iter_mut().collect();
+    parts.sort_by_key(|(name, _)| (name.len(), name.as_str()));
+    parts.into_iter().map(|(_, part)| part).collect()
+}
+
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+/// Splits an instruction into words and quoted strings.
+fn tokenize(instruction: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = instruction.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut text = String::new();
+        if c == '"' || c == '“' || c == '”' {
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' | '“' | '”' => break,
+                    '\\' if chars.peek() == Some(&'"') => text.extend(chars.next()),
+                    c => text.push(c),
+                }
+            }
+            tokens.push(Token { text, quoted: true });
+        } else {
+            text.push(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                text.push(c);
+            }
+            tokens.push(Token {
+                text,
+                quoted: false,
+            });
+        }
+    }
+    tokens
+}
+
+/// A run split into its contents, so that field results can be replaced
+struct Piece<'a> {
+    /// Index of the run in `FieldWalker::runs`, `None` for other paragraph content
+    run: Option<usize>,
+    content: PieceContent<'a>,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum PieceContent<'a> {
+    Paragraph(ParagraphContent<'a>),
+    Run(RunContent<'a>),
+    /// Keeps runs without content
+    EmptyRun,
+}
+
+struct OpenField<'a> {
+    /// Position of the `begin` character, `None` if it's in a previous paragraph
+    begin: Option<usize>,
+    separate: Option<usize>,
+    in_instruction: bool,
+    instruction: String,
+    result: String,
+    result_runs: Vec<Run<'a>>,
+    locked: bool,
+    form_data: Option<FormFieldData<'a>>,
+}
+
+struct FieldWalker<'c, 'a> {
+    context: Option<&'c FieldContext>,
+    /// Document and custom properties, by lower case name
+    properties: HashMap<String, String>,
+    variables: HashMap<String, String>,
+    /// Text and page of each bookmark
+    bookmarks: HashMap<String, (String, usize)>,
+    /// Pages are counted by `w:lastRenderedPageBreak` instead of explicit breaks.
+    rendered_breaks: bool,
+    pages: usize,
+    page: usize,
+    in_body: bool,
+    /// Only `REF` and `PAGEREF` fields are updated.
+    references: bool,
+    /// Only form fields are updated.
+    forms_only: bool,
+    sequences: HashMap<String, usize>,
+    /// Runs the pieces were split from, with their content taken out
+    runs: Vec<Run<'a>>,
+    open: Vec<OpenField<'a>>,
+    fields: Vec<Field<'a>>,
+    updated: usize,
+}
+
+impl<'c, 'a> FieldWalker<'c, 'a> {
+    fn new(context: Option<&'c FieldContext>, docx: &Docx<'a>) -> Self {
+        let mut properties = HashMap::new();
+        let mut set = |name: &str, value: Option<&str>| {
+            if let Some(value) = value {
+                properties.insert(name.to_ascii_lowercase(), value.to_string());
+            }
+        };
+        if let Some(core) = &docx.core {
+            let date = |date: Option<DateTime>| date.map(|d| d.format("M/d/yyyy h:mm am/pm"));
+            set("Title", core.title());
+            set("Subject", core.subject());
+            set("Author", core.creator());
+            set("Keywords", core.keywords());
+            set("Comments", core.description());
+            set("LastSavedBy", core.last_modified_by());
+            set("RevisionNumber", core.revision());
+            set("Category", core.category());
+            set("CreateTime", date(core.created()).as_deref());
+            set("LastSavedTime", date(core.modified()).as_deref());
+            set("LastPrinted", date(core.last_printed()).as_deref());
+        }
+        if let Some(app) = &docx.app {
+            set("Template", app.template());
+            set("Pages", app.pages());
+            set("Words", app.words());
+            set("Characters", app.characters());
+            set("NameOfApplication", app.application());
+            set("Lines", app.lines());
+            set("Paragraphs", app.paragraphs());
+            set("Company", app.company());
+        }
+        for (name, value) in docx.custom_properties.iter().flat_map(|c| c.iter()) {
+            set(name, Some(&value.to_string()));
+        }
+
+        let variables = docx
+            .settings
+            .iter()
+            .flat_map(|s| &s.doc_vars)
+            .flat_map(|v| &v.vars)
+            .map(|v| (v.name.to_string(), v.val.to_string()))
+            .collect();
+
+        let rendered_breaks = docx.document.body.content.iter().any(has_rendered_breaks);
+        let pages = page_count(&docx.document.body.content, rendered_breaks);
+
+        FieldWalker {
+            context,
+            properties,
+            variables,
+            bookmarks: HashMap::new(),
+            rendered_breaks,
+            pages,
+            page: 1,
+            in_body: true,
+            references: false,
+            forms_only: false,
+            sequences: HashMap::new(),
+            runs: Vec::new(),
+            open: Vec::new(),
+            fields: Vec::new(),
+            updated: 0,
+        }
+    }
+
+    fn blocks(&mut self, content: &mut [BodyContent<'a>]) {
+        for c in content {
+            match c {
+                BodyContent::Paragraph(p) => self.paragraph(p),
+                BodyContent::Table(t) => {
+                    for row in &mut t.rows {
+                        for cell in &mut row.cells {
+                            if let TableRowContent::TableCell(cell) = cell {
+                                for TableCellContent::Paragraph(p) in &mut cell.content {
+                                    self.paragraph(p);
+                                }
+                            }
+                        }
+                    }
+                }
+                BodyContent::Sdt(sdt) => {
+                    if let Some(content) = &mut sdt.content {
+                        self.blocks(&mut content.content);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn paragraph(&mut self, p: &mut Paragraph<'a>) {
+        if !self.rendered_breaks
+            && p.property
+                .as_ref()
+                .and_then(|p| p.page_break_before.as_ref())
+                .is_some_and(|b| b.value != Some(false))
+        {
+            self.page += 1;
+        }
+        for field in &mut self.open {
+            field.begin = None;
+            field.separate = None;
+        }
+
+        let mut pieces = Vec::new();
+        for content in mem::take(&mut p.content) {
+            match content {
+                ParagraphContent::Run(mut run) => {
+                    let index = self.runs.len();
+                    let content = mem::take(&mut run.content);
+                    self.runs.push(run);
+                    if content.is_empty() {
+                        pieces.push(Piece {
+                            run: Some(index),
+                            content: PieceContent::EmptyRun,
+                        });
+                    }
+                    pieces.extend(content.into_iter().map(|c| Piece {
+                        run: Some(index),
+                        content: PieceContent::Run(c),
+                    }));
+                }
+                content => pieces.push(Piece {
+                    run: None,
+                    content: PieceContent::Paragraph(content),
+                }),
+            }
+        }
+
+        let mut i = 0;
+        while i < pieces.len() {
+            i = self.piece(&mut pieces, i) + 1;
+        }
+
+        // joins the pieces back into runs
+        let mut last_run = None;
+        for piece in pieces {
+            match (piece.run, piece.content) {
+                (_, PieceContent::Paragraph(content)) => {
+                    last_run = None;
+                    p.content.push(content);
+                }
+                (Some(index), content) => {
+                    if last_run != Some(index) {
+                        p.content
+                            .push(ParagraphContent::Run(self.runs[index].clone()));
+                        last_run = Some(index);
+                    }
+                    if let (PieceContent::Run(content), Some(ParagraphContent::Run(run))) =
+                        (content, p.content.last_mut())
+                    {
+                        run.content.push(content);
+                    }
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    /// Processes the piece at `i`, and returns the position of its last piece
+    /// if the result of a field was replaced.
+    fn piece(&mut self, pieces: &mut Vec<Piece<'a>>, i: usize) -> usize {
+        if self.context.is_none() {
+            if let Some(run) = pieces[i].run {
+                for field in self.open.iter_mut().filter(|f| !f.in_instruction) {
+                    push_to_runs(&mut field.result_runs, &self.runs[run], &pieces[i].content);
+                }
+            }
+        }
+
+        match &mut pieces[i].content {
+            PieceContent::Paragraph(ParagraphContent::SimpleField(field)) => {
+                let mut parsed = Field::new(field.instruction.to_string());
+                parsed.locked = field.lock == Some(true);
+                parsed.result_runs = field.runs.clone();
+                let mut result = parsed.result();
+                if let Some(value) = self.evaluate(&parsed) {
+                    let property = field.runs.first().and_then(|r| r.property.clone());
+                    field.runs = vec![Run {
+                        property,
+                        ..Default::default()
+                    }
+                    .push_text((value.clone(), TextSpace::Preserve))];
+                    field.dirty = None;
+                    parsed.result_runs = field.runs.clone();
+                    result = value;
+                }
+                self.field_finished(parsed, result);
+            }
+            PieceContent::Run(RunContent::FieldChar(field_char)) => match field_char.ty {
+                Some(CharType::Begin) => {
+                    let locked = field_char.lock == Some(true);
+                    if !locked && !self.references {
+                        if let Some(data) = &mut field_char.form_data {
+                            self.fill_choice(data);
+                        }
+                    }
+                    self.open.push(OpenField {
+                        begin: Some(i),
+                        separate: None,
+                        in_instruction: true,
+                        instruction: String::new(),
+                        result: String::new(),
+                        result_runs: Vec::new(),
+                        locked,
+                        form_data: field_char.form_data.clone(),
+                    })
+                }
+                Some(CharType::Separate) => {
+                    if let Some(field) = self.open.last_mut() {
+                        field.in_instruction = false;
+                        field.separate = Some(i);
+                    }
+                }
+                Some(CharType::End) => {
+                    if let Some(field) = self.open.pop() {
+                        return self.field_end(pieces, i, field);
+                    }
+                }
+                None => {}
+            },
+            PieceContent::Run(RunContent::InstrText(text)) => {
+                if let Some(field) = self.open.last_mut().filter(|f| f.in_instruction) {
+                    field.instruction.push_str(&text.text);
+                }
+            }
+            PieceContent::Run(RunContent::Text(text)) => {
+                if let Some(field) = self.open.last_mut().filter(|f| !f.in_instruction) {
+                    field.result.push_str(&text.text);
+                }
+            }
+            PieceContent::Run(content) if starts_page(content, self.rendered_breaks) => {
+                self.page += 1;
+            }
+            _ => {}
+        }
+        i
+    }
+
+    fn field_end(&mut self, pieces: &mut Vec<Piece<'a>>, end: usize, open: OpenField<'a>) -> usize {
+        let mut field = Field::new(open.instruction);
+        field.locked = open.locked;
+        field.result_runs = open.result_runs;
+        field.form_data = open.form_data;
+        // the end character itself was added to the result
+        if let Some(run) = field.result_runs.last_mut() {
+            if let Some(RunContent::FieldChar(_)) = run.content.last() {
+                run.content.pop();
+            }
+            if run.content.is_empty() {
+                field.result_runs.pop();
+            }
+        }
+
+        let Some(begin) = open.begin else {
+            self.field_finished(field, open.result);
+            return end;
+        };
+        let Some(value) = self.evaluate(&field) else {
+            self.field_finished(field, open.result);
+            return end;
+        };
+
+        let start = open.separate.map_or(end, |s| s + 1);
+        let property = pieces[start..end]
+            .iter()
+            .find(|p| matches!(p.content, PieceContent::Run(_)))
+            .or(pieces.get(begin))
+            .and_then(|p| p.run)
+            .and_then(|r| self.runs[r].property.clone());
+
+        // keeps bookmarks and comment ranges inside of the old result
+        let kept: Vec<_> = pieces
+            .drain(start..end)
+            .filter(|p| {
+                matches!(
+                    p.content,
+                    PieceContent::Paragraph(
+                        ParagraphContent::BookmarkStart(_)
+                            | ParagraphContent::BookmarkEnd(_)
+                            | ParagraphContent::CommentRangeStart(_)
+                            | ParagraphContent::CommentRangeEnd(_)
+                    )
+                )
+            })
+            .collect();
+
+        let mut new = Vec::new();
+        if open.separate.is_none() {
+            new.push(self.new_piece(&property, RunContent::FieldChar(CharType::Separate.into())));
+        }
+        if !value.is_empty() {
+            let text = (value.clone(), TextSpace::Preserve).into();
+            new.push(self.new_piece(&property, RunContent::Text(text)));
+        }
+        let result_runs = new
+            .iter()
+            .filter(|p| !matches!(p.content, PieceContent::Run(RunContent::FieldChar(_))))
+            .fold(Vec::new(), |mut runs, p| {
+                push_to_runs(&mut runs, &self.runs[p.run.unwrap_or_default()], &p.content);
+                runs
+            });
+        new.extend(kept);
+        let end = start + new.len();
+        pieces.splice(start..start, new);
+
+        if let PieceContent::Run(RunContent::FieldChar(begin)) = &mut pieces[begin].content {
+            begin.dirty = None;
+        }
+        field.result_runs = result_runs;
+        self.field_finished(field, value);
+        end
+    }
+
+    fn new_piece(
+        &mut self,
+        property: &Option<CharacterProperty<'a>>,
+        content: RunContent<'a>,
+    ) -> Piece<'a> {
+        self.runs.push(Run {
+            property: property.clone(),
+            ..Default::default()
+        });
+        Piece {
+            run: Some(self.runs.len() - 1),
+            content: PieceContent::Run(content),
+        }
+    }
+
+    /// Passes the result of a field on to the field around it.
+    fn field_finished(&mut self, field: Field<'a>, result: String) {
+        if let Some(outer) = self.open.last_mut() {
+            if outer.in_instruction {
+                outer.instruction.push_str(&result);
+            } else {
+                outer.result.push_str(&result);
+            }
+        }
+        if self.context.is_none() {
+            self.fields.push(field);
+        }
+    }
+
+    /// Selects the value of a check box or drop-down form field.
+    fn fill_choice(&mut self, data: &mut FormFieldData<'a>) {
+        let Some(value) = self.context.and_then(|c| c.form_data.get(data.name()?)) else {
+            return;
+        };
+        if let Some(check_box) = &mut data.check_box {
+            check_box.checked = Some(matches!(value.as_str(), "true" | "1").into());
+        } else if let Some(list) = &mut data.drop_down_list {
+            let Some(index) = list.entries.iter().position(|e| e.value == *value) else {
+                return;
+            };
+            list.result = Some(FormDropDownResult { value: index });
+        } else {
+            return;
+        }
+        self.updated += 1;
+    }
+
+    fn evaluate(&mut self, field: &Field) -> Option<String> {
+        let context = self.context?;
+        if field.locked || (self.forms_only && field.name != "FORMTEXT") {
+            return None;
+        }
+        let reference = matches!(field.name.as_str(), "REF" | "PAGEREF");
+        if reference != self.references {
+            return None;
+        }
+
+        let argument = field.arguments.first().map(|a| a.as_str());
+        let value = match field.name.as_str() {
+            "DATE" => context
+                .now
+                .format(field.switch("@").unwrap_or(&context.date_format)),
+            "TIME" => context
+                .now
+                .format(field.switch("@").unwrap_or(&context.time_format)),
+            "PAGE" if self.in_body => self.page.to_string(),
+            "NUMPAGES" => self.pages.to_string(),
+            "DOCPROPERTY" => self
+                .properties
+                .get(&argument?.to_ascii_lowercase())?
+                .clone(),
+            "TITLE" | "SUBJECT" | "AUTHOR" | "KEYWORDS" | "COMMENTS" | "LASTSAVEDBY"
+            | "TEMPLATE" => self
+                .properties
+                .get(&field.name.to_ascii_lowercase())?
+                .clone(),
+            "DOCVARIABLE" => self.variables.get(argument?)?.clone(),
+            "REF" => self.bookmarks.get(argument?)?.0.clone(),
+            "PAGEREF" => self.bookmarks.get(argument?)?.1.to_string(),
+            "SEQ" => {
+                let counter = self.sequences.entry(argument?.to_string()).or_insert(0);
+                if let Some(reset) = field.switch("r").and_then(|r| r.parse().ok()) {
+                    *counter = reset;
+                } else if field.switch("c").is_none() {
+                    *counter += 1;
+                }
+                if field.switch("h").is_some() {
+                    return Some(String::new());
+                }
+                counter.to_string()
+            }
+            "MERGEFIELD" => {
+                let value = context.merge_data.get(argument?)?;
+                if value.is_empty() {
+                    String::new()
+                } else {
+                    let before = field.switch("b").unwrap_or_default();
+                    let after = field.switch("f").unwrap_or_default();
+                    format!("{}{}{}", before, value, after)
+                }
+            }
+            "IF" => evaluate_if(&field.arguments)?,
+            "FORMTEXT" => {
+                let data = field.form_data.as_ref()?;
+                let value = context.form_data.get(data.name()?)?;
+                let input = data.text_input.as_ref();
+                let max = input
+                    .and_then(|i| i.max_length.as_ref())
+                    .map_or(usize::MAX, |m| m.value);
+                let value: String = value.chars().take(max).collect();
+                let format = match input.and_then(|i| i.format.as_ref()).map(|f| &f.value) {
+                    Some(f) if f == "UPPERCASE" => "Upper",
+                    Some(f) if f == "LOWERCASE" => "Lower",
+                    Some(f) if f == "FIRST CAPITAL" => "FirstCap",
+                    Some(f) if f == "TITLE CASE" => "Caps",
+                    _ => "",
+                };
+                format_general(&value, format)
+            }
+            _ => return None,
+        };
+
+        let mut value = match field.switch("#") {
+            Some(picture) => value
+                .trim()
+                .parse::<f64>()
+                .map_or(value, |n| format_numeric(n, picture)),
+            None => value,
+        };
+        for switch in field.switches.iter().filter(|s| s.name == "*") {
+            value = format_general(&value, switch.value.as_deref().unwrap_or_default());
+        }
+        self.updated += 1;
+        Some(value)
+    }
+}
+
+/// Appends a piece to the last run if it comes from the same run.
+fn push_to_runs<'a>(runs: &mut Vec<Run<'a>>, origin: &Run<'a>, content: &PieceContent<'a>) {
+    let content = match content {
+        PieceContent::Run(content) => Some(content.clone()),
+        PieceContent::EmptyRun => None,
+        PieceContent::Paragraph(_) => return,
+    };
+    let same = runs
+        .last()
+        .is_some_and(|r| r.rsid_r == origin.rsid_r && r.property == origin.property);
+    if !same || content.is_none() {
+        runs.push(origin.clone());
+    }
+    if let (Some(content), Some(run)) = (content, runs.last_mut()) {
+        run.content.push(content);
+    }
+}
+
+/// `IF left operator right "true text" "false text"`
+fn evaluate_if(arguments: &[String]) -> Option<String> {
+    let [left, operator, right, rest @ ..] = arguments else {
+        return None;
+    };
+    let numbers = left.parse::<f64>().ok().zip(right.parse::<f64>().ok());
+    let ordering = match numbers {
+        Some((l, r)) => l.partial_cmp(&r)?,
+        None => left.as_str().cmp(right.as_str()),
+    };
+    let matched = match operator.as_str() {
+        "=" => ordering.is_eq(),
+        "<>" => ordering.is_ne(),
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        _ => return None,
+    };
+    let index = if matched { 0 } else { 1 };
+    Some(rest.get(index).cloned().unwrap_or_default())
+}
+
+/// Applies a `\*` switch, like `Upper`, `roman` or `Ordinal`.
+fn format_general(value: &str, format: &str) -> String {
+    let number = value.trim().parse::<usize>().ok();
+    match (format, number) {
+        ("roman", Some(n)) => format_number(n, "lowerRoman"),
+        ("ROMAN" | "Roman", Some(n)) => format_number(n, "upperRoman"),
+        ("alphabetic", Some(n)) => format_number(n, "lowerLetter"),
+        ("ALPHABETIC" | "Alphabetic", Some(n)) => format_number(n, "upperLetter"),
+        ("hex", Some(n)) => format!("{:x}", n),
+        ("HEX" | "Hex", Some(n)) => format!("{:X}", n),
+        (f, Some(n)) if f.eq_ignore_ascii_case("ordinal") => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{}{}", n, suffix)
+        }
+        (f, _) if f.eq_ignore_ascii_case("upper") => value.to_uppercase(),
+        (f, _) if f.eq_ignore_ascii_case("lower") => value.to_lowercase(),
+        (f, _) if f.eq_ignore_ascii_case("firstcap") => {
+            let mut chars = value.chars();
+            chars.next().map_or(String::new(), |c| {
+                c.to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+        }
+        (f, _) if f.eq_ignore_ascii_case("caps") => value
+            .split(' ')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or(String::new(), |c| {
+                    c.to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                        .collect()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => value.to_string(),
+    }
+}
+
+/// Applies a `\#` picture, like `#,##0.00` or `$0.0`.
+fn format_numeric(value: f64, picture: &str) -> String {
+    let is_placeholder = |c: char| c == '0' || c == '#';
+    let (Some(first), Some(last)) = (picture.find(is_placeholder), picture.rfind(is_placeholder))
+    else {
+        return value.to_string();
+    };
+    let prefix = &picture[..first];
+    let number = &picture[first..=last];
+    let suffix = &picture[last + 1..];
+
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let decimals = fraction.chars().filter(|c| is_placeholder(*c)).count();
+    let min_digits = integer.chars().filter(|c| *c == '0').count();
+    let grouping = integer.contains(',');
+
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let mut int_part = int_part.trim_start_matches('0').to_string();
+    while int_part.len() < min_digits {
+        int_part.insert(0, '0');
+    }
+    if grouping {
+        let digits: Vec<char> = int_part.chars().collect();
+        int_part = digits
+            .rchunks(3)
+            .rev()
+            .map(|c| c.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join(",");
+    }
+
+    let mut out = String::new();
+    if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        out.push('-');
+    }
+    out.push_str(prefix);
+    out.push_str(&int_part);
+    if !frac_part.is_empty() {
+        out.push('.');
+        out.push_str(frac_part);
+    }
+    out.push_str(suffix);
+    out
+}
+
+/// Whether a new page starts after the content, counting either the explicit
+/// page breaks or those Word rendered when the document was last saved.
+fn starts_page(content: &RunContent, rendered_breaks: bool) -> bool {
+    match content {
+        RunContent::Break(br) => !rendered_breaks && matches!(br.ty, Some(BreakType::Page)),
+        RunContent::LastRenderedPageBreak(_) => rendered_breaks,
+        _ => false,
+    }
+}
+
+fn has_rendered_breaks(content: &BodyContent) -> bool {
+    match content {
+        BodyContent::Paragraph(p) => p.content.iter().any(|c| match c {
+            ParagraphContent::Run(r) => r
+                .content
+                .iter()
+                .any(|c| matches!(c, RunContent::LastRenderedPageBreak(_))),
+            _ => false,
+        }),
+        BodyContent::Table(t) => t.rows.iter().flat_map(|r| &r.cells).any(|c| match c {
+            TableRowContent::TableCell(c) => c.content.iter().any(|c| {
+                let TableCellContent::Paragraph(p) = c;
+                has_rendered_breaks(&BodyContent::Paragraph(p.clone()))
+            }),
+            _ => false,
+        }),
+        BodyContent::Sdt(sdt) => sdt
+            .content
+            .iter()
+            .flat_map(|c| &c.content)
+            .any(has_rendered_breaks),
+        _ => false,
+    }
+}
+
+fn page_count(content: &[BodyContent], rendered_breaks: bool) -> usize {
+    let mut scanner = BookmarkScanner::new(rendered_breaks);
+    scanner.blocks(content);
+    scanner.page
+}
+
+/// Estimates the page each bookmark in the body starts on.
+pub(crate) fn bookmark_pages(content: &[BodyContent]) -> HashMap<String, usize> {
+    let rendered_breaks = content.iter().any(has_rendered_breaks);
+    bookmarks(content, rendered_breaks)
+        .into_iter()
+        .map(|(name, (_, page))| (name, page))
+        .collect()
+}
+
+/// Collects the text and page of each bookmark in the body.
+pub(crate) fn bookmarks(
+    content: &[BodyContent],
+    rendered_breaks: bool,
+) -> HashMap<String, (String, usize)> {
+    let mut scanner = BookmarkScanner::new(rendered_breaks);
+    scanner.blocks(content);
+    scanner
+        .bookmarks
+        .into_iter()
+        .map(|(name, (text, page))| (name, (text.trim().to_string(), page)))
+        .collect()
+}
+
+struct BookmarkScanner {
+    rendered_breaks: bool,
+    page: usize,
+    /// Names of the bookmarks not ended yet, by id
+    open: HashMap<String, String>,
+    bookmarks: HashMap<String, (String, usize)>,
+    /// Nesting of fields, `true` while in their instructions
+    fields: Vec<bool>,
+}
+
+impl BookmarkScanner {
+    fn new(rendered_breaks: bool) -> Self {
+        BookmarkScanner {
+            rendered_breaks,
+            page: 1,
+            open: HashMap::new(),
+            bookmarks: HashMap::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for c in content {
+            match c {
+                BodyContent::Paragraph(p) => self.paragraph(p),
+                BodyContent::Table(t) => {
+                    for row in &t.rows {
+                        for cell in &row.cells {
+                            if let TableRowContent::TableCell(cell) = cell {
+                                for TableCellContent::Paragraph(p) in &cell.content {
+                                    self.paragraph(p);
+                                }
+                            }
+                        }
+                    }
+                }
+                BodyContent::Sdt(sdt) => {
+                    if let Some(content) = &sdt.content {
+                        self.blocks(&content.content);
+                    }
+                }
+                BodyContent::BookmarkStart(start) => self.bookmark_start(start),
+                BodyContent::BookmarkEnd(end) => self.bookmark_end(end),
+                _ => {}
+            }
+        }
+    }
+
+    fn bookmark_start(&mut self, start: &BookmarkStart) {
+        if let (Some(id), Some(name)) = (&start.id, &start.name) {
+            self.open.insert(id.to_string(), name.to_string());
+            self.bookmarks
+                .insert(name.to_string(), (String::new(), self.page));
+        }
+    }
+
+    fn bookmark_end(&mut self, end: &BookmarkEnd) {
+        if let Some(id) = &end.id {
+            self.open.remove(id.as_ref());
+        }
+    }
+
+    fn paragraph(&mut self, p: &Paragraph) {
+        if !self.rendered_breaks
+            && p.property
+                .as_ref()
+                .and_then(|p| p.page_break_before.as_ref())
+                .is_some_and(|b| b.value != Some(false))
+        {
+            self.page += 1;
+        }
+        for content in &p.content {
+            match content {
+                ParagraphContent::BookmarkStart(start) => self.bookmark_start(start),
+                ParagraphContent::BookmarkEnd(end) => self.bookmark_end(end),
+                ParagraphContent::Run(run) => self.run(run),
+                ParagraphContent::SimpleField(field) => {
+                    for run in &field.runs {
+                        self.run(run);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.push_text(" ");
+    }
+
+    fn run(&mut self, run: &Run) {
+        for content in &run.content {
+            match content {
+                RunContent::FieldChar(c) => match c.ty {
+                    Some(CharType::Begin) => self.fields.push(true),
+                    Some(CharType::Separate) => {
+                        if let Some(f) = self.fields.last_mut() {
+                            *f = false;
+                        }
+                    }
+                    Some(CharType::End) => {
+                        self.fields.pop();
+                    }
+                    None => {}
+                },
+                RunContent::Text(t) if !self.fields.iter().any(|f| *f) => self.push_text(&t.text),
+                RunContent::Tab(_) => self.push_text("\t"),
+                content if starts_page(content, self.rendered_breaks) => self.page += 1,
+                _ => {}
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for name in self.open.values() {
+            if let Some((bookmark, _)) = self.bookmarks.get_mut(name) {
+                bookmark.push_str(text);
+            }
+        }
+    }
+}
+
+#[test]
+fn field_test() {
+    let field = Field::new(r#" MERGEFIELD  "First Name" \b "Dear " \* Upper \* MERGEFORMAT "#);
+    assert_eq!(field.name, "MERGEFIELD");
+    assert_eq!(field.arguments, ["First Name"]);
+    assert_eq!(field.switch("b"), Some("Dear "));
+    assert_eq!(field.switches.len(), 3);
+
+    let field = Field::new(r#"PAGEREF _Toc123 \h"#);
+    assert_eq!(field.arguments, ["_Toc123"]);
+    assert_eq!(field.switch("h"), Some(""));
+
+    assert_eq!(
+        evaluate_if(&["Female", "=", "Female", "Ms", "Mr"].map(String::from)),
+        Some("Ms".into())
+    );
+    assert_eq!(
+        evaluate_if(&["10", ">", "9", "yes", "no"].map(String::from)),
+        Some("yes".into())
+    );
+
+    assert_eq!(format_numeric(1234.5, "#,##0.00"), "1,234.50");
+    assert_eq!(format_numeric(0.5, "$0.0"), "$0.5");
+    assert_eq!(format_numeric(-3.0, "00"), "-03");
+    assert_eq!(format_general("12", "ROMAN"), "XII");
+    assert_eq!(format_general("22", "Ordinal"), "22nd");
+    assert_eq!(format_general("hello world", "Caps"), "Hello World");
+}
+
+#[test]
+fn date_time_test() {
+    let date = DateTime::from_unix_timestamp(1709303400);
+    assert_eq!(date, DateTime::new(2024, 3, 1, 14, 30, 0));
+    assert_eq!(
+        date.format("ddd d MMM yyyy HH:mm:ss"),
+        "Fri 1 Mar 2024 14:30:00"
+    );
+    assert_eq!(date.format("'Week of' MMMM"), "Week of March");
+    assert_eq!(
+        DateTime::from_unix_timestamp(0).format("yyyy-MM-dd"),
+        "1970-01-01"
+    );
+    assert_eq!(
+        DateTime::new(2000, 2, 29, 0, 0, 0).format("dddd h AM/PM"),
+        "Tuesday 12 AM"
+    );
+    assert_eq!(date.to_iso8601(), "2024-03-01T14:30:00Z");
+    assert_eq!(
+        DateTime::parse_iso8601("2024-03-01T14:30:00.25Z"),
+        Some(date)
+    );
+    assert_eq!(
+        DateTime::parse_iso8601("2024-03-01"),
+        Some(DateTime::new(2024, 3, 1, 0, 0, 0))
+    );
+    assert_eq!(DateTime::parse_iso8601("2024-13-01T00:00:00Z"), None);
+    assert_eq!(
+        DateTime::parse_iso8601("2024-03-01T16:00+01:30"),
+        Some(DateTime::new(2024, 3, 1, 14, 30, 0))
+    );
+    assert_eq!(
+        DateTime::parse_iso8601("2024"),
+        Some(DateTime::new(2024, 1, 1, 0, 0, 0))
+    );
+    assert_eq!(date.to_unix_timestamp(), 1709303400);
+    assert_eq!(
+        DateTime::new(1969, 12, 31, 23, 59, 59).to_unix_timestamp(),
+        -1
+    );
+}
@@ -0,0 +1,190 @@
+//! Legacy form fields
+//!
+//! Templates made with older versions of Word use `FORMTEXT`, `FORMCHECKBOX` and
+//! `FORMDROPDOWN` fields, whose name, default value and choices are stored in the
+//! `w:ffData` of their `begin` field character. [`Docx::form_fields`] lists them, and
+//! [`Docx::set_form_field`] fills them in. [`Docx::protect_forms`] locks the rest of
+//! the document, so that only the form fields can be edited.
+//!
+//! ```rust
+//! use docx_rust::{document::*, form::FormValue, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(
+//!     Paragraph::default()
+//!         .push_text("Name: ")
+//!         .push(Run::default().push(
+//!             FieldChar::from(CharType::Begin).form_data(
+//!                 FormFieldData::new("Name").text_input(FormTextInput::default().max_length(10)),
+//!             ),
+//!         ))
+//!         .push(Run::default().push(InstrText::from(" FORMTEXT ")))
+//!         .push(Run::default().push(FieldChar::from(CharType::End))),
+//! );
+//!
+//! assert_eq!(docx.set_form_field("Name", "Alice").unwrap(), 1);
+//! assert!(docx.set_form_field("Name", "Bartholomew Jr.").is_err());
+//! assert_eq!(docx.form_fields()[0].value, FormValue::Text("Alice".into()));
+//! docx.protect_forms();
+//! ```
+//!
+//! [`Docx::form_fields`]: ../struct.Docx.html#method.form_fields
+//! [`Docx::set_form_field`]: ../struct.Docx.html#method.set_form_field
+//! [`Docx::protect_forms`]: ../struct.Docx.html#method.protect_forms
+
+use crate::{
+    document::{FormFieldData, FormTextType},
+    field::FieldContext,
+    settings::DocumentProtectionType,
+    Docx, DocxError, DocxResult,
+};
+
+/// The kind of a form field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormFieldType {
+    /// `FORMTEXT`
+    Text,
+    /// `FORMCHECKBOX`
+    CheckBox,
+    /// `FORMDROPDOWN`
+    DropDown,
+}
+
+/// A value of a form field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormValue {
+    /// Text of a text field, or the selected entry of a drop-down list
+    Text(String),
+    /// State of a check box
+    Checked(bool),
+}
+
+impl From<&str> for FormValue {
+    fn from(val: &str) -> Self {
+        FormValue::Text(val.to_string())
+    }
+}
+
+impl From<String> for FormValue {
+    fn from(val: String) -> Self {
+        FormValue::Text(val)
+    }
+}
+
+impl From<bool> for FormValue {
+    fn from(val: bool) -> Self {
+        FormValue::Checked(val)
+    }
+}
+
+/// A form field along with its current value
+#[derive(Debug, Clone)]
+pub struct FormField<'a> {
+    pub name: String,
+    pub ty: FormFieldType,
+    pub value: FormValue,
+    pub data: FormFieldData<'a>,
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the named form fields of the body, headers, footers, footnotes and endnotes.
+    pub fn form_fields(&self) -> Vec<FormField<'a>> {
+        self.fields()
+            .into_iter()
+            .filter_map(|field| {
+                let data = field.form_data.clone()?;
+                let name = data.name()?.to_string();
+                let (ty, value) = match field.name.as_str() {
+                    "FORMTEXT" => (FormFieldType::Text, FormValue::Text(field.result())),
+                    "FORMCHECKBOX" => (
+                        FormFieldType::CheckBox,
+                        FormValue::Checked(data.check_box.as_ref()?.is_checked()),
+                    ),
+                    "FORMDROPDOWN" => {
+                        let selected = data.drop_down_list.as_ref()?.selected();
+                        let selected = selected.unwrap_or_default().to_string();
+                        (FormFieldType::DropDown, FormValue::Text(selected))
+                    }
+                    _ => return None,
+                };
+                Some(FormField {
+                    name,
+                    ty,
+                    value,
+                    data,
+                })
+            })
+            .collect()
+    }
+
+    /// Fills in the form fields with a name, and returns how many were changed.
+    ///
+    /// Fails if the value doesn't fit one of the fields, like text longer than the
+    /// maximum length, text that isn't a number for a number field, or text that
+    /// isn't one of the entries of a drop-down list. Locked fields are left alone.
+    pub fn set_form_field<T: Into<FormValue>>(
+        &mut self,
+        name: &str,
+        value: T,
+    ) -> DocxResult<usize> {
+        let value = value.into();
+        let mut text = None;
+        for field in self.form_fields().iter().filter(|f| f.name == name) {
+            let checked = check_value(field, &value)?;
+            text.get_or_insert(checked);
+        }
+        let Some(text) = text else {
+            return Ok(0);
+        };
+        Ok(self.fill_form_fields(&FieldContext::default().form_field(name, text)))
+    }
+
+    /// Restricts editing to the form fields, except in sections whose
+    /// [`SectionProperty::form_prot`] is `false`.
+    ///
+    /// [`SectionProperty::form_prot`]: ../formatting/struct.SectionProperty.html#structfield.form_prot
+    pub fn protect_forms(&mut self) {
+        self.protect(DocumentProtectionType::Forms, None);
+    }
+}
+
+/// Checks a value against a form field, and returns it as text.
+fn check_value(field: &FormField, value: &FormValue) -> DocxResult<String> {
+    let invalid = |message: String| Err(DocxError::FormField(message));
+    match (field.ty, value) {
+        (FormFieldType::CheckBox, FormValue::Checked(checked)) => Ok(checked.to_string()),
+        (FormFieldType::Text, FormValue::Text(text)) => {
+            let input = field.data.text_input.as_ref();
+            let max_length = input.and_then(|i| i.max_length.as_ref());
+            if let Some(max) = max_length.filter(|m| text.chars().count() > m.value) {
+                return invalid(format!(
+                    "{:?} is longer than {} characters",
+                    text, max.value
+                ));
+            }
+            match input
+                .and_then(|i| i.ty.as_ref())
+                .and_then(|t| t.value.as_ref())
+            {
+                Some(FormTextType::Number) if text.trim().parse::<f64>().is_err() => {
+                    invalid(format!("{:?} is not a number", text))
+                }
+                Some(
+                    FormTextType::CurrentDate
+                    | FormTextType::CurrentTime
+                    | FormTextType::Calculated,
+                ) => invalid(format!("{} can't be filled in", field.name)),
+                _ => Ok(text.clone()),
+            }
+        }
+        (FormFieldType::DropDown, FormValue::Text(text)) => {
+            let list = field.data.drop_down_list.as_ref();
+            if list.is_some_and(|l| l.entries.iter().any(|e| e.value == *text)) {
+                Ok(text.clone())
+            } else {
+                invalid(format!("{:?} is not an entry of {}", text, field.name))
+            }
+        }
+        (ty, value) => invalid(format!("{:?} doesn't fit a {:?} field", value, ty)),
+    }
+}
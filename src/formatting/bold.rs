@@ -10,16 +10,14 @@ use crate::__xml_test_suites;
 /// let bold = Bold::from(false);
 /// let bold = Bold::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:b")]
 pub struct Bold {
     #[xml(attr = "w:val")]
     pub value: Option<bool>,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:bCs")]
 pub struct BoldComplex {
     #[xml(attr = "w:val")]
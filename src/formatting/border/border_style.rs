@@ -1,7 +1,6 @@
 use crate::__string_enum;
 
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum BorderStyle {
     Nil, //No Border
     #[default]
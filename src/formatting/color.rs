@@ -15,8 +15,7 @@ use crate::{__string_enum, __xml_test_suites};
 /// let color = Color::from(0u32); // "000000"
 /// let color = Color::from((0u8, 0u8, 0u8)); // "000000"
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:color")]
 pub struct Color<'a> {
     #[xml(attr = "w:val")]
@@ -55,16 +54,14 @@ impl From<(u8, u8, u8)> for Color<'_> {
     }
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:highlight")]
 pub struct Highlight {
     #[xml(attr = "w:val")]
     pub value: Option<HighlightType>,
 }
 
-#[derive(Debug, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, PartialEq)]
 pub enum HighlightType {
     Black,       //Black Highlighting Color
     Blue,        //Blue Highlighting Color
@@ -107,16 +104,14 @@ __string_enum! {
     }
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:vertAlign")]
 pub struct VertAlign {
     #[xml(attr = "w:val")]
     pub value: Option<VertAlignType>,
 }
 
-#[derive(Debug, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, PartialEq)]
 pub enum VertAlignType {
     Baseline,    //Regular Vertical Positioning
     Superscript, //	Superscript
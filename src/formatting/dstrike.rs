@@ -10,16 +10,14 @@ use crate::__xml_test_suites;
 /// let dstrike = Dstrike::from(false);
 /// let dstrike = Dstrike::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:dstrike")]
 pub struct Dstrike {
     #[xml(attr = "w:val")]
     pub value: Option<bool>,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:position")]
 pub struct Position {
     #[xml(attr = "w:val")]
@@ -2,7 +2,9 @@
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::__xml_test_suites;
+use crate::{__setter, __xml_test_suites};
+
+use super::Twips;
 
 /// indent
 /// percent to one character.
@@ -10,7 +12,11 @@ use crate::__xml_test_suites;
 /// ```rust
 /// use docx_rust::formatting::*;
 ///
-/// let sz = Size::from(42isize);
+/// let ind = Indent::default().first_line(Pt(21.0)).hanging(Cm(0.5));
+///
+/// // the twip setters leave the character units alone
+/// let ind = Indent::default().left(Cm(1.0)).left_chars(100isize);
+/// assert_eq!((ind.left, ind.left_chars), (Some(567), Some(100)));
 /// ```
 #[derive(Debug, XmlRead, XmlWrite, Clone, Default)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -33,36 +39,21 @@ pub struct Indent {
 }
 
 impl Indent {
-    fn left<T>(mut self, val: T) -> Self
-    where
-        isize: From<T>,
-    {
-        self.left_chars = Some(val.into());
-        self.left = self.left_chars;
-        self
-    }
-
-    fn right<T>(mut self, val: T) -> Self
-    where
-        isize: From<T>,
-    {
-        self.right_chars = Some(val.into());
-        self.right = self.right_chars;
-        self
-    }
-
-    fn first_line<T>(mut self, val: T) -> Self
-    where
-        isize: From<T>,
-    {
-        self.first_line = Some(val.into());
-        self.first_line_chars = self.first_line;
-        self
-    }
+    __setter!(left: Option<isize> as Twips);
+    __setter!(right: Option<isize> as Twips);
+    __setter!(first_line: Option<isize> as Twips);
+    __setter!(hanging: Option<isize> as Twips);
+    __setter!(left_chars: Option<isize>);
+    __setter!(right_chars: Option<isize>);
+    __setter!(first_line_chars: Option<isize>);
 }
 
 __xml_test_suites!(
     Indent,
     Indent::default().first_line(200isize),
-    r#"<w:ind w:firstLineChars="200" w:firstLine="200"/>"#,
+    r#"<w:ind w:firstLine="200"/>"#,
+    Indent::default().left(super::Pt(18.0)).left_chars(150isize),
+    r#"<w:ind w:leftChars="150" w:left="360"/>"#,
+    Indent::default().hanging(super::Cm(1.0)),
+    r#"<w:ind w:hanging="567"/>"#,
 );
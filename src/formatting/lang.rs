@@ -11,8 +11,7 @@ use crate::{__setter, __xml_test_suites};
 ///
 /// let sz = Size::from(42isize);
 /// ```
-#[derive(Debug, XmlRead, XmlWrite, Clone, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, XmlRead, XmlWrite, Clone, Default, PartialEq)]
 #[xml(tag = "w:lang")]
 pub struct Lang<'a> {
     #[xml(attr = "w:val")]
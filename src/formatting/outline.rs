@@ -10,8 +10,7 @@ use crate::__xml_test_suites;
 /// let outline = Outline::from(false);
 /// let outline = Outline::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:outline")]
 pub struct Outline {
     #[xml(attr = "w:val")]
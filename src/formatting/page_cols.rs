@@ -1,28 +1,42 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-/// Numbering Id
+use crate::{__setter, __xml_test_suites};
+
+/// Page Columns
 ///
 /// ```rust
 /// use docx_rust::formatting::*;
 ///
-/// let id = NumberingId::from(42isize);
+/// let cols = PageCols::default().num(2isize).space(720isize).sep(true);
 /// ```
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:cols")]
 pub struct PageCols {
+    /// Space between columns, in twentieths of a point
     #[xml(attr = "w:space")]
     pub space: Option<isize>,
+    /// Number of columns
+    #[xml(attr = "w:num")]
+    pub num: Option<isize>,
+    /// Whether a line separates the columns
+    #[xml(attr = "w:sep")]
+    pub sep: Option<bool>,
+    #[xml(attr = "w:equalWidth")]
+    pub equal_width: Option<bool>,
 }
 
-// impl<T: Into<isize>> From<T> for NumberingId {
-//     fn from(val: T) -> Self {
-//         NumberingId { value: val.into() }
-//     }
-// }
+impl PageCols {
+    __setter!(space: Option<isize>);
+    __setter!(num: Option<isize>);
+    __setter!(sep: Option<bool>);
+    __setter!(equal_width: Option<bool>);
+}
 
-// __xml_test_suites!(
-//     NumberingId,
-//     NumberingId::from(40isize),
-//     r#"<w:numId w:val="40"/>"#,
-// );
+__xml_test_suites!(
+    PageCols,
+    PageCols::default(),
+    r#"<w:cols/>"#,
+    PageCols::default().num(2isize).space(720isize).sep(true),
+    r#"<w:cols w:space="720" w:num="2" w:sep="true"/>"#,
+);
@@ -1,11 +1,17 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-/// Numbering Id
+use crate::__setter;
+
+use super::Twips;
+
+/// Page Margins
+///
+/// Margins are in twentieths of a point.
 ///
 /// ```rust
 /// use docx_rust::formatting::*;
 ///
-/// let id = NumberingId::from(42isize);
+/// let margin = PageMargin::default().top(Cm(2.5)).bottom(Cm(2.5)).gutter(0isize);
 /// ```
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -27,6 +33,16 @@ pub struct PageMargin {
     pub gutter: Option<isize>,
 }
 
+impl PageMargin {
+    __setter!(top: Option<isize> as Twips);
+    __setter!(right: Option<isize> as Twips);
+    __setter!(bottom: Option<isize> as Twips);
+    __setter!(left: Option<isize> as Twips);
+    __setter!(header: Option<isize> as Twips);
+    __setter!(footer: Option<isize> as Twips);
+    __setter!(gutter: Option<isize> as Twips);
+}
+
 // impl<T: Into<isize>> From<T> for NumberingId {
 //     fn from(val: T) -> Self {
 //         NumberingId { value: val.into() }
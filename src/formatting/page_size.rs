@@ -1,11 +1,17 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-/// Numbering Id
+use crate::{__setter, __string_enum, __xml_test_suites};
+
+use super::Twips;
+
+/// Page Size
+///
+/// Width and height are in twentieths of a point.
 ///
 /// ```rust
 /// use docx_rust::formatting::*;
 ///
-/// let id = NumberingId::from(42isize);
+/// let size = PageSize::new(Cm(21.0), Cm(29.7)).orient(PageOrientation::Portrait);
 /// ```
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -15,16 +21,40 @@ pub struct PageSize {
     pub weight: isize,
     #[xml(attr = "w:h")]
     pub height: isize,
+    #[xml(attr = "w:orient")]
+    pub orient: Option<PageOrientation>,
+}
+
+impl PageSize {
+    __setter!(orient: Option<PageOrientation>);
+
+    pub fn new<W: Into<Twips>, H: Into<Twips>>(width: W, height: H) -> Self {
+        PageSize {
+            weight: width.into().0,
+            height: height.into().0,
+            orient: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageOrientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+__string_enum! {
+    PageOrientation {
+        Portrait = "portrait",
+        Landscape = "landscape",
+    }
 }
 
-// impl<T: Into<isize>> From<T> for NumberingId {
-//     fn from(val: T) -> Self {
-//         NumberingId { value: val.into() }
-//     }
-// }
-
-// __xml_test_suites!(
-//     NumberingId,
-//     NumberingId::from(40isize),
-//     r#"<w:numId w:val="40"/>"#,
-// );
+__xml_test_suites!(
+    PageSize,
+    PageSize::new(super::Cm(21.0), super::Cm(29.7)),
+    r#"<w:pgSz w:w="11906" w:h="16838"/>"#,
+    PageSize::new(16838, 11906).orient(PageOrientation::Landscape),
+    r#"<w:pgSz w:w="16838" w:h="11906" w:orient="landscape"/>"#,
+);
@@ -135,6 +135,7 @@ impl<'a> ParagraphProperty<'a> {
     __setter!(numbering: Option<NumberingProperty<'a>>);
     __setter!(spacing: Option<Spacing>);
     __setter!(indent: Option<Indent>);
+    __setter!(section_property: Option<SectionProperty<'a>>);
 }
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -255,8 +256,7 @@ pub struct AdjustRightInd {
     pub value: Option<bool>,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:snapToGrid")]
 pub struct SnapToGrid {
     #[xml(attr = "w:val")]
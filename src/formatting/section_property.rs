@@ -3,9 +3,9 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __define_enum, __define_struct, __string_enum,
+    __define_enum, __define_struct, __setter, __string_enum,
     document::HeaderFooterReference,
-    formatting::{PageCols, PageGrid, PageMargin, PageSize},
+    formatting::{Inch, PageCols, PageGrid, PageMargin, PageOrientation, PageSize},
 };
 
 use super::Bidi;
@@ -376,6 +376,12 @@ pub struct SectionTypeP {
     pub ty: Option<SectionType>,
 }
 
+impl From<SectionType> for SectionTypeP {
+    fn from(ty: SectionType) -> Self {
+        SectionTypeP { ty: Some(ty) }
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:formProt")]
@@ -384,6 +390,12 @@ pub struct FormProt {
     pub val: Option<bool>,
 }
 
+impl From<bool> for FormProt {
+    fn from(val: bool) -> Self {
+        FormProt { val: Some(val) }
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:noEndnote")]
@@ -852,6 +864,49 @@ impl<'a> SectionProperty<'a> {
     //     __setter!(numbering: Option<NumberingProperty>);
     //     __setter!(spacing: Option<Spacing>);
     //     __setter!(indent: Option<Indent>);
+    __setter!(ty: Option<SectionTypeP>);
+    __setter!(page_size: Option<PageSize>);
+    __setter!(page_margin: Option<PageMargin>);
+    __setter!(page_numbering: Option<PgNumType>);
+    __setter!(cols: Option<PageCols>);
+    __setter!(form_prot: Option<FormProt>);
+
+    /// Sets the orientation of the pages, swapping their width and height if needed.
+    ///
+    /// Sections without a page size get a Letter one.
+    pub fn set_orientation(&mut self, orientation: PageOrientation) -> &mut Self {
+        let size = self
+            .page_size
+            .get_or_insert_with(|| PageSize::new(Inch(8.5), Inch(11.0)));
+        let landscape = size.weight > size.height;
+        if landscape != (orientation == PageOrientation::Landscape) {
+            std::mem::swap(&mut size.weight, &mut size.height);
+        }
+        size.orient = Some(orientation);
+        self
+    }
+
+    /// Lays the text out in `num` columns, `space` twentieths of a point apart.
+    pub fn set_columns(&mut self, num: isize, space: isize) -> &mut Self {
+        let cols = self.cols.get_or_insert_with(Default::default);
+        cols.num = Some(num);
+        cols.space = Some(space);
+        self
+    }
+
+    pub fn set_margins(&mut self, margin: PageMargin) -> &mut Self {
+        self.page_margin = Some(margin);
+        self
+    }
+
+    /// Starts numbering the pages of the section again from `start`.
+    pub fn restart_page_numbering(&mut self, start: isize) -> &mut Self {
+        self.page_numbering
+            .get_or_insert_with(Default::default)
+            .start = Some(start);
+        self
+    }
+
     fn first_page_has_diffrent_header_and_footer(&mut self, val: bool) -> &mut Self {
         if val {
             self.title_page = Some(TitlePage::default());
@@ -2,25 +2,61 @@ use hard_xml::{XmlRead, XmlWrite};
 
 use crate::__xml_test_suites;
 
+use super::{Cm, HalfPoints, Inch, Pt, Twips};
+
 /// Size
 ///
+/// Font size, in half-points
+///
 /// ```rust
 /// use docx_rust::formatting::*;
 ///
 /// let sz = Size::from(42isize);
+/// let sz = Size::from(Pt(10.5));
 /// ```
-#[derive(Debug, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:sz")]
 pub struct Size {
     #[xml(attr = "w:val")]
     pub value: isize,
 }
 
-impl<T: Into<isize>> From<T> for Size {
-    fn from(val: T) -> Self {
-        Size { value: val.into() }
+impl From<isize> for Size {
+    fn from(val: isize) -> Self {
+        Size { value: val }
+    }
+}
+
+impl From<HalfPoints> for Size {
+    fn from(val: HalfPoints) -> Self {
+        Size { value: val.0 }
     }
 }
 
-__xml_test_suites!(Size, Size::from(42isize), r#"<w:sz w:val="42"/>"#,);
+macro_rules! __size_from {
+    ($($unit:ident),*) => {
+        $(
+            impl From<$unit> for Size {
+                fn from(val: $unit) -> Self {
+                    Size {
+                        value: HalfPoints::from(val).0,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+__size_from!(Pt, Cm, Inch, Twips);
+
+__xml_test_suites!(
+    Size,
+    Size::from(42isize),
+    r#"<w:sz w:val="42"/>"#,
+    Size::from(HalfPoints(21)),
+    r#"<w:sz w:val="21"/>"#,
+    Size::from(Pt(10.5)),
+    r#"<w:sz w:val="21"/>"#,
+    Size::from(Twips(240)),
+    r#"<w:sz w:val="24"/>"#,
+);
@@ -4,7 +4,7 @@ use hard_xml::{XmlRead, XmlWrite};
 
 use crate::{__setter, __xml_test_suites};
 
-use super::line_rule::LineRule;
+use super::{line_rule::LineRule, Twips};
 
 /// Spacing
 /// 100 percent.
@@ -13,7 +13,11 @@ use super::line_rule::LineRule;
 /// ```rust
 /// use docx_rust::formatting::*;
 ///
-/// let sz = Size::from(42isize);
+/// let spacing = Spacing::default().before(Pt(12.0)).after(Pt(6.0));
+///
+/// // the twip setters leave the line units alone
+/// let spacing = Spacing::default().before(Pt(12.0)).before_lines(50isize);
+/// assert_eq!((spacing.before, spacing.before_lines), (Some(240), Some(50)));
 /// ```
 #[derive(Debug, XmlRead, XmlWrite, Clone, Default)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -41,23 +45,10 @@ pub struct Spacing {
 }
 
 impl Spacing {
-    fn before<T>(mut self, val: T) -> Self
-    where
-        isize: From<T>,
-    {
-        self.before = Some(val.into());
-        self.before_lines = self.before;
-        self
-    }
-    fn after<T>(mut self, val: T) -> Self
-    where
-        isize: From<T>,
-    {
-        self.after = Some(val.into());
-        self.after_lines = self.before;
-        self
-    }
-
+    __setter!(before: Option<isize> as Twips);
+    __setter!(after: Option<isize> as Twips);
+    __setter!(before_lines: Option<isize>);
+    __setter!(after_lines: Option<isize>);
     __setter!(line: Option<isize>);
     __setter!(line_rule: Option<LineRule>);
 }
@@ -69,5 +60,7 @@ __xml_test_suites!(
         .after(50isize)
         .line(384isize)
         .line_rule(LineRule::Auto),
-    r#"<w:spacing w:beforeLines="50" w:before="50" w:afterLines="50" w:after="50" w:line="384" w:lineRule="auto"/>"#,
+    r#"<w:spacing w:before="50" w:after="50" w:line="384" w:lineRule="auto"/>"#,
+    Spacing::default().after(super::Pt(6.0)).after_lines(50isize),
+    r#"<w:spacing w:afterLines="50" w:after="120"/>"#,
 );
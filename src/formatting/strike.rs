@@ -10,8 +10,7 @@ use crate::__xml_test_suites;
 /// let strike = Strike::from(false);
 /// let strike = Strike::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:strike")]
 pub struct Strike {
     #[xml(attr = "w:val")]
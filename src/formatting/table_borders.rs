@@ -27,7 +27,36 @@ pub struct TableBorders<'a> {
 
 impl<'a> TableBorders<'a> {
     __setter!(top: Option<TopBorder<'a>>);
+    __setter!(left: Option<LeftBorder<'a>>);
     __setter!(bottom: Option<BottomBorder<'a>>);
+    __setter!(right: Option<RightBorder<'a>>);
+    __setter!(inside_horizon: Option<InsideHorizonBorder<'a>>);
+    __setter!(inside_vertical: Option<InsideVerticalBorder<'a>>);
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tcBorders")]
+pub struct TableCellBorders<'a> {
+    #[xml(child = "w:top")]
+    pub top: Option<TopBorder<'a>>,
+    #[xml(child = "w:left")]
+    pub left: Option<LeftBorder<'a>>,
+    #[xml(child = "w:bottom")]
+    pub bottom: Option<BottomBorder<'a>>,
+    #[xml(child = "w:right")]
+    pub right: Option<RightBorder<'a>>,
+    #[xml(child = "w:insideH")]
+    pub inside_horizon: Option<InsideHorizonBorder<'a>>,
+    #[xml(child = "w:insideV")]
+    pub inside_vertical: Option<InsideVerticalBorder<'a>>,
+}
+
+impl<'a> TableCellBorders<'a> {
+    __setter!(top: Option<TopBorder<'a>>);
+    __setter!(left: Option<LeftBorder<'a>>);
+    __setter!(bottom: Option<BottomBorder<'a>>);
+    __setter!(right: Option<RightBorder<'a>>);
 }
 
 __xml_test_suites!(
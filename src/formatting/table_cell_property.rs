@@ -1,20 +1,86 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites};
+use crate::{__setter, __string_enum, __xml_test_suites};
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tcPr")]
-pub struct TableCellProperty {
+pub struct TableCellProperty<'a> {
     #[xml(child = "w:tcW")]
     pub wide: Option<super::TableCellWidth>,
+    /// Number of grid columns spanned by the cell
+    #[xml(child = "w:gridSpan")]
+    pub grid_span: Option<GridSpan>,
+    /// Vertically merged cell
+    #[xml(child = "w:vMerge")]
+    pub v_merge: Option<VMerge>,
+    #[xml(child = "w:tcBorders")]
+    pub borders: Option<super::TableCellBorders<'a>>,
+    #[xml(child = "w:shd")]
+    pub shading: Option<super::Shading<'a>>,
     #[xml(default, child = "w:vAlign")]
     pub v_align: super::VAlign,
 }
 
-impl TableCellProperty {
+impl<'a> TableCellProperty<'a> {
     __setter!(v_align: super::VAlign);
     __setter!(wide: Option<super::TableCellWidth>);
+    __setter!(grid_span: Option<GridSpan>);
+    __setter!(v_merge: Option<VMerge>);
+    __setter!(borders: Option<super::TableCellBorders<'a>>);
+    __setter!(shading: Option<super::Shading<'a>>);
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:gridSpan")]
+pub struct GridSpan {
+    #[xml(attr = "w:val")]
+    pub value: isize,
+}
+
+impl From<isize> for GridSpan {
+    fn from(value: isize) -> Self {
+        GridSpan { value }
+    }
+}
+
+/// Vertically Merged Cell
+///
+/// A missing value means the cell continues the merge started above it.
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:vMerge")]
+pub struct VMerge {
+    #[xml(attr = "w:val")]
+    pub value: Option<VMergeType>,
+}
+
+impl VMerge {
+    /// Returns true if this cell starts a new merged region.
+    pub fn is_restart(&self) -> bool {
+        matches!(self.value, Some(VMergeType::Restart))
+    }
+}
+
+impl From<VMergeType> for VMerge {
+    fn from(value: VMergeType) -> Self {
+        VMerge { value: Some(value) }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum VMergeType {
+    Continue,
+    Restart,
+}
+
+__string_enum! {
+    VMergeType {
+        Continue = "continue",
+        Restart = "restart",
+    }
 }
 
 __xml_test_suites!(
@@ -23,4 +89,18 @@ __xml_test_suites!(
     r#"<w:tcPr><w:vAlign w:val="top"/></w:tcPr>"#,
     TableCellProperty::default().v_align(super::VAlignType::Bottom),
     r#"<w:tcPr><w:vAlign w:val="bottom"/></w:tcPr>"#,
+    TableCellProperty::default().grid_span(2),
+    r#"<w:tcPr><w:gridSpan w:val="2"/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default().v_merge(VMergeType::Restart),
+    r#"<w:tcPr><w:vMerge w:val="restart"/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default().v_merge(VMerge::default()),
+    r#"<w:tcPr><w:vMerge/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default().shading(super::Shading {
+        fill: Some("FF0000".into()),
+        ..Default::default()
+    }),
+    r#"<w:tcPr><w:shd w:fill="FF0000"/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default()
+        .borders(super::TableCellBorders::default().left(super::LeftBorder::default())),
+    r#"<w:tcPr><w:tcBorders><w:left w:val="none"/></w:tcBorders><w:vAlign w:val="top"/></w:tcPr>"#,
 );
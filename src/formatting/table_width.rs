@@ -2,6 +2,8 @@ use hard_xml::{XmlRead, XmlWrite};
 
 use crate::{__string_enum, __xml_test_suites};
 
+use super::{Cm, Inch, Pct, Pt, Twips};
+
 /// Table Width
 ///
 /// ```rust
@@ -58,6 +60,36 @@ impl From<(isize, TableWidthUnit)> for TableWidth {
     }
 }
 
+macro_rules! __width_from {
+    ($name:ident) => {
+        impl From<Pct> for $name {
+            fn from(val: Pct) -> Self {
+                $name {
+                    value: Some(val.fiftieths()),
+                    unit: Some(TableWidthUnit::Pct),
+                }
+            }
+        }
+
+        __width_from!($name: Twips, Pt, Cm, Inch);
+    };
+    ($name:ident: $($unit:ident),*) => {
+        $(
+            impl From<$unit> for $name {
+                fn from(val: $unit) -> Self {
+                    $name {
+                        value: Some(Twips::from(val).0),
+                        unit: Some(TableWidthUnit::Dxa),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+__width_from!(TableWidth);
+__width_from!(TableCellWidth);
+
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum TableWidthUnit {
@@ -86,4 +118,8 @@ __xml_test_suites!(
     r#"<w:tblW w:type="pct"/>"#,
     TableWidth::from((42, TableWidthUnit::Dxa)),
     r#"<w:tblW w:w="42" w:type="dxa"/>"#,
+    TableWidth::from(Pct(100.0)),
+    r#"<w:tblW w:w="5000" w:type="pct"/>"#,
+    TableWidth::from(Inch(1.0)),
+    r#"<w:tblW w:w="1440" w:type="dxa"/>"#,
 );
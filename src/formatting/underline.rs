@@ -13,8 +13,7 @@ use crate::{__string_enum, __xml_test_suites};
 /// let udl = Underline::from(("00ff00", UnderlineStyle::Dash));
 /// let udl = Underline::from((String::from("ff0000"), UnderlineStyle::DotDash));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone, PartialEq)]
 #[xml(tag = "w:u")]
 pub struct Underline<'a> {
     #[xml(attr = "w:color")]
@@ -68,8 +67,7 @@ impl<'a> From<(&'a str, UnderlineStyle)> for Underline<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, PartialEq)]
 pub enum UnderlineStyle {
     Dash,
     DashDotDotHeavy,
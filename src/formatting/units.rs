@@ -0,0 +1,198 @@
+//! Measurement units
+//!
+//! Each attribute of WordprocessingML counts its size in a unit of its own: font sizes
+//! are in half-points, indents, spacing and page setup in twentieths of a point
+//! (twips), drawings in English Metric Units. The builders of these attributes take
+//! the matching type, which any other length converts into.
+//!
+//! ```rust
+//! use docx_rust::formatting::*;
+//!
+//! assert_eq!(Twips::from(Cm(2.54)), Twips(1440));
+//! assert_eq!(HalfPoints::from(Pt(10.5)), HalfPoints(21));
+//! assert_eq!(Emu::from(Inch(1.0)), Emu(914400));
+//!
+//! let margin = PageMargin::default().top(Cm(2.0)).left(Inch(1.0));
+//! assert_eq!(margin.top, Some(1134));
+//! ```
+
+/// Twentieths of a point, also called dxa
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Twips(pub isize);
+
+/// Half-points, the unit of font sizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HalfPoints(pub isize);
+
+/// English Metric Units, the unit of drawings
+///
+/// There are 914400 of them in an inch, and 360000 in a centimeter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Emu(pub i64);
+
+/// Points
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Pt(pub f64);
+
+/// Centimeters
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Cm(pub f64);
+
+/// Inches
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Inch(pub f64);
+
+/// Percentage of the available width, from 0 to 100
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Pct(pub f64);
+
+impl Pct {
+    /// Returns the percentage in fiftieths of a percent, as stored in table widths.
+    pub fn fiftieths(self) -> isize {
+        (self.0 * 50.0).round() as isize
+    }
+}
+
+/// A length that converts to and from inches
+trait Length: Copy {
+    const PER_INCH: f64;
+
+    fn value(self) -> f64;
+
+    fn with_value(value: f64) -> Self;
+}
+
+macro_rules! __length {
+    ($name:ident, $per_inch:expr, integer) => {
+        impl Length for $name {
+            const PER_INCH: f64 = $per_inch;
+
+            fn value(self) -> f64 {
+                self.0 as f64
+            }
+
+            fn with_value(value: f64) -> Self {
+                $name(value.round() as _)
+            }
+        }
+    };
+    ($name:ident, $per_inch:expr, float) => {
+        impl Length for $name {
+            const PER_INCH: f64 = $per_inch;
+
+            fn value(self) -> f64 {
+                self.0
+            }
+
+            fn with_value(value: f64) -> Self {
+                $name(value)
+            }
+        }
+    };
+}
+
+__length!(Twips, 1440.0, integer);
+__length!(HalfPoints, 144.0, integer);
+__length!(Emu, 914400.0, integer);
+__length!(Pt, 72.0, float);
+__length!(Cm, 2.54, float);
+__length!(Inch, 1.0, float);
+
+macro_rules! __conversions {
+    ($from:ident => $($to:ident),*) => {
+        $(
+            impl From<$from> for $to {
+                fn from(length: $from) -> Self {
+                    $to::with_value(length.value() * $to::PER_INCH / $from::PER_INCH)
+                }
+            }
+        )*
+    };
+}
+
+__conversions!(Twips => HalfPoints, Emu, Pt, Cm, Inch);
+__conversions!(HalfPoints => Twips, Emu, Pt, Cm, Inch);
+__conversions!(Emu => Twips, HalfPoints, Pt, Cm, Inch);
+__conversions!(Pt => Twips, HalfPoints, Emu, Cm, Inch);
+__conversions!(Cm => Twips, HalfPoints, Emu, Pt, Inch);
+__conversions!(Inch => Twips, HalfPoints, Emu, Pt, Cm);
+
+// raw values, for the attributes stored as plain numbers
+
+impl From<isize> for Twips {
+    fn from(value: isize) -> Self {
+        Twips(value)
+    }
+}
+
+impl From<i32> for Twips {
+    fn from(value: i32) -> Self {
+        Twips(value as isize)
+    }
+}
+
+impl From<Twips> for isize {
+    fn from(value: Twips) -> Self {
+        value.0
+    }
+}
+
+impl From<isize> for HalfPoints {
+    fn from(value: isize) -> Self {
+        HalfPoints(value)
+    }
+}
+
+impl From<i32> for HalfPoints {
+    fn from(value: i32) -> Self {
+        HalfPoints(value as isize)
+    }
+}
+
+impl From<HalfPoints> for isize {
+    fn from(value: HalfPoints) -> Self {
+        value.0
+    }
+}
+
+impl From<i64> for Emu {
+    fn from(value: i64) -> Self {
+        Emu(value)
+    }
+}
+
+impl From<u64> for Emu {
+    fn from(value: u64) -> Self {
+        Emu(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<Emu> for i64 {
+    fn from(value: Emu) -> Self {
+        value.0
+    }
+}
+
+impl From<Emu> for u64 {
+    /// Negative lengths become zero.
+    fn from(value: Emu) -> Self {
+        u64::try_from(value.0).unwrap_or(0)
+    }
+}
+
+#[test]
+fn units_test() {
+    assert_eq!(Twips::from(Pt(12.0)), Twips(240));
+    assert_eq!(Twips::from(Inch(0.5)), Twips(720));
+    assert_eq!(Twips::from(Cm(1.0)), Twips(567));
+    assert_eq!(HalfPoints::from(Twips(240)), HalfPoints(24));
+    assert_eq!(Emu::from(Pt(1.0)), Emu(12700));
+    assert_eq!(Emu::from(Cm(1.0)), Emu(360000));
+    assert_eq!(Emu::from(Twips(1)), Emu(635));
+    assert_eq!(Pt::from(HalfPoints(21)), Pt(10.5));
+    assert_eq!(Inch::from(Emu(457200)), Inch(0.5));
+    assert_eq!(Pct(100.0).fiftieths(), 5000);
+    assert_eq!(u64::from(Emu(-1)), 0);
+    assert_eq!(Twips::from(1440), Twips(1440));
+    assert_eq!(HalfPoints::from(24i32), HalfPoints(24));
+}
@@ -0,0 +1,1278 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, Break, BreakType, Drawing, HeaderFooterReference, HeaderFooterReferenceType,
+        Hyperlink, Paragraph, ParagraphContent, Run, RunContent, Table, TableCell,
+        TableCellContent, TableRow, TableRowContent,
+    },
+    formatting::{
+        BorderStyle, CharacterProperty, HighlightType, JustificationVal, LineRule,
+        ParagraphProperty, Shading, TableBorders, TableWidthUnit, UnderlineStyle, VAlignType,
+        VertAlignType,
+    },
+    styles::Style,
+    Docx,
+};
+
+/// Builds a `name:value` declaration from one of the border elements.
+macro_rules! border_css {
+    ($name:expr, $border:expr) => {
+        $border.as_ref().and_then(|b| {
+            border_decl(&b.style, b.color.as_deref(), b.size).map(|d| format!("{}:{}", $name, d))
+        })
+    };
+}
+
+/// Options of the HTML export
+///
+/// ```rust
+/// use docx_rust::html::HtmlOptions;
+///
+/// let options = HtmlOptions::default()
+///     .full_document(false)
+///     .headers(true)
+///     .footers(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlOptions {
+    /// Wraps the output into `<html>`, `<head>` and `<body>`.
+    pub full_document: bool,
+    /// Embeds images as data URIs, otherwise images are left out.
+    pub images: bool,
+    /// Renders the default header of the last section before the body.
+    pub headers: bool,
+    /// Renders the default footer of the last section after the body.
+    pub footers: bool,
+    /// Renders the referenced footnotes and endnotes after the body.
+    pub footnotes: bool,
+    /// Renders comment markers and the comments after the body.
+    pub comments: bool,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            full_document: true,
+            images: true,
+            headers: false,
+            footers: false,
+            footnotes: false,
+            comments: false,
+        }
+    }
+}
+
+impl HtmlOptions {
+    __setter!(full_document: bool);
+    __setter!(images: bool);
+    __setter!(headers: bool);
+    __setter!(footers: bool);
+    __setter!(footnotes: bool);
+    __setter!(comments: bool);
+}
+
+impl<'a> Docx<'a> {
+    /// Renders the document as HTML.
+    pub fn to_html(&self, options: &HtmlOptions) -> String {
+        HtmlRenderer::new(self, options).render()
+    }
+}
+
+const STYLE_SHEET: &str = ".docx p,.docx h1,.docx h2,.docx h3,.docx h4,.docx h5,.docx h6{margin:0;font-size:inherit;font-weight:inherit}\
+.docx table{border-collapse:collapse}\
+.docx td,.docx th{vertical-align:top;padding:0 5.4pt}\
+.docx .notes,.docx footer{border-top:1px solid #999;margin-top:1em}";
+
+struct OpenList {
+    tag: &'static str,
+    li_open: bool,
+}
+
+struct HtmlRenderer<'d, 'a> {
+    docx: &'d Docx<'a>,
+    options: &'d HtmlOptions,
+    styles: HashMap<&'d str, &'d Style<'a>>,
+    /// Run formatting of the document defaults, set on the root element.
+    defaults: RunFormat,
+    out: String,
+    lists: Vec<OpenList>,
+    footnotes: Vec<String>,
+    endnotes: Vec<String>,
+    comments: Vec<String>,
+}
+
+impl<'d, 'a> HtmlRenderer<'d, 'a> {
+    fn new(docx: &'d Docx<'a>, options: &'d HtmlOptions) -> Self {
+        let styles = docx
+            .styles
+            .styles
+            .iter()
+            .map(|s| (s.style_id.as_ref(), s))
+            .collect();
+        let mut defaults = RunFormat::default();
+        if let Some(p) = docx
+            .styles
+            .default
+            .as_ref()
+            .and_then(|d| d.character.inner.as_ref())
+        {
+            defaults.apply(p);
+        }
+        HtmlRenderer {
+            docx,
+            options,
+            styles,
+            defaults,
+            out: String::new(),
+            lists: Vec::new(),
+            footnotes: Vec::new(),
+            endnotes: Vec::new(),
+            comments: Vec::new(),
+        }
+    }
+
+    fn render(mut self) -> String {
+        if self.options.full_document {
+            self.out
+                .push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+            let title = self.docx.core.as_ref().and_then(|c| c.title());
+            if let Some(title) = title {
+                let _ = write!(self.out, "<title>{}</title>", escape(title));
+            }
+            let _ = write!(self.out, "<style>{}</style></head><body>", STYLE_SHEET);
+        } else {
+            let _ = write!(self.out, "<style>{}</style>", STYLE_SHEET);
+        }
+        self.out.push_str("<div class=\"docx\"");
+        let css = self.defaults.css();
+        push_style(&mut self.out, &css);
+        self.out.push('>');
+
+        let sect = self
+            .docx
+            .document
+            .body
+            .content
+            .iter()
+            .rev()
+            .find_map(|c| match c {
+                BodyContent::SectionProperty(s) => Some(s),
+                _ => None,
+            });
+        let references: &[HeaderFooterReference] = sect
+            .map(|s| s.header_footer_references.as_slice())
+            .unwrap_or_default();
+
+        if self.options.headers {
+            let header = references.iter().find_map(|r| match r {
+                HeaderFooterReference::Header(h) if is_default(&h.ty) => {
+                    self.target(h.id.as_deref()?)
+                }
+                _ => None,
+            });
+            if let Some(header) = header.and_then(|t| self.docx.headers.get(t)) {
+                self.out.push_str("<header>");
+                self.render_blocks(&header.content);
+                self.out.push_str("</header>");
+            }
+        }
+
+        self.render_blocks(&self.docx.document.body.content);
+
+        if self.options.footers {
+            let footer = references.iter().find_map(|r| match r {
+                HeaderFooterReference::Footer(f) if is_default(&f.ty) => {
+                    self.target(f.id.as_deref()?)
+                }
+                _ => None,
+            });
+            if let Some(footer) = footer.and_then(|t| self.docx.footers.get(t)) {
+                self.out.push_str("<footer>");
+                self.render_blocks(&footer.content);
+                self.out.push_str("</footer>");
+            }
+        }
+
+        if self.options.footnotes {
+            self.render_notes();
+        }
+
+        if self.options.comments {
+            self.render_comments();
+        }
+
+        self.out.push_str("</div>");
+        if self.options.full_document {
+            self.out.push_str("</body></html>\n");
+        }
+        self.out
+    }
+
+    /// Resolves a relationship id of the main document part.
+    fn target(&self, id: &str) -> Option<&'d str> {
+        let rels = self.docx.document_rels.as_ref()?;
+        rels.relationships
+            .iter()
+            .find(|r| r.id == id)
+            .map(|r| r.target.as_ref())
+    }
+
+    /// Returns the style and its ancestors, the root style first.
+    fn style_chain(&self, id: &str) -> Vec<&'d Style<'a>> {
+        let mut chain = Vec::new();
+        let mut next = self.styles.get(id).copied();
+        while let Some(style) = next {
+            if chain.len() > 16 || chain.iter().any(|s: &&Style| s.style_id == style.style_id) {
+                break;
+            }
+            chain.push(style);
+            next = style
+                .base
+                .as_ref()
+                .and_then(|b| self.styles.get(b.value.as_ref()).copied());
+        }
+        chain.reverse();
+        chain
+    }
+
+    fn paragraph_format(&self, prop: Option<&ParagraphProperty>) -> (ParagraphFormat, RunFormat) {
+        let mut para = ParagraphFormat::default();
+        let mut run = self.defaults.clone();
+        if let Some(p) = self
+            .docx
+            .styles
+            .default
+            .as_ref()
+            .and_then(|d| d.paragraph.inner.as_ref())
+        {
+            para.apply(p);
+        }
+        let style_id = prop
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref())
+            .or_else(|| self.default_style_id());
+        if let Some(id) = style_id {
+            for style in self.style_chain(id) {
+                if let Some(p) = &style.paragraph {
+                    para.apply(p);
+                }
+                if let Some(c) = &style.character {
+                    run.apply(c);
+                }
+                if para.heading.is_none() {
+                    para.heading = style.name.as_ref().and_then(|n| heading_level(&n.value));
+                }
+            }
+        }
+        if let Some(p) = prop {
+            para.apply(p);
+        }
+        (para, run)
+    }
+
+    fn default_style_id(&self) -> Option<&'d str> {
+        self.docx
+            .styles
+            .styles
+            .iter()
+            .find(|s| {
+                s.default == Some(true) && matches!(s.ty, Some(crate::styles::StyleType::Paragraph))
+            })
+            .map(|s| s.style_id.as_ref())
+    }
+
+    fn render_blocks(&mut self, content: &[BodyContent]) {
+        for c in content {
+            match c {
+                BodyContent::Paragraph(p) => self.render_paragraph(p),
+                BodyContent::Table(t) => {
+                    self.close_lists();
+                    self.render_table(t);
+                }
+                BodyContent::Sdt(sdt) => {
+                    if let Some(content) = &sdt.content {
+                        self.render_blocks(&content.content);
+                    }
+                }
+                BodyContent::Run(r) => {
+                    self.close_lists();
+                    self.out.push_str("<p>");
+                    let base = self.defaults.clone();
+                    self.render_run(r, &base);
+                    self.out.push_str("</p>");
+                }
+                BodyContent::TableCell(_)
+                | BodyContent::SectionProperty(_)
+                | BodyContent::BookmarkStart(_)
+                | BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => {}
+            }
+        }
+        self.close_lists();
+    }
+
+    fn render_paragraph(&mut self, p: &Paragraph) {
+        let (format, run) = self.paragraph_format(p.property.as_ref());
+
+        if let Some((num_id, level)) = format.list() {
+            self.open_list_item(num_id, level);
+            self.out.push_str("<li");
+            push_style(
+                &mut self.out,
+                &join(format.css(false), run.css_over(&self.defaults)),
+            );
+            self.out.push('>');
+            self.render_inlines(&p.content, &run);
+            return;
+        }
+
+        self.close_lists();
+        let tag = match format.heading {
+            Some(level) => HEADINGS[level],
+            None => "p",
+        };
+        let _ = write!(self.out, "<{}", tag);
+        push_style(
+            &mut self.out,
+            &join(format.css(true), run.css_over(&self.defaults)),
+        );
+        self.out.push('>');
+        let start = self.out.len();
+        self.render_inlines(&p.content, &run);
+        if self.out.len() == start {
+            self.out.push_str("<br>");
+        }
+        let _ = write!(self.out, "</{}>", tag);
+    }
+
+    fn open_list_item(&mut self, num_id: isize, level: isize) {
+        let level = level.clamp(0, 8) as usize;
+        let details = self
+            .docx
+            .numbering
+            .as_ref()
+            .and_then(|n| n.numbering_details(num_id));
+        let lvl = details
+            .as_ref()
+            .and_then(|d| d.levels.iter().find(|l| l.i_level == Some(level as isize)));
+        let ordered = lvl
+            .and_then(|l| l.number_format.as_ref())
+            .map(|f| f.value != "bullet" && f.value != "none")
+            .unwrap_or(false);
+        let tag = if ordered { "ol" } else { "ul" };
+
+        while self.lists.len() > level + 1 {
+            self.close_list();
+        }
+        if self.lists.len() == level + 1 {
+            if self.lists[level].tag != tag {
+                self.close_list();
+            } else if self.lists[level].li_open {
+                self.out.push_str("</li>");
+                self.lists[level].li_open = false;
+            }
+        }
+        while self.lists.len() < level + 1 {
+            let _ = write!(self.out, "<{}", tag);
+            if self.lists.len() == level && ordered {
+                if let Some(start) = lvl.and_then(|l| l.start.as_ref()).and_then(|s| s.value) {
+                    if start != 1 {
+                        let _ = write!(self.out, " start=\"{}\"", start);
+                    }
+                }
+            }
+            self.out.push('>');
+            self.lists.push(OpenList {
+                tag,
+                li_open: false,
+            });
+        }
+        self.lists[level].li_open = true;
+    }
+
+    fn close_list(&mut self) {
+        if let Some(list) = self.lists.pop() {
+            if list.li_open {
+                self.out.push_str("</li>");
+            }
+            let _ = write!(self.out, "</{}>", list.tag);
+        }
+    }
+
+    fn close_lists(&mut self) {
+        while !self.lists.is_empty() {
+            self.close_list();
+        }
+    }
+
+    fn render_inlines(&mut self, content: &[ParagraphContent], base: &RunFormat) {
+        for c in content {
+            match c {
+                ParagraphContent::Run(r) => self.render_run(r, base),
+                ParagraphContent::Link(link) => self.render_link(link, base),
+                ParagraphContent::SimpleField(field) => {
+                    for r in &field.runs {
+                        self.render_run(r, base);
+                    }
+                }
+                ParagraphContent::BookmarkStart(b) => {
+                    if let Some(name) = b.name.as_deref().filter(|n| *n != "_GoBack") {
+                        let _ = write!(self.out, "<a id=\"{}\"></a>", escape(name));
+                    }
+                }
+                ParagraphContent::SDT(sdt) => {
+                    for c in sdt.content.iter().flat_map(|c| c.content.iter()) {
+                        match c {
+                            BodyContent::Run(r) => self.render_run(r, base),
+                            BodyContent::Paragraph(p) => self.render_inlines(&p.content, base),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn render_link(&mut self, link: &Hyperlink, base: &RunFormat) {
+        let href = match (&link.id, &link.anchor) {
+            (Some(id), _) => self.target(id).map(|t| t.to_string()),
+            (None, Some(anchor)) => Some(format!("#{}", anchor)),
+            _ => None,
+        };
+        match href.filter(|href| is_safe_href(href)) {
+            Some(href) => {
+                let _ = write!(self.out, "<a href=\"{}\">", escape(&href));
+            }
+            None => self.out.push_str("<a>"),
+        }
+        if let Some(r) = &link.content {
+            self.render_run(r, base);
+        }
+        let mut levels: Vec<_> = link.bidirectional_embedding.iter().collect();
+        while let Some(level) = levels.pop() {
+            for r in &level.runs {
+                self.render_run(r, base);
+            }
+            levels.extend(level.nested_levels.iter().rev());
+        }
+        self.out.push_str("</a>");
+    }
+
+    fn render_run(&mut self, run: &Run, base: &RunFormat) {
+        let mut format = base.clone();
+        if let Some(prop) = &run.property {
+            if let Some(id) = &prop.style_id {
+                for style in self.style_chain(&id.value) {
+                    if let Some(c) = &style.character {
+                        format.apply(c);
+                    }
+                }
+            }
+            format.apply(prop);
+        }
+        if format.hidden {
+            return;
+        }
+
+        let mut inner = String::new();
+        for c in &run.content {
+            match c {
+                RunContent::Text(t) => inner.push_str(&escape(&t.text)),
+                RunContent::Tab(_) | RunContent::PTab(_) => inner.push_str("&emsp;"),
+                RunContent::Break(Break {
+                    ty: Some(BreakType::Page),
+                }) => inner.push_str("<br class=\"page-break\">"),
+                RunContent::Break(_) | RunContent::CarriageReturn(_) => inner.push_str("<br>"),
+                RunContent::NoBreakHyphen(_) => inner.push_str("&#8209;"),
+                RunContent::SoftHyphen(_) => inner.push_str("&shy;"),
+                RunContent::Sym(sym) => {
+                    if let Some(ch) = sym
+                        .char
+                        .as_deref()
+                        .and_then(|c| u32::from_str_radix(c, 16).ok())
+                        .and_then(char::from_u32)
+                    {
+                        inner.push_str(&escape(&ch.to_string()));
+                    }
+                }
+                RunContent::Drawing(d) if self.options.images => {
+                    self.render_drawing(d, &mut inner);
+                }
+                RunContent::FootnoteReference(r) if self.options.footnotes => {
+                    if let Some(id) = &r.id {
+                        self.footnotes.push(id.to_string());
+                        format.vert_align = Some(VertAlignType::Superscript);
+                        let _ = write!(
+                            inner,
+                            "<a class=\"footnote-ref\" href=\"#fn-{0}\" id=\"fnref-{0}\">{1}</a>",
+                            escape(id),
+                            self.footnotes.len()
+                        );
+                    }
+                }
+                RunContent::EndnoteReference(r) if self.options.footnotes => {
+                    if let Some(id) = &r.id {
+                        self.endnotes.push(id.to_string());
+                        format.vert_align = Some(VertAlignType::Superscript);
+                        let _ = write!(
+                            inner,
+                            "<a class=\"endnote-ref\" href=\"#en-{0}\" id=\"enref-{0}\">{1}</a>",
+                            escape(id),
+                            to_roman(self.endnotes.len())
+                        );
+                    }
+                }
+                RunContent::CommentReference(r) if self.options.comments => {
+                    if let Some(id) = &r.id {
+                        self.comments.push(id.to_string());
+                        let _ = write!(
+                            inner,
+                            "<sup class=\"comment-ref\"><a href=\"#comment-{0}\">[{1}]</a></sup>",
+                            escape(id),
+                            self.comments.len()
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        if inner.is_empty() {
+            return;
+        }
+
+        let wrapper = match format.vert_align {
+            Some(VertAlignType::Superscript) => Some("sup"),
+            Some(VertAlignType::Subscript) => Some("sub"),
+            _ => None,
+        };
+        if let Some(tag) = wrapper {
+            let _ = write!(self.out, "<{}>", tag);
+        }
+        let css = format.css_over(base);
+        if css.is_empty() {
+            self.out.push_str(&inner);
+        } else {
+            self.out.push_str("<span");
+            push_style(&mut self.out, &css);
+            self.out.push('>');
+            self.out.push_str(&inner);
+            self.out.push_str("</span>");
+        }
+        if let Some(tag) = wrapper {
+            let _ = write!(self.out, "</{}>", tag);
+        }
+    }
+
+    fn render_drawing(&self, drawing: &Drawing, out: &mut String) {
+        let (extent, doc_property, graphic) = match (&drawing.inline, &drawing.anchor) {
+            (Some(i), _) => (&i.extent, &i.doc_property, &i.graphic),
+            (None, Some(a)) => (&a.extent, &a.doc_property, &a.graphic),
+            _ => return,
+        };
+        let Some(picture) = graphic.as_ref().and_then(|g| g.data.children.first()) else {
+            return;
+        };
+        let Some(target) = self.target(&picture.fill.blip.embed) else {
+            return;
+        };
+        let target = target.trim_start_matches('/').trim_start_matches("word/");
+        let Some((_, bytes)) = self.docx.media.get(target) else {
+            return;
+        };
+        let _ = write!(
+            out,
+            "<img src=\"data:{};base64,{}\"",
+            mime_type(target),
+            crate::base64::encode(bytes)
+        );
+        let alt = doc_property
+            .descr
+            .as_deref()
+            .or(doc_property.name.as_deref())
+            .unwrap_or_default();
+        let _ = write!(out, " alt=\"{}\"", escape(alt));
+        if let Some(extent) = extent {
+            // 9525 EMUs per CSS pixel
+            let _ = write!(
+                out,
+                " width=\"{}\" height=\"{}\"",
+                extent.cx / 9525,
+                extent.cy / 9525
+            );
+        }
+        out.push('>');
+    }
+
+    fn render_table(&mut self, table: &Table) {
+        let mut borders: Option<&TableBorders> = None;
+        if let Some(id) = &table.property.style_id {
+            for style in self.style_chain(&id.value) {
+                if let Some(b) = style.table.as_ref().and_then(|t| t.borders.as_ref()) {
+                    borders = Some(b);
+                }
+            }
+        }
+        if let Some(b) = &table.property.borders {
+            borders = Some(b);
+        }
+
+        let mut css = Vec::new();
+        if let Some(width) = &table.property.width {
+            match (&width.unit, width.value) {
+                (Some(TableWidthUnit::Pct), Some(v)) => {
+                    css.push(format!("width:{}%", num(v as f64 / 50.0)))
+                }
+                (Some(TableWidthUnit::Dxa), Some(v)) | (None, Some(v)) if v > 0 => {
+                    css.push(format!("width:{}pt", num(v as f64 / 20.0)))
+                }
+                _ => {}
+            }
+        }
+        let mut cell_css = Vec::new();
+        if let Some(b) = borders {
+            css.extend(border_css!("border-top", b.top));
+            css.extend(border_css!("border-left", b.left));
+            css.extend(border_css!("border-bottom", b.bottom));
+            css.extend(border_css!("border-right", b.right));
+            cell_css.extend(border_css!("border-top", b.inside_horizon));
+            cell_css.extend(border_css!("border-bottom", b.inside_horizon));
+            cell_css.extend(border_css!("border-left", b.inside_vertical));
+            cell_css.extend(border_css!("border-right", b.inside_vertical));
+        }
+
+        self.out.push_str("<table");
+        push_style(&mut self.out, &css.join(";"));
+        self.out.push('>');
+
+        let rows: Vec<Vec<(usize, usize, &TableCell)>> =
+            table.rows.iter().map(grid_cells).collect();
+        let header_rows = table
+            .rows
+            .iter()
+            .take_while(|r| r.property.table_header.is_some())
+            .count();
+
+        for (index, row) in rows.iter().enumerate() {
+            if index == 0 && header_rows > 0 {
+                self.out.push_str("<thead>");
+            } else if index == header_rows {
+                self.out.push_str("<tbody>");
+            }
+            self.out.push_str("<tr>");
+            for &(col, span, cell) in row {
+                let rowspan = match &cell.property.v_merge {
+                    Some(m) if !m.is_restart() => continue,
+                    Some(_) => {
+                        1 + rows[index + 1..]
+                            .iter()
+                            .take_while(|r| {
+                                r.iter().any(|&(c, _, cell)| {
+                                c == col
+                                    && matches!(&cell.property.v_merge, Some(m) if !m.is_restart())
+                            })
+                            })
+                            .count()
+                    }
+                    None => 1,
+                };
+                let tag = if index < header_rows { "th" } else { "td" };
+                let _ = write!(self.out, "<{}", tag);
+                if span > 1 {
+                    let _ = write!(self.out, " colspan=\"{}\"", span);
+                }
+                if rowspan > 1 {
+                    let _ = write!(self.out, " rowspan=\"{}\"", rowspan);
+                }
+                let mut css = cell_css.clone();
+                css.extend(cell_css_of(cell));
+                push_style(&mut self.out, &css.join(";"));
+                self.out.push('>');
+
+                // lists must not leak out of the cell
+                let lists = std::mem::take(&mut self.lists);
+                for content in &cell.content {
+                    match content {
+                        TableCellContent::Paragraph(p) => self.render_paragraph(p),
+                    }
+                }
+                self.close_lists();
+                self.lists = lists;
+
+                let _ = write!(self.out, "</{}>", tag);
+            }
+            self.out.push_str("</tr>");
+            if index + 1 == header_rows {
+                self.out.push_str("</thead>");
+            }
+        }
+        if rows.len() > header_rows {
+            self.out.push_str("</tbody>");
+        }
+        self.out.push_str("</table>");
+    }
+
+    fn render_notes(&mut self) {
+        let footnotes = std::mem::take(&mut self.footnotes);
+        let endnotes = std::mem::take(&mut self.endnotes);
+        if footnotes.is_empty() && endnotes.is_empty() {
+            return;
+        }
+        self.out.push_str("<section class=\"notes\">");
+        if let Some(notes) = &self.docx.footnotes {
+            if !footnotes.is_empty() {
+                self.out.push_str("<ol class=\"footnotes\">");
+                for id in &footnotes {
+                    let Some(note) = notes
+                        .content
+                        .iter()
+                        .find(|n| n.id.map(|i| i.to_string()).as_deref() == Some(id))
+                    else {
+                        continue;
+                    };
+                    let _ = write!(self.out, "<li id=\"fn-{}\">", escape(id));
+                    self.render_blocks(&note.content);
+                    self.out.push_str("</li>");
+                }
+                self.out.push_str("</ol>");
+            }
+        }
+        if let Some(notes) = &self.docx.endnotes {
+            if !endnotes.is_empty() {
+                self.out.push_str("<ol class=\"endnotes\" type=\"i\">");
+                for id in &endnotes {
+                    let Some(note) = notes
+                        .content
+                        .iter()
+                        .find(|n| n.id.map(|i| i.to_string()).as_deref() == Some(id))
+                    else {
+                        continue;
+                    };
+                    let _ = write!(self.out, "<li id=\"en-{}\">", escape(id));
+                    self.render_blocks(&note.content);
+                    self.out.push_str("</li>");
+                }
+                self.out.push_str("</ol>");
+            }
+        }
+        self.out.push_str("</section>");
+    }
+
+    fn render_comments(&mut self) {
+        let Some(comments) = &self.docx.comments else {
+            return;
+        };
+        if comments.comments.is_empty() {
+            return;
+        }
+        self.out.push_str("<section class=\"comments\">");
+        for comment in &comments.comments {
+            let id = comment.id.map(|i| i.to_string()).unwrap_or_default();
+            let _ = write!(
+                self.out,
+                "<aside class=\"comment\" id=\"comment-{}\"><p class=\"comment-author\"><b>{}</b></p>",
+                escape(&id),
+                escape(&comment.author)
+            );
+            self.render_paragraph(&comment.content);
+            self.close_lists();
+            self.out.push_str("</aside>");
+        }
+        self.out.push_str("</section>");
+    }
+}
+
+const HEADINGS: [&str; 6] = ["h1", "h2", "h3", "h4", "h5", "h6"];
+
+fn is_default(ty: &Option<HeaderFooterReferenceType>) -> bool {
+    matches!(ty, None | Some(HeaderFooterReferenceType::Default))
+}
+
+/// Places the cells of a row on the table grid, returning
+/// `(first grid column, spanned columns, cell)` for each cell.
+fn grid_cells<'r, 'a>(row: &'r TableRow<'a>) -> Vec<(usize, usize, &'r TableCell<'a>)> {
+    let mut cells = Vec::new();
+    for content in &row.cells {
+        match content {
+            TableRowContent::TableCell(c) => cells.push(c),
+            TableRowContent::SDT(sdt) => {
+                for c in sdt.content.iter().flat_map(|c| c.content.iter()) {
+                    if let BodyContent::TableCell(c) = c {
+                        cells.push(c);
+                    }
+                }
+            }
+        }
+    }
+    let mut col = 0;
+    cells
+        .into_iter()
+        .map(|c| {
+            let span = c
+                .property
+                .grid_span
+                .as_ref()
+                .map(|s| s.value.max(1) as usize)
+                .unwrap_or(1);
+            col += span;
+            (col - span, span, c)
+        })
+        .collect()
+}
+
+fn cell_css_of(cell: &TableCell) -> Vec<String> {
+    let mut css = Vec::new();
+    let prop = &cell.property;
+    if let Some(b) = &prop.borders {
+        css.extend(border_css!("border-top", b.top));
+        css.extend(border_css!("border-left", b.left));
+        css.extend(border_css!("border-bottom", b.bottom));
+        css.extend(border_css!("border-right", b.right));
+    }
+    if let Some(fill) = prop.shading.as_ref().and_then(shading_fill) {
+        css.push(format!("background-color:{}", fill));
+    }
+    match prop.v_align.val {
+        VAlignType::Center => css.push("vertical-align:middle".into()),
+        VAlignType::Bottom => css.push("vertical-align:bottom".into()),
+        _ => {}
+    }
+    if let Some(w) = &prop.wide {
+        if let (Some(TableWidthUnit::Dxa), Some(v)) = (&w.unit, w.value) {
+            css.push(format!("width:{}pt", num(v as f64 / 20.0)));
+        }
+    }
+    css
+}
+
+/// Formatting of a run after applying styles and direct formatting.
+#[derive(Debug, Default, Clone)]
+struct RunFormat {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    caps: bool,
+    small_caps: bool,
+    hidden: bool,
+    font: Option<String>,
+    size: Option<isize>,
+    color: Option<String>,
+    highlight: Option<&'static str>,
+    shading: Option<String>,
+    vert_align: Option<VertAlignType>,
+    border: Option<String>,
+}
+
+impl RunFormat {
+    fn apply(&mut self, p: &CharacterProperty) {
+        if let Some(v) = &p.bold {
+            self.bold = v.value.unwrap_or(true);
+        }
+        if let Some(v) = &p.italics {
+            self.italic = v.value.unwrap_or(true);
+        }
+        if let Some(v) = &p.caps {
+            self.caps = v.value.unwrap_or(true);
+        }
+        if let Some(v) = &p.small_caps {
+            self.small_caps = v.value.unwrap_or(true);
+        }
+        if let Some(v) = &p.strike {
+            self.strike = v.value.unwrap_or(true);
+        }
+        if let Some(v) = &p.dstrike {
+            self.strike = v.value.unwrap_or(true);
+        }
+        if let Some(v) = &p.vanish {
+            self.hidden = v.value.unwrap_or(true);
+        }
+        if let Some(fonts) = &p.fonts {
+            if let Some(font) = fonts
+                .ascii
+                .as_ref()
+                .or(fonts.h_ansi.as_ref())
+                .or(fonts.east_asia.as_ref())
+            {
+                self.font = Some(font.clone());
+            }
+        }
+        if let Some(size) = &p.size {
+            self.size = Some(size.value);
+        }
+        if let Some(color) = &p.color {
+            self.color = hex_color(&color.value);
+        }
+        if let Some(highlight) = &p.highlight {
+            self.highlight = highlight.value.as_ref().and_then(highlight_color);
+        }
+        if let Some(shading) = &p.shading {
+            self.shading = shading_fill(shading);
+        }
+        if let Some(underline) = &p.underline {
+            self.underline = !matches!(underline.val, Some(UnderlineStyle::None));
+        }
+        if let Some(v) = &p.vertical_align {
+            self.vert_align = v.value.clone();
+        }
+        if let Some(b) = &p.border {
+            self.border = border_decl(&b.style, b.color.as_deref(), b.size);
+        }
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        let mut css = Vec::new();
+        if let Some(font) = &self.font {
+            css.push(("font-family", format!("'{}'", font.replace('\'', ""))));
+        }
+        if let Some(size) = self.size {
+            css.push(("font-size", format!("{}pt", num(size as f64 / 2.0))));
+        }
+        css.push((
+            "font-weight",
+            if self.bold { "bold" } else { "normal" }.into(),
+        ));
+        css.push((
+            "font-style",
+            if self.italic { "italic" } else { "normal" }.into(),
+        ));
+        let decoration = match (self.underline, self.strike) {
+            (true, true) => "underline line-through",
+            (true, false) => "underline",
+            (false, true) => "line-through",
+            (false, false) => "none",
+        };
+        css.push(("text-decoration", decoration.into()));
+        if self.caps {
+            css.push(("text-transform", "uppercase".into()));
+        }
+        if self.small_caps {
+            css.push(("font-variant", "small-caps".into()));
+        }
+        if let Some(color) = &self.color {
+            css.push(("color", color.clone()));
+        }
+        if let Some(color) = self
+            .highlight
+            .map(str::to_string)
+            .or_else(|| self.shading.clone())
+        {
+            css.push(("background-color", color));
+        }
+        if let Some(border) = &self.border {
+            css.push(("border", border.clone()));
+        }
+        css
+    }
+
+    /// CSS of all the properties.
+    fn css(&self) -> String {
+        self.css_over(&RunFormat::default())
+    }
+
+    /// CSS of the properties which differ from the inherited ones.
+    fn css_over(&self, base: &RunFormat) -> String {
+        let base = base.declarations();
+        self.declarations()
+            .into_iter()
+            .filter(|d| !base.contains(d))
+            .map(|(name, value)| format!("{}:{}", name, value))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+}
+
+/// Formatting of a paragraph after applying styles and direct formatting.
+#[derive(Debug, Default, Clone)]
+struct ParagraphFormat {
+    align: Option<&'static str>,
+    left: Option<isize>,
+    right: Option<isize>,
+    first_line: Option<isize>,
+    hanging: Option<isize>,
+    before: Option<isize>,
+    after: Option<isize>,
+    line: Option<isize>,
+    line_rule: Option<LineRule>,
+    borders: [Option<String>; 4],
+    shading: Option<String>,
+    heading: Option<usize>,
+    num_id: Option<isize>,
+    num_level: Option<isize>,
+}
+
+impl ParagraphFormat {
+    fn apply(&mut self, p: &ParagraphProperty) {
+        if let Some(jc) = &p.justification {
+            self.align = Some(match jc.value {
+                JustificationVal::Start | JustificationVal::Left => "left",
+                JustificationVal::End | JustificationVal::Right => "right",
+                JustificationVal::Center => "center",
+                JustificationVal::Both | JustificationVal::Distribute => "justify",
+            });
+        }
+        if let Some(ind) = &p.indent {
+            if ind.left.is_some() {
+                self.left = ind.left;
+            }
+            if ind.right.is_some() {
+                self.right = ind.right;
+            }
+            if ind.first_line.is_some() {
+                self.first_line = ind.first_line;
+                self.hanging = None;
+            }
+            if ind.hanging.is_some() {
+                self.hanging = ind.hanging;
+                self.first_line = None;
+            }
+        }
+        if let Some(sp) = &p.spacing {
+            if sp.before.is_some() {
+                self.before = sp.before;
+            }
+            if sp.after.is_some() {
+                self.after = sp.after;
+            }
+            if sp.line.is_some() {
+                self.line = sp.line;
+                self.line_rule = sp.line_rule.clone();
+            }
+        }
+        if let Some(b) = &p.border {
+            let sides = [
+                border_css!("border-top", b.top),
+                border_css!("border-right", b.right),
+                border_css!("border-bottom", b.bottom),
+                border_css!("border-left", b.left),
+            ];
+            for (i, side) in sides.into_iter().enumerate() {
+                if side.is_some() {
+                    self.borders[i] = side;
+                }
+            }
+        }
+        if let Some(shading) = &p.shading {
+            self.shading = shading_fill(shading);
+        }
+        if let Some(level) = &p.outline_lvl {
+            self.heading = usize::try_from(level.value).ok().filter(|l| *l < 6);
+        }
+        if let Some(numbering) = &p.numbering {
+            if let Some(id) = &numbering.id {
+                self.num_id = Some(id.value);
+            }
+            if let Some(level) = &numbering.level {
+                self.num_level = Some(level.value);
+            }
+        }
+    }
+
+    fn list(&self) -> Option<(isize, isize)> {
+        match self.num_id {
+            Some(id) if id > 0 => Some((id, self.num_level.unwrap_or(0))),
+            _ => None,
+        }
+    }
+
+    fn css(&self, indent: bool) -> String {
+        let mut css = Vec::new();
+        if let Some(align) = self.align {
+            css.push(format!("text-align:{}", align));
+        }
+        if indent {
+            if let Some(v) = self.left.filter(|v| *v != 0) {
+                css.push(format!("margin-left:{}pt", twips(v)));
+            }
+            if let Some(v) = self.right.filter(|v| *v != 0) {
+                css.push(format!("margin-right:{}pt", twips(v)));
+            }
+            if let Some(v) = self.first_line.filter(|v| *v != 0) {
+                css.push(format!("text-indent:{}pt", twips(v)));
+            }
+            if let Some(v) = self.hanging.filter(|v| *v != 0) {
+                css.push(format!("text-indent:-{}pt", twips(v)));
+            }
+        }
+        if let Some(v) = self.before {
+            css.push(format!("margin-top:{}pt", twips(v)));
+        }
+        if let Some(v) = self.after {
+            css.push(format!("margin-bottom:{}pt", twips(v)));
+        }
+        if let Some(line) = self.line {
+            match self.line_rule {
+                Some(LineRule::Exact) | Some(LineRule::AtLeast) => {
+                    css.push(format!("line-height:{}pt", twips(line)))
+                }
+                _ => css.push(format!("line-height:{}", num(line as f64 / 240.0))),
+            }
+        }
+        css.extend(self.borders.iter().flatten().cloned());
+        if let Some(fill) = &self.shading {
+            css.push(format!("background-color:{}", fill));
+        }
+        css.join(";")
+    }
+}
+
+fn border_decl(style: &BorderStyle, color: Option<&str>, size: Option<isize>) -> Option<String> {
+    let style = match style {
+        BorderStyle::Nil | BorderStyle::None => return None,
+        BorderStyle::Double => "double",
+        BorderStyle::Dotted => "dotted",
+        BorderStyle::Dashed | BorderStyle::DotDash | BorderStyle::DotDotDash => "dashed",
+        BorderStyle::Outset => "outset",
+        BorderStyle::Inset => "inset",
+        _ => "solid",
+    };
+    // eighths of a point
+    let width = size
+        .map(|s| num((s as f64 / 8.0).max(0.25)))
+        .unwrap_or_else(|| "0.5".into());
+    let color = color
+        .and_then(hex_color)
+        .unwrap_or_else(|| "#000000".into());
+    Some(format!("{}pt {} {}", width, style, color))
+}
+
+fn shading_fill(shading: &Shading) -> Option<String> {
+    shading.fill.as_deref().and_then(hex_color)
+}
+
+fn hex_color(value: &str) -> Option<String> {
+    if value.len() == 6 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("#{}", value.to_ascii_lowercase()))
+    } else {
+        None
+    }
+}
+
+fn highlight_color(ty: &HighlightType) -> Option<&'static str> {
+    Some(match ty {
+        HighlightType::Black => "black",
+        HighlightType::Blue => "blue",
+        HighlightType::Cyan => "cyan",
+        HighlightType::Green => "lime",
+        HighlightType::Magenta => "magenta",
+        HighlightType::Red => "red",
+        HighlightType::Yellow => "yellow",
+        HighlightType::White => "white",
+        HighlightType::DarkBlue => "navy",
+        HighlightType::DarkCyan => "teal",
+        HighlightType::DarkGreen => "green",
+        HighlightType::DarkMagenta => "purple",
+        HighlightType::DarkRed => "maroon",
+        HighlightType::DarkYellow => "olive",
+        HighlightType::DarkGray => "gray",
+        HighlightType::LightGray => "silver",
+        HighlightType::None => return None,
+    })
+}
+
+/// Matches built-in heading style names like `heading 1`.
+fn heading_level(name: &str) -> Option<usize> {
+    let name = name.to_ascii_lowercase();
+    let level = name.strip_prefix("heading")?.trim().parse::<usize>().ok()?;
+    (1..=6).contains(&level).then(|| level - 1)
+}
+
+/// Whether a link target is relative, an anchor, or one of the schemes a
+/// document may link to, which keeps `javascript:` and the like out of `href`.
+fn is_safe_href(href: &str) -> bool {
+    // browsers skip whitespace and control characters when reading the scheme
+    let href: String = href
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    match href.find([':', '/', '?', '#']) {
+        Some(i) if href[i..].starts_with(':') => ["http", "https", "mailto"]
+            .iter()
+            .any(|scheme| href[..i].eq_ignore_ascii_case(scheme)),
+        _ => true,
+    }
+}
+
+fn mime_type(name: &str) -> &'static str {
+    let ext = name
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+pub(crate) fn to_roman(mut n: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while n >= value {
+            out.push_str(numeral);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// Formats twentieths of a point as points.
+fn twips(v: isize) -> String {
+    num(v as f64 / 20.0)
+}
+
+fn num(v: f64) -> String {
+    let s = format!("{:.2}", v);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn join(a: String, b: String) -> String {
+    match (a.is_empty(), b.is_empty()) {
+        (_, true) => a,
+        (true, false) => b,
+        (false, false) => format!("{};{}", a, b),
+    }
+}
+
+fn push_style(out: &mut String, css: &str) {
+    if !css.is_empty() {
+        let _ = write!(out, " style=\"{}\"", escape(css));
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
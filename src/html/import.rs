@@ -0,0 +1,1285 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    __setter,
+    document::{
+        Break, Hyperlink, Paragraph, ParagraphContent, Run, RunContent, Table, TableCell,
+        TableCellContent, TableRow, TextSpace,
+    },
+    formatting::{
+        CharacterProperty, Fonts, Indent, JustificationVal, NumberingProperty, OnOffOnlyType,
+        ParagraphProperty, Shading, ShadingStyle, TableCellProperty, TableRowProperty,
+        UnderlineStyle, VMerge, VMergeType, VertAlign, VertAlignType,
+    },
+    import, Docx,
+};
+
+/// Options of the HTML import
+///
+/// ```rust
+/// use docx_rust::html::HtmlImportOptions;
+///
+/// let options = HtmlImportOptions::default()
+///     .base_dir("assets")
+///     .max_image_width(3657600u64);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlImportOptions {
+    /// Directory relative image sources are resolved against,
+    /// defaults to the current directory.
+    pub base_dir: Option<PathBuf>,
+    /// Font of `pre` and `code` elements.
+    pub code_font: String,
+    /// Images wider than this, in EMUs, are scaled down. Defaults to six inches.
+    pub max_image_width: u64,
+}
+
+impl Default for HtmlImportOptions {
+    fn default() -> Self {
+        HtmlImportOptions {
+            base_dir: None,
+            code_font: "Consolas".to_string(),
+            max_image_width: 5486400,
+        }
+    }
+}
+
+impl HtmlImportOptions {
+    __setter!(base_dir: Option<PathBuf>);
+    __setter!(code_font: String);
+    __setter!(max_image_width: u64);
+}
+
+impl Docx<'static> {
+    /// Creates a document from HTML, see [`Docx::push_html`].
+    pub fn from_html(html: &str, options: &HtmlImportOptions) -> Docx<'static> {
+        let mut docx = Docx::default();
+        docx.push_html(html, options);
+        docx
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts HTML and appends it to the document body.
+    ///
+    /// Supported are `p`, `div`, `h1`-`h6`, `b`/`strong`, `i`/`em`, `u`, `s`,
+    /// `sup`/`sub`, `code`, `pre`, `blockquote`, `span`, `a`, nested `ul`/`ol`/`li`,
+    /// `table`/`tr`/`td`/`th` with `colspan` and `rowspan`, `img`, `br` and `hr`,
+    /// along with the `color`, `background-color`, `font-size`, `font-family`,
+    /// `font-weight`, `font-style`, `text-decoration` and `text-align` properties
+    /// of inline styles. Other elements only contribute their text.
+    ///
+    /// Lists get new numbering definitions, and headings, code, quotes and links
+    /// refer to styles that are added when missing, like [`Docx::from_markdown`] does.
+    ///
+    /// [`Docx::from_markdown`]: #method.from_markdown
+    pub fn push_html(&mut self, html: &str, options: &HtmlImportOptions) {
+        let mut importer = HtmlImporter {
+            options,
+            docx: self,
+            paragraph: None,
+            line_start: true,
+            space_written: false,
+            open: Vec::new(),
+            table: None,
+            link: None,
+        };
+        for token in tokenize(html) {
+            importer.token(token);
+        }
+        // closes what the input left open
+        while let Some(name) = importer.open.first().map(|e| e.name.clone()) {
+            importer.end(&name);
+        }
+        importer.finish_paragraph();
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Start {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Elements without content
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose content is not text of the document
+const SKIPPED_ELEMENTS: &[&str] = &["head", "script", "style", "template", "title"];
+
+/// Elements that break paragraphs
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// Splits HTML into tags and text, dropping comments, doctypes
+/// and the content of elements like `script`.
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |i| &comment[i + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |i| &rest[i + 1..]);
+        } else if let Some(end) = rest
+            .strip_prefix("</")
+            .filter(|r| r.starts_with(|c: char| c.is_ascii_alphabetic()))
+        {
+            let close = end.find('>').unwrap_or(end.len());
+            let name = end[..close]
+                .trim()
+                .trim_end_matches('/')
+                .to_ascii_lowercase();
+            tokens.push(Token::End(name));
+            rest = end.get(close + 1..).unwrap_or("");
+        } else if let Some(tag) = rest
+            .strip_prefix('<')
+            .filter(|r| r.starts_with(|c: char| c.is_ascii_alphabetic()))
+        {
+            let (name, attrs, self_closing, remaining) = parse_tag(tag);
+            rest = remaining;
+            if SKIPPED_ELEMENTS.contains(&name.as_str()) && !self_closing {
+                let close = format!("</{}", name);
+                rest = find_ignore_case(rest, &close)
+                    .map_or("", |i| &rest[i..])
+                    .split_once('>')
+                    .map_or("", |(_, r)| r);
+                continue;
+            }
+            let void = self_closing || VOID_ELEMENTS.contains(&name.as_str());
+            tokens.push(Token::Start {
+                name: name.clone(),
+                attrs,
+            });
+            if void {
+                tokens.push(Token::End(name));
+            }
+        } else {
+            // a stray `<` is text too, so the search starts after the first char
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let end = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+/// Parses a start tag following its `<`, returns the name, attributes,
+/// whether it's self-closing and the input after the tag.
+fn parse_tag(input: &str) -> (String, Vec<(String, String)>, bool, &str) {
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == ':';
+    let name_end = input.find(|c: char| !is_name(c)).unwrap_or(input.len());
+    let name = input[..name_end].to_ascii_lowercase();
+    let mut rest = &input[name_end..];
+    let mut attrs = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return (name, attrs, false, rest);
+        }
+        if let Some(r) = rest.strip_prefix("/>") {
+            return (name, attrs, true, r);
+        }
+        if let Some(r) = rest.strip_prefix('>') {
+            return (name, attrs, false, r);
+        }
+        if let Some(r) = rest.strip_prefix('/') {
+            rest = r;
+            continue;
+        }
+
+        let attr_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let attr = rest[..attr_end].to_ascii_lowercase();
+        rest = rest[attr_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(r) = rest.strip_prefix('=') {
+            let r = r.trim_start();
+            if let Some(quote) = r.chars().next().filter(|c| *c == '"' || *c == '\'') {
+                let r = &r[1..];
+                let end = r.find(quote).unwrap_or(r.len());
+                value = decode_entities(&r[..end]);
+                rest = r.get(end + 1..).unwrap_or("");
+            } else {
+                let end = r
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(r.len());
+                value = decode_entities(&r[..end]);
+                rest = &r[end..];
+            }
+        }
+        attrs.push((attr, value));
+    }
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end + 1])?, end + 2)));
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(num) = entity.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let c = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "shy" => '\u{ad}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "deg" => '°',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "sect" => '§',
+        "para" => '¶',
+        "middot" => '·',
+        "bull" => '•',
+        "hellip" => '…',
+        "ndash" => '–',
+        "mdash" => '—',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "times" => '×',
+        "divide" => '÷',
+        _ => return None,
+    };
+    Some(c)
+}
+
+/// Run formatting inherited from the open elements
+#[derive(Debug, Clone, Default)]
+struct Format {
+    bold: bool,
+    italics: bool,
+    underline: bool,
+    strike: bool,
+    code: bool,
+    vert_align: Option<VertAlignType>,
+    color: Option<String>,
+    background: Option<String>,
+    /// In half-points
+    size: Option<isize>,
+    font: Option<String>,
+}
+
+impl Format {
+    fn property(&self, style: Option<&str>) -> Option<CharacterProperty<'static>> {
+        let mut property = CharacterProperty::default();
+        let mut empty = true;
+        let style = style.or(if self.code {
+            Some("VerbatimChar")
+        } else {
+            None
+        });
+        if let Some(style) = style {
+            property = property.style_id(style.to_string());
+            empty = false;
+        }
+        if let Some(font) = &self.font {
+            property = property.fonts(
+                Fonts::default()
+                    .ascii(font.clone())
+                    .h_ansi(font.clone())
+                    .east_asia(font.clone()),
+            );
+            empty = false;
+        }
+        if self.bold {
+            property = property.bold(true);
+            empty = false;
+        }
+        if self.italics {
+            property = property.italics(true);
+            empty = false;
+        }
+        if self.strike {
+            property = property.strike(true);
+            empty = false;
+        }
+        if let Some(color) = &self.color {
+            property = property.color(color.clone());
+            empty = false;
+        }
+        if let Some(size) = self.size {
+            property = property.size(size);
+            empty = false;
+        }
+        if self.underline {
+            property = property.underline(UnderlineStyle::Single);
+            empty = false;
+        }
+        if let Some(fill) = &self.background {
+            property.shading = Some(Shading {
+                style: Some(ShadingStyle::Clear),
+                color: Some("auto".into()),
+                fill: Some(fill.clone().into()),
+                ..Default::default()
+            });
+            empty = false;
+        }
+        if let Some(vert_align) = &self.vert_align {
+            property.vertical_align = Some(VertAlign {
+                value: Some(vert_align.clone()),
+            });
+            empty = false;
+        }
+        (!empty).then_some(property)
+    }
+}
+
+struct ListState {
+    num_id: isize,
+    /// The next paragraph is the first one of an item and gets the number
+    item_started: bool,
+}
+
+struct Element {
+    name: String,
+    format: Format,
+    align: Option<JustificationVal>,
+    list: Option<ListState>,
+}
+
+#[derive(Default)]
+struct CellState<'a> {
+    paragraphs: Vec<Paragraph<'a>>,
+    colspan: usize,
+    rowspan: usize,
+    header: bool,
+    fill: Option<String>,
+}
+
+#[derive(Default)]
+struct TableState<'a> {
+    rows: Vec<Vec<CellState<'a>>>,
+    row: Option<Vec<CellState<'a>>>,
+    cell: Option<CellState<'a>>,
+    /// Number of tables nested in the converted one, their structure is flattened
+    nested: usize,
+}
+
+struct LinkState {
+    id: Option<String>,
+    anchor: Option<String>,
+    text: String,
+    property: Option<CharacterProperty<'static>>,
+}
+
+struct HtmlImporter<'o, 'd, 'a> {
+    options: &'o HtmlImportOptions,
+    docx: &'d mut Docx<'a>,
+    paragraph: Option<Paragraph<'a>>,
+    /// Nothing was written since the paragraph started or the last line break
+    line_start: bool,
+    /// The last written character is collapsed white space
+    space_written: bool,
+    open: Vec<Element>,
+    table: Option<TableState<'a>>,
+    link: Option<LinkState>,
+}
+
+impl<'o, 'd, 'a> HtmlImporter<'o, 'd, 'a> {
+    fn token(&mut self, token: Token) {
+        match token {
+            Token::Start { name, attrs } => self.start(name, &attrs),
+            Token::End(name) => self.end(&name),
+            Token::Text(text) => self.text(&text),
+        }
+    }
+
+    fn start(&mut self, name: String, attrs: &[(String, String)]) {
+        let attr = |key: &str| {
+            attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+
+        // elements closed by their siblings
+        match name.as_str() {
+            "p" => self.close_if_open("p", &[]),
+            "li" => self.close_if_open("li", &["ul", "ol"]),
+            "td" | "th" => {
+                self.close_if_open("td", &["tr", "table"]);
+                self.close_if_open("th", &["tr", "table"]);
+            }
+            "tr" => self.close_if_open("tr", &["table"]),
+            _ => {}
+        }
+
+        if BLOCK_ELEMENTS.contains(&name.as_str()) {
+            self.finish_paragraph();
+        }
+
+        let parent = self
+            .open
+            .last()
+            .map(|e| e.format.clone())
+            .unwrap_or_default();
+        let style = attr("style").unwrap_or_default();
+        let format = element_format(&name, parent, style);
+        let mut align = css(style, "text-align")
+            .or(attr("align"))
+            .and_then(justification);
+        if name == "center" {
+            align = Some(JustificationVal::Center);
+        }
+
+        let mut list = None;
+        match name.as_str() {
+            "br" => {
+                if let Some(link) = &mut self.link {
+                    link.text.push(' ');
+                } else {
+                    self.trim_trailing_space();
+                    self.push_run(Run::default().push_break(Break { ty: None }));
+                    self.line_start = true;
+                }
+            }
+            "hr" => {
+                self.finish_paragraph();
+                self.paragraph = Some(import::horizontal_rule());
+                self.finish_paragraph();
+            }
+            "img" => self.image(attr("src"), attr("alt"), attrs),
+            "a" if self.link.is_none() => {
+                if let Some(href) = attr("href") {
+                    let (id, anchor) = match href.strip_prefix('#') {
+                        Some(anchor) => (None, Some(anchor.to_string())),
+                        None => (Some(self.docx.add_hyperlink(href.to_string())), None),
+                    };
+                    self.ensure_style("Hyperlink");
+                    self.link = Some(LinkState {
+                        id,
+                        anchor,
+                        text: String::new(),
+                        property: format.property(Some("Hyperlink")),
+                    });
+                }
+            }
+            "ul" | "ol" => {
+                let level = self.list_depth();
+                let numbering = self.docx.numbering.get_or_insert_with(Default::default);
+                let num_id = numbering.add_list(name == "ol");
+                if let Some(start) = attr("start").and_then(|s| s.trim().parse().ok()) {
+                    if name == "ol" && start != 1 {
+                        import::set_list_start(numbering, num_id, level as isize, start);
+                    }
+                }
+                list = Some(ListState {
+                    num_id,
+                    item_started: false,
+                });
+            }
+            "li" => {
+                if let Some(list) = self.open.iter_mut().rev().find_map(|e| e.list.as_mut()) {
+                    list.item_started = true;
+                }
+            }
+            "table" => match &mut self.table {
+                Some(table) => table.nested += 1,
+                None => self.table = Some(TableState::default()),
+            },
+            "tr" => {
+                if let Some(table) = self.table.as_mut().filter(|t| t.nested == 0) {
+                    table.row = Some(Vec::new());
+                }
+            }
+            "td" | "th" => {
+                if let Some(table) = self.table.as_mut().filter(|t| t.nested == 0) {
+                    let span = |key| {
+                        attr(key)
+                            .and_then(|s| s.trim().parse::<usize>().ok())
+                            .filter(|s| *s > 0)
+                            .unwrap_or(1)
+                    };
+                    let fill = css(style, "background-color")
+                        .or(attr("bgcolor"))
+                        .and_then(css_color);
+                    table.cell = Some(CellState {
+                        paragraphs: Vec::new(),
+                        colspan: span("colspan"),
+                        rowspan: span("rowspan"),
+                        header: name == "th",
+                        fill,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        if !VOID_ELEMENTS.contains(&name.as_str()) {
+            self.open.push(Element {
+                name,
+                format,
+                align,
+                list,
+            });
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        let Some(index) = self.open.iter().rposition(|e| e.name == name) else {
+            return;
+        };
+        // closes elements left open inside this one first
+        while self.open.len() > index + 1 {
+            let inner = self.open.last().map(|e| e.name.clone()).unwrap_or_default();
+            self.end(&inner);
+        }
+
+        if BLOCK_ELEMENTS.contains(&name) {
+            self.finish_paragraph();
+        }
+
+        match name {
+            "a" => self.finish_link(),
+            "td" | "th" => self.close_table_elements(1),
+            "tr" => self.close_table_elements(2),
+            "table" => match &mut self.table {
+                Some(table) if table.nested > 0 => table.nested -= 1,
+                Some(_) => self.close_table_elements(usize::MAX),
+                None => {}
+            },
+            _ => {}
+        }
+
+        self.open.pop();
+    }
+
+    fn text(&mut self, text: &str) {
+        let pre = self.open.iter().any(|e| e.name == "pre");
+        if pre {
+            let text = if self.line_start {
+                text.strip_prefix('\n').unwrap_or(text)
+            } else {
+                text
+            };
+            for (i, line) in text.split('\n').enumerate() {
+                if i > 0 {
+                    self.push_run(Run::default().push_break(Break { ty: None }));
+                }
+                if !line.is_empty() {
+                    self.push_text(line.to_string());
+                }
+            }
+            return;
+        }
+
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if c.is_ascii_whitespace() {
+                if !self.line_start && !self.space_written {
+                    collapsed.push(' ');
+                    self.space_written = true;
+                }
+            } else {
+                self.space_written = false;
+                self.line_start = false;
+                collapsed.push(c);
+            }
+        }
+        if !collapsed.is_empty() {
+            self.push_text(collapsed);
+        }
+    }
+
+    fn push_text(&mut self, text: String) {
+        if let Some(link) = &mut self.link {
+            link.text.push_str(&text);
+            return;
+        }
+        let format = self
+            .open
+            .last()
+            .map(|e| e.format.clone())
+            .unwrap_or_default();
+        if format.code {
+            self.ensure_style("VerbatimChar");
+        }
+        let run = Run {
+            property: format.property(None),
+            ..Default::default()
+        };
+        self.push_run(run.push_text((text, TextSpace::Preserve)));
+    }
+
+    fn push_run(&mut self, run: Run<'a>) {
+        self.line_start = false;
+        self.ensure_paragraph();
+        if let Some(paragraph) = self.paragraph.take() {
+            self.paragraph = Some(paragraph.push(run));
+        }
+    }
+
+    /// Removes collapsed white space written at the end of a line.
+    fn trim_trailing_space(&mut self) {
+        if !self.space_written {
+            return;
+        }
+        self.space_written = false;
+        if let Some(link) = &mut self.link {
+            link.text.truncate(link.text.trim_end_matches(' ').len());
+            return;
+        }
+        let last_text = self
+            .paragraph
+            .as_mut()
+            .and_then(|p| match p.content.last_mut()? {
+                ParagraphContent::Run(run) => match run.content.last_mut()? {
+                    RunContent::Text(text) => Some(text),
+                    _ => None,
+                },
+                _ => None,
+            });
+        if let Some(text) = last_text {
+            if let Some(trimmed) = text.text.strip_suffix(' ') {
+                text.text = trimmed.to_string().into();
+            }
+        }
+    }
+
+    fn finish_link(&mut self) {
+        let Some(link) = self.link.take() else {
+            return;
+        };
+        let run = Run {
+            property: link.property,
+            ..Default::default()
+        }
+        .push_text((link.text, TextSpace::Preserve));
+        let hyperlink = Hyperlink {
+            id: link.id.map(Into::into),
+            anchor: link.anchor.map(Into::into),
+            content: Some(run),
+            bidirectional_embedding: None,
+        };
+        self.ensure_paragraph();
+        if let Some(paragraph) = self.paragraph.take() {
+            self.paragraph = Some(paragraph.push(hyperlink));
+        }
+    }
+
+    fn image(&mut self, src: Option<&str>, alt: Option<&str>, attrs: &[(String, String)]) {
+        let alt = alt.unwrap_or_default();
+        let Some(src) = src else {
+            return;
+        };
+
+        let loaded = if let Some(data) = src.strip_prefix("data:") {
+            data.split_once(";base64,").and_then(|(mime, data)| {
+                let ext = mime.strip_prefix("image/")?.replace("jpeg", "jpg");
+                let name = format!("image{}.{}", self.docx.media.len() + 1, ext);
+                Some((name, crate::base64::decode(data)?))
+            })
+        } else if src.contains("://") {
+            None
+        } else {
+            let path = match &self.options.base_dir {
+                Some(base) => base.join(src),
+                None => Path::new(src).to_path_buf(),
+            };
+            let name = path.file_name().and_then(|n| n.to_str()).map(String::from);
+            name.zip(std::fs::read(&path).ok())
+        };
+
+        let picture = loaded.and_then(|(name, bytes)| {
+            let size = image_size(&bytes, attrs);
+            import::picture(
+                self.docx,
+                &name,
+                bytes,
+                alt,
+                size,
+                self.options.max_image_width,
+            )
+        });
+        match picture {
+            Some(drawing) => self.push_run(Run::default().push(drawing)),
+            None => {
+                log::warn!("[HTML] Could not load image {:?}.", src);
+                self.text(alt);
+            }
+        }
+    }
+
+    /// Closes the current cell (`depth` 1), row (2) or table of the converted table.
+    fn close_table_elements(&mut self, depth: usize) {
+        let Some(table) = &mut self.table else {
+            return;
+        };
+        if table.nested > 0 && depth < usize::MAX {
+            return;
+        }
+        if let Some(mut cell) = table.cell.take() {
+            if cell.paragraphs.is_empty() {
+                cell.paragraphs.push(Paragraph::default());
+            }
+            table.row.get_or_insert_with(Vec::new).push(cell);
+        }
+        if depth >= 2 {
+            if let Some(row) = table.row.take() {
+                table.rows.push(row);
+            }
+        }
+        if depth == usize::MAX {
+            if let Some(table) = self.table.take() {
+                self.docx.document.push(build_table(table));
+            }
+        }
+    }
+
+    /// Closes an open `name` element, unless one of `boundaries` is opened after it.
+    fn close_if_open(&mut self, name: &str, boundaries: &[&str]) {
+        for element in self.open.iter().rev() {
+            if element.name == name {
+                self.end(name);
+                return;
+            }
+            if boundaries.contains(&element.name.as_str()) {
+                return;
+            }
+        }
+    }
+
+    fn list_depth(&self) -> usize {
+        self.open.iter().filter(|e| e.list.is_some()).count()
+    }
+
+    /// Starts a paragraph formatted after the open block elements, if none is open.
+    fn ensure_paragraph(&mut self) {
+        if self.paragraph.is_some() {
+            return;
+        }
+
+        let mut property = ParagraphProperty::default();
+        let innermost = |names: &[&str]| {
+            self.open
+                .iter()
+                .rev()
+                .find(|e| names.contains(&e.name.as_str()))
+                .map(|e| e.name.clone())
+        };
+        let heading = innermost(&["h1", "h2", "h3", "h4", "h5", "h6"]);
+        let pre = innermost(&["pre"]).is_some();
+        let in_item = innermost(&["li", "ul", "ol"]).is_some_and(|n| n == "li");
+        let in_list = self.open.iter().any(|e| e.list.is_some());
+        let quote_depth = self.open.iter().filter(|e| e.name == "blockquote").count();
+
+        if let Some(heading) = heading {
+            let style = format!("Heading{}", &heading[1..]);
+            self.ensure_style(&style);
+            property = property.style_id(style);
+        } else if pre {
+            self.ensure_style("SourceCode");
+            property = property.style_id("SourceCode");
+        } else if quote_depth > 0 && !in_list {
+            self.ensure_style("Quote");
+            property = property.style_id("Quote");
+        }
+
+        if let Some(align) = self.open.iter().rev().find_map(|e| e.align.clone()) {
+            property = property.justification(align);
+        }
+
+        let level = self.list_depth().saturating_sub(1).min(8) as isize;
+        if let Some(list) = self.open.iter_mut().rev().find_map(|e| e.list.as_mut()) {
+            if list.item_started && in_item {
+                list.item_started = false;
+                property = property.numbering(NumberingProperty::from((list.num_id, level)));
+            } else {
+                // continuation paragraphs line up with the text of the item
+                property = property.indent(Indent {
+                    left: Some(720 * (level + 1)),
+                    ..Default::default()
+                });
+            }
+        } else if quote_depth > 1 {
+            property = property.indent(Indent {
+                left: Some(720 * quote_depth as isize),
+                ..Default::default()
+            });
+        }
+
+        self.paragraph = Some(Paragraph::default().property(property));
+    }
+
+    fn finish_paragraph(&mut self) {
+        self.trim_trailing_space();
+        self.finish_link();
+        self.line_start = true;
+        self.space_written = false;
+
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+        if let Some(table) = self.table.as_mut() {
+            if let Some(cell) = &mut table.cell {
+                cell.paragraphs.push(paragraph);
+                return;
+            }
+        }
+        self.docx.document.push(paragraph);
+    }
+
+    fn ensure_style(&mut self, style_id: &str) {
+        import::ensure_style(self.docx, style_id, &self.options.code_font);
+    }
+}
+
+/// The formatting of an element's content
+fn element_format(name: &str, mut format: Format, style: &str) -> Format {
+    match name {
+        "b" | "strong" | "th" => format.bold = true,
+        "i" | "em" | "cite" | "dfn" | "var" => format.italics = true,
+        "u" | "ins" => format.underline = true,
+        "s" | "strike" | "del" => format.strike = true,
+        "sup" => format.vert_align = Some(VertAlignType::Superscript),
+        "sub" => format.vert_align = Some(VertAlignType::Subscript),
+        "code" | "kbd" | "samp" | "tt" => format.code = true,
+        "mark" => format.background = Some("FFFF00".to_string()),
+        _ => {}
+    }
+
+    // table cells are shaded instead of their text
+    let cell = matches!(name, "td" | "th" | "tr" | "table");
+    for (property, original) in css_declarations(style) {
+        let value = original.to_ascii_lowercase();
+        match property.as_str() {
+            "color" => format.color = css_color(&value),
+            "background-color" | "background" if !cell => {
+                format.background = css_color(&value);
+            }
+            "font-weight" => {
+                format.bold = match value.as_str() {
+                    "bold" | "bolder" => true,
+                    "normal" | "lighter" => false,
+                    weight => weight.parse::<u32>().map_or(format.bold, |w| w >= 600),
+                }
+            }
+            "font-style" => format.italics = value == "italic" || value == "oblique",
+            "text-decoration" | "text-decoration-line" => {
+                if value.contains("none") {
+                    format.underline = false;
+                    format.strike = false;
+                }
+                if value.contains("underline") {
+                    format.underline = true;
+                }
+                if value.contains("line-through") {
+                    format.strike = true;
+                }
+            }
+            "vertical-align" => match value.as_str() {
+                "super" => format.vert_align = Some(VertAlignType::Superscript),
+                "sub" => format.vert_align = Some(VertAlignType::Subscript),
+                "baseline" => format.vert_align = None,
+                _ => {}
+            },
+            "font-size" => {
+                if let Some(size) = css_font_size(&value, format.size.unwrap_or(24)) {
+                    format.size = Some(size);
+                }
+            }
+            "font-family" => {
+                format.font = original
+                    .split(',')
+                    .next()
+                    .map(|f| f.trim().trim_matches(['"', '\'']).to_string())
+                    .filter(|f| !f.is_empty());
+            }
+            _ => {}
+        }
+    }
+
+    format
+}
+
+/// Splits an inline style into lowercase property names and values.
+fn css_declarations(style: &str) -> impl Iterator<Item = (String, &str)> {
+    style.split(';').filter_map(|declaration| {
+        let (property, value) = declaration.split_once(':')?;
+        let value = value.trim().trim_end_matches("!important").trim();
+        Some((property.trim().to_ascii_lowercase(), value))
+    })
+}
+
+fn css<'s>(style: &'s str, property: &str) -> Option<&'s str> {
+    css_declarations(style)
+        .filter(|(p, _)| p == property)
+        .last()
+        .map(|(_, v)| v)
+}
+
+fn justification(value: &str) -> Option<JustificationVal> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "left" | "start" => Some(JustificationVal::Left),
+        "center" => Some(JustificationVal::Center),
+        "right" | "end" => Some(JustificationVal::Right),
+        "justify" => Some(JustificationVal::Both),
+        _ => None,
+    }
+}
+
+/// Converts a CSS color to `RRGGBB`.
+fn css_color(value: &str) -> Option<String> {
+    let value = value.trim().to_ascii_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 => Some(hex.chars().flat_map(|c| [c, c]).collect::<String>()),
+            6 => Some(hex.to_string()),
+            _ => None,
+        }
+        .map(|h| h.to_ascii_uppercase());
+    }
+    if let Some(args) = value
+        .strip_prefix("rgb(")
+        .or(value.strip_prefix("rgba("))
+        .and_then(|v| v.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = args
+            .split([',', ' ', '/'])
+            .filter(|c| !c.is_empty())
+            .take(3)
+            .map(|c| match c.strip_suffix('%') {
+                Some(pct) => pct.parse::<f64>().map(|p| (p * 2.55).round() as u8),
+                None => c.parse::<f64>().map(|v| v.clamp(0.0, 255.0) as u8),
+            })
+            .collect::<Result<_, _>>()
+            .ok()?;
+        if channels.len() == 3 {
+            return Some(format!(
+                "{:02X}{:02X}{:02X}",
+                channels[0], channels[1], channels[2]
+            ));
+        }
+        return None;
+    }
+    let hex = match value.as_str() {
+        "black" => "000000",
+        "white" => "FFFFFF",
+        "red" => "FF0000",
+        "green" => "008000",
+        "lime" => "00FF00",
+        "blue" => "0000FF",
+        "yellow" => "FFFF00",
+        "orange" => "FFA500",
+        "purple" => "800080",
+        "fuchsia" | "magenta" => "FF00FF",
+        "aqua" | "cyan" => "00FFFF",
+        "navy" => "000080",
+        "teal" => "008080",
+        "maroon" => "800000",
+        "olive" => "808000",
+        "silver" => "C0C0C0",
+        "gray" | "grey" => "808080",
+        _ => return None,
+    };
+    Some(hex.to_string())
+}
+
+/// Converts a CSS font size to half-points, `parent` is the inherited size.
+fn css_font_size(value: &str, parent: isize) -> Option<isize> {
+    let value = value.trim();
+    let number = |s: &str| s.trim().parse::<f64>().ok();
+    let half_points = if let Some(pt) = value.strip_suffix("pt") {
+        number(pt)? * 2.0
+    } else if let Some(px) = value.strip_suffix("px") {
+        number(px)? * 1.5
+    } else if let Some(em) = value.strip_suffix("rem") {
+        number(em)? * 24.0
+    } else if let Some(em) = value.strip_suffix("em") {
+        number(em)? * parent as f64
+    } else if let Some(pct) = value.strip_suffix('%') {
+        number(pct)? * parent as f64 / 100.0
+    } else {
+        match value {
+            "xx-small" => 14.0,
+            "x-small" => 15.0,
+            "small" => 20.0,
+            "medium" => 24.0,
+            "large" => 27.0,
+            "x-large" => 36.0,
+            "xx-large" => 48.0,
+            _ => return None,
+        }
+    };
+    Some(half_points.round() as isize).filter(|s| *s > 0)
+}
+
+/// Size of an image in pixels from its `width` and `height` attributes or styles,
+/// keeping the aspect ratio when only one of them is given.
+fn image_size(bytes: &[u8], attrs: &[(String, String)]) -> Option<(u64, u64)> {
+    let style = attrs
+        .iter()
+        .find(|(k, _)| k == "style")
+        .map(|(_, v)| v.as_str())
+        .unwrap_or_default();
+    let pixels = |key: &str| {
+        css(style, key)
+            .or_else(|| {
+                attrs
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+            })
+            .and_then(|v| v.trim().trim_end_matches("px").parse::<f64>().ok())
+            .filter(|v| *v > 0.0)
+            .map(|v| v as u64)
+    };
+
+    let natural = crate::media::get_image_size(bytes).map(|(w, h)| (w as u64, h as u64));
+    match (pixels("width"), pixels("height"), natural) {
+        (Some(w), Some(h), _) => Some((w, h)),
+        (Some(w), None, Some((nw, nh))) if nw > 0 => Some((w, w * nh / nw)),
+        (None, Some(h), Some((nw, nh))) if nh > 0 => Some((h * nw / nh, h)),
+        _ => natural,
+    }
+}
+
+fn build_table(state: TableState) -> Table {
+    // cells still covered by a rowspan from above, per grid column
+    let mut covered: Vec<(usize, usize)> = Vec::new();
+    let mut rows = Vec::new();
+    let mut columns = 0;
+    let mut header = true;
+
+    for cells in state.rows {
+        let mut row = TableRow::default();
+        let mut column = 0;
+        let mut all_header = !cells.is_empty();
+        let mut cells = cells.into_iter().peekable();
+
+        loop {
+            if let Some((remaining, colspan)) = covered.get_mut(column).filter(|c| c.0 > 0) {
+                *remaining -= 1;
+                let colspan = *colspan;
+                row = row.push_cell(merged_cell(colspan, VMerge::default()));
+                column += colspan;
+                continue;
+            }
+            let Some(cell) = cells.next() else {
+                // rowspans reaching past the last cell of the row
+                if covered.iter().skip(column).any(|c| c.0 > 0) {
+                    column += 1;
+                    continue;
+                }
+                break;
+            };
+
+            all_header &= cell.header;
+            let mut property = TableCellProperty::default();
+            if cell.colspan > 1 {
+                property = property.grid_span(cell.colspan as isize);
+            }
+            if cell.rowspan > 1 {
+                property = property.v_merge(VMergeType::Restart);
+                if covered.len() < column + 1 {
+                    covered.resize(column + 1, (0, 1));
+                }
+                covered[column] = (cell.rowspan - 1, cell.colspan);
+            }
+            if let Some(fill) = cell.fill {
+                property = property.shading(Shading {
+                    style: Some(ShadingStyle::Clear),
+                    color: Some("auto".into()),
+                    fill: Some(fill.into()),
+                    ..Default::default()
+                });
+            }
+            row = row.push_cell(TableCell {
+                property,
+                content: cell
+                    .paragraphs
+                    .into_iter()
+                    .map(TableCellContent::Paragraph)
+                    .collect(),
+            });
+            column += cell.colspan;
+        }
+
+        header &= all_header;
+        if header {
+            row = row.property(TableRowProperty::default().table_header(OnOffOnlyType::On));
+        }
+        columns = columns.max(column);
+        rows.push(row);
+    }
+
+    let mut table = Table {
+        grids: import::table_grid(columns),
+        ..Default::default()
+    }
+    .property(import::table_property());
+    for row in rows {
+        table = table.push_row(row);
+    }
+    table
+}
+
+fn merged_cell<'a>(colspan: usize, v_merge: VMerge) -> TableCell<'a> {
+    let mut property = TableCellProperty::default().v_merge(v_merge);
+    if colspan > 1 {
+        property = property.grid_span(colspan as isize);
+    }
+    TableCell::paragraph(Paragraph::default()).property(property)
+}
+
+#[test]
+fn tokenize_test() {
+    let tokens = tokenize(
+        r#"<!DOCTYPE html><p class=x data-a='1 &amp; 2'>a &lt;b&gt;&nbsp;<br/><!-- c --><script>x</script></P>"#,
+    );
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Start {
+                name: "p".into(),
+                attrs: vec![
+                    ("class".into(), "x".into()),
+                    ("data-a".into(), "1 & 2".into())
+                ]
+            },
+            Token::Text("a <b>\u{a0}".into()),
+            Token::Start {
+                name: "br".into(),
+                attrs: vec![]
+            },
+            Token::End("br".into()),
+            Token::End("p".into()),
+        ]
+    );
+
+    assert_eq!(
+        tokenize("<b>日本</b>été"),
+        vec![
+            Token::Start {
+                name: "b".into(),
+                attrs: vec![]
+            },
+            Token::Text("日本".into()),
+            Token::End("b".into()),
+            Token::Text("été".into()),
+        ]
+    );
+}
+
+#[test]
+fn css_test() {
+    assert_eq!(css_color("#f00").as_deref(), Some("FF0000"));
+    assert_eq!(css_color("#1a2B3c").as_deref(), Some("1A2B3C"));
+    assert_eq!(css_color("rgb(255, 128, 0)").as_deref(), Some("FF8000"));
+    assert_eq!(css_color("navy").as_deref(), Some("000080"));
+    assert_eq!(css_color("inherit"), None);
+
+    assert_eq!(css_font_size("12pt", 24), Some(24));
+    assert_eq!(css_font_size("16px", 24), Some(24));
+    assert_eq!(css_font_size("1.5em", 20), Some(30));
+    assert_eq!(css_font_size("large", 24), Some(27));
+
+    let format = element_format(
+        "span",
+        Format::default(),
+        "font-weight: 700; font-family: 'Times New Roman', serif; text-decoration: underline",
+    );
+    assert!(format.bold && format.underline);
+    assert_eq!(format.font.as_deref(), Some("Times New Roman"));
+}
@@ -0,0 +1,36 @@
+//! HTML conversion
+//!
+//! [`Docx::to_html`] renders a document into semantic HTML with inline CSS,
+//! which is good enough for previews in a browser.
+//!
+//! ```no_run
+//! use docx_rust::{html::HtmlOptions, DocxFile};
+//!
+//! let docx = DocxFile::from_file("origin.docx").unwrap();
+//! let docx = docx.parse().unwrap();
+//!
+//! let html = docx.to_html(&HtmlOptions::default().footnotes(true).comments(true));
+//! std::fs::write("origin.html", html).unwrap();
+//! ```
+//!
+//! [`Docx::from_html`] goes the other way, turning HTML from rich text editors
+//! into paragraphs, tables and lists.
+//!
+//! ```rust
+//! use docx_rust::{html::HtmlImportOptions, Docx};
+//!
+//! let docx = Docx::from_html(
+//!     "<p>Signed by <b style=\"color: #c00\">both parties</b>.</p>",
+//!     &HtmlImportOptions::default(),
+//! );
+//! assert_eq!(docx.document.body.text(), "Signed by both parties.");
+//! ```
+//!
+//! [`Docx::to_html`]: ../struct.Docx.html#method.to_html
+//! [`Docx::from_html`]: ../struct.Docx.html#method.from_html
+
+mod export;
+mod import;
+
+pub use self::export::*;
+pub use self::import::*;
@@ -0,0 +1,249 @@
+//! Building blocks shared by the Markdown and HTML imports and the table of contents.
+
+use crate::{
+    document::{Drawing, LevelOverride, Numbering, Paragraph, StartOverride, TableGrid},
+    formatting::{
+        BorderStyle, Borders, BottomBorder, CharacterProperty, CustomTabStop, CustomTabStopSet,
+        CustomTabStopSetChoice, Fonts, Indent, InsideHorizonBorder, InsideVerticalBorder, KeepNext,
+        LeftBorder, OutlineLvl, ParagraphProperty, RightBorder, Spacing, TabLeaderCharacter,
+        TabStopType, TableBorders, TableProperty, TableWidthUnit, TopBorder, UnderlineStyle,
+    },
+    styles::{BasedOn, Style, StyleType},
+    Docx,
+};
+
+/// EMUs per pixel, assuming images are 96 DPI
+pub(crate) const EMU_PER_PIXEL: u64 = 9525;
+
+/// Text width of a letter page with one inch margins, in twips
+const TEXT_WIDTH: isize = 9360;
+
+/// Adds one of the styles the imports refer to, unless the document already has it.
+///
+/// Known styles are `Heading1` to `Heading6`, `SourceCode`, `VerbatimChar`,
+/// `Quote`, `Hyperlink`, `TOCHeading` and `TOC1` to `TOC9`.
+pub(crate) fn ensure_style(docx: &mut Docx, style_id: &str, code_font: &str) {
+    if docx.styles.styles.iter().any(|s| s.style_id == style_id) {
+        return;
+    }
+    let code_fonts = || {
+        Fonts::default()
+            .ascii(code_font.to_string())
+            .h_ansi(code_font.to_string())
+    };
+    let style = match style_id {
+        "SourceCode" => Style::new(StyleType::Paragraph, "SourceCode")
+            .name("Source Code")
+            .paragraph(ParagraphProperty::default().spacing(Spacing {
+                before: Some(0),
+                after: Some(0),
+                ..Default::default()
+            }))
+            .character(
+                CharacterProperty::default()
+                    .fonts(code_fonts())
+                    .size(20isize),
+            ),
+        "VerbatimChar" => Style::new(StyleType::Character, "VerbatimChar")
+            .name("Verbatim Char")
+            .character(
+                CharacterProperty::default()
+                    .fonts(code_fonts())
+                    .size(20isize),
+            ),
+        "Quote" => Style::new(StyleType::Paragraph, "Quote")
+            .name("Quote")
+            .paragraph(ParagraphProperty::default().indent(Indent {
+                left: Some(720),
+                right: Some(720),
+                ..Default::default()
+            }))
+            .character(CharacterProperty::default().italics(true).color("404040")),
+        "Hyperlink" => Style::new(StyleType::Character, "Hyperlink")
+            .name("Hyperlink")
+            .character(
+                CharacterProperty::default()
+                    .color("0563C1")
+                    .underline(UnderlineStyle::Single),
+            ),
+        "TOCHeading" => {
+            ensure_style(docx, "Heading1", code_font);
+            let mut style = Style::new(StyleType::Paragraph, "TOCHeading")
+                .name("TOC Heading")
+                .paragraph(ParagraphProperty {
+                    outline_lvl: Some(OutlineLvl { value: 9 }),
+                    ..Default::default()
+                });
+            style.base = Some(BasedOn {
+                value: "Heading1".into(),
+            });
+            style
+        }
+        toc if toc.starts_with("TOC") => {
+            let Some(level) = toc[3..]
+                .parse::<isize>()
+                .ok()
+                .filter(|l| (1..=9).contains(l))
+            else {
+                return;
+            };
+            let tab = CustomTabStop::default()
+                .tab_stop_type(TabStopType::Right)
+                .leader(TabLeaderCharacter::Dot)
+                .pos(TEXT_WIDTH);
+            let paragraph = ParagraphProperty {
+                tabs: Some(CustomTabStopSet {
+                    content: vec![CustomTabStopSetChoice::CustomTabStop(tab)],
+                }),
+                spacing: Some(Spacing {
+                    after: Some(100),
+                    ..Default::default()
+                }),
+                indent: Some(Indent {
+                    left: Some(220 * (level - 1)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            Style::new(StyleType::Paragraph, toc.to_string())
+                .name(format!("toc {}", level))
+                .paragraph(paragraph)
+        }
+        heading => {
+            let Some(level) = heading
+                .strip_prefix("Heading")
+                .and_then(|l| l.parse::<usize>().ok())
+                .filter(|l| (1..=6).contains(l))
+            else {
+                return;
+            };
+            let paragraph = ParagraphProperty {
+                keep_next: Some(KeepNext { value: None }),
+                spacing: Some(Spacing {
+                    before: Some(240),
+                    after: Some(60),
+                    ..Default::default()
+                }),
+                outline_lvl: Some(OutlineLvl {
+                    value: level as isize - 1,
+                }),
+                ..Default::default()
+            };
+            let size: isize = [32, 28, 26, 24, 22, 22][level - 1];
+            Style::new(StyleType::Paragraph, heading.to_string())
+                .name(format!("heading {}", level))
+                .paragraph(paragraph)
+                .character(CharacterProperty::default().bold(true).size(size))
+        }
+    };
+    docx.styles.push(style);
+}
+
+/// Adds an image to the document and returns an inline picture showing it.
+///
+/// Without an explicit `size` in pixels, the size is read from the image header.
+/// Pictures wider than `max_width` EMUs are scaled down.
+pub(crate) fn picture<'a>(
+    docx: &mut Docx<'a>,
+    file_name: &str,
+    bytes: Vec<u8>,
+    alt: &str,
+    size: Option<(u64, u64)>,
+    max_width: u64,
+) -> Option<Drawing<'a>> {
+    let (width, height) = match size {
+        Some(size) => size,
+        None => {
+            let (width, height) = crate::media::get_image_size(&bytes)?;
+            (width as u64, height as u64)
+        }
+    };
+    let id = docx.add_image(file_name, bytes)?;
+
+    let mut cx = width * EMU_PER_PIXEL;
+    let mut cy = height * EMU_PER_PIXEL;
+    if cx > max_width {
+        cy = cy * max_width / cx;
+        cx = max_width;
+    }
+
+    let drawing_id = docx.media.len() as isize;
+    let mut drawing = Drawing::inline_picture(drawing_id, file_name.to_string(), id, cx, cy);
+    if let Some(inline) = &mut drawing.inline {
+        if !alt.is_empty() {
+            inline.doc_property.descr = Some(alt.to_string().into());
+        }
+    }
+    Some(drawing)
+}
+
+/// An empty paragraph with a bottom border.
+pub(crate) fn horizontal_rule() -> Paragraph<'static> {
+    let border = Borders::default().bottom(
+        BottomBorder::default()
+            .style(BorderStyle::Single)
+            .size(6isize)
+            .space(1isize)
+            .color("auto"),
+    );
+    Paragraph::default().property(ParagraphProperty::default().border(border))
+}
+
+/// Full width with single borders around and between all cells.
+pub(crate) fn table_property() -> TableProperty<'static> {
+    let borders = TableBorders::default()
+        .top(TopBorder::default().style(BorderStyle::Single).size(4isize))
+        .left(
+            LeftBorder::default()
+                .style(BorderStyle::Single)
+                .size(4isize),
+        )
+        .bottom(
+            BottomBorder::default()
+                .style(BorderStyle::Single)
+                .size(4isize),
+        )
+        .right(
+            RightBorder::default()
+                .style(BorderStyle::Single)
+                .size(4isize),
+        )
+        .inside_horizon(
+            InsideHorizonBorder::default()
+                .style(BorderStyle::Single)
+                .size(4isize),
+        )
+        .inside_vertical(
+            InsideVerticalBorder::default()
+                .style(BorderStyle::Single)
+                .size(4isize),
+        );
+
+    TableProperty::default()
+        .width((5000, TableWidthUnit::Pct))
+        .borders(borders)
+}
+
+/// Columns of equal width.
+pub(crate) fn table_grid(columns: usize) -> TableGrid {
+    let columns = columns.max(1) as isize;
+    let mut grid = TableGrid::default();
+    for _ in 0..columns {
+        grid = grid.push_column(TEXT_WIDTH / columns);
+    }
+    grid
+}
+
+/// Makes a list level start at another number than one.
+pub(crate) fn set_list_start(numbering: &mut Numbering, num_id: isize, level: isize, start: isize) {
+    if let Some(num) = numbering
+        .numberings
+        .iter_mut()
+        .find(|n| n.num_id == Some(num_id))
+    {
+        num.level_overrides.push(LevelOverride {
+            i_level: Some(level),
+            start_override: Some(StartOverride { value: Some(start) }),
+        });
+    }
+}
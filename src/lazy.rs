@@ -0,0 +1,262 @@
+//! On-demand reading of packages
+//!
+//! [`DocxFile`](crate::DocxFile) reads every part of a package up front, and
+//! [`DocxFile::parse`](crate::DocxFile::parse) parses all of them. A
+//! [`LazyDocxFile`] keeps the archive open instead, reads a part the first time
+//! it's asked for and parses it on every call, which is cheaper when only a few
+//! parts are needed, like the text of the main document.
+//!
+//! ```no_run
+//! use docx_rust::LazyDocxFile;
+//!
+//! let docx = LazyDocxFile::from_file("origin.docx").unwrap();
+//!
+//! println!("{}", docx.document().unwrap().body.text());
+//! for name in docx.media_names() {
+//!     let bytes = docx.media_bytes(name).unwrap().unwrap();
+//!     println!("{}: {} bytes", name, bytes.len());
+//! }
+//! ```
+
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+use zip::{read::ZipFile, result::ZipError, ZipArchive};
+
+use crate::{
+    app::App,
+    content_type::ContentTypes,
+    core::Core,
+    custom_properties::CustomProperties,
+    document::{Comments, Document, EndNotes, FootNotes, Footer, Header, Numbering, Theme},
+    docx::is_compound_file,
+    error::{DocxError, DocxResult},
+    font_table::FontTable,
+    parse::parse_part,
+    reader::DocumentReader,
+    rels::Relationships,
+    settings::Settings,
+    styles::Styles,
+    web_settings::WebSettings,
+};
+
+/// A docx file whose parts are read when accessed
+///
+/// [`part_names`](Self::part_names), [`xml`](Self::xml) and
+/// [`is_loaded`](Self::is_loaded) use the full names in the archive, like
+/// `word/document.xml`. The headers, footers, themes and media are named as in
+/// [`Docx`](crate::Docx) instead, relative to `word/`, like `header1.xml` or
+/// `media/image1.png`, by their `*_names` methods and the methods reading them.
+pub struct LazyDocxFile<R: Read + Seek> {
+    zip: RefCell<ZipArchive<R>>,
+    /// Content of the parts read so far, by name in the archive
+    parts: HashMap<String, OnceCell<String>>,
+    /// Names of the media files in the archive, which are never kept in memory
+    media: Vec<String>,
+}
+
+macro_rules! __lazy_part {
+    ($($(#[$doc:meta])* $fn:ident -> $ty:ty = $name:expr;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $fn(&self) -> DocxResult<Option<$ty>> {
+                match self.xml($name)? {
+                    Some(xml) => Ok(Some(parse_part::<$ty>($name, xml)?)),
+                    None => Ok(None),
+                }
+            }
+        )*
+    };
+}
+
+impl<R: Read + Seek> LazyDocxFile<R> {
+    /// Opens a package without reading any of its parts.
+    ///
+    /// Fails with [`DocxError::Encrypted`] on files that need a password to open.
+    pub fn from_reader(mut reader: R) -> DocxResult<Self> {
+        if is_compound_file(&mut reader)? {
+            return Err(DocxError::Encrypted);
+        }
+
+        let zip = ZipArchive::new(reader)?;
+        let (mut media, parts): (Vec<_>, Vec<_>) = zip
+            .file_names()
+            .map(String::from)
+            .partition(|name| name.starts_with("word/media/"));
+        media.sort();
+
+        Ok(LazyDocxFile {
+            zip: RefCell::new(zip),
+            parts: parts
+                .into_iter()
+                .map(|name| (name, OnceCell::new()))
+                .collect(),
+            media,
+        })
+    }
+
+    /// Returns the names of all the files in the archive, like `word/document.xml`.
+    pub fn part_names(&self) -> impl Iterator<Item = &str> + '_ {
+        let mut names: Vec<&str> = self
+            .parts
+            .keys()
+            .chain(&self.media)
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names.into_iter()
+    }
+
+    /// Returns the content of a part, reading it from the archive the first time.
+    ///
+    /// `name` is the full name in the archive, like `word/document.xml`.
+    pub fn xml(&self, name: &str) -> DocxResult<Option<&str>> {
+        let Some(cell) = self.parts.get(name) else {
+            return Ok(None);
+        };
+        if let Some(xml) = cell.get() {
+            return Ok(Some(xml));
+        }
+        let mut buffer = String::new();
+        self.zip
+            .borrow_mut()
+            .by_name(name)?
+            .read_to_string(&mut buffer)?;
+        Ok(Some(cell.get_or_init(|| buffer)))
+    }
+
+    /// Returns `true` if the part has been read from the archive.
+    ///
+    /// `name` is the full name in the archive, like `word/header1.xml`.
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.parts
+            .get(name)
+            .is_some_and(|cell| cell.get().is_some())
+    }
+
+    /// Parses the main document part.
+    pub fn document(&self) -> DocxResult<Document<'_>> {
+        let xml = self
+            .xml("word/document.xml")?
+            .ok_or(DocxError::Zip(ZipError::FileNotFound))?;
+        parse_part("word/document.xml", xml)
+    }
+
+    /// Streams the events of the main document part, without keeping it in memory.
+    pub fn document_reader(&mut self) -> DocxResult<DocumentReader<ZipFile<'_>>> {
+        let file = self.zip.get_mut().by_name("word/document.xml")?;
+        Ok(DocumentReader::new(file))
+    }
+
+    /// Parses the styles, which are empty if the package has none.
+    pub fn styles(&self) -> DocxResult<Styles<'_>> {
+        match self.xml("word/styles.xml")? {
+            Some(xml) => parse_part("word/styles.xml", xml),
+            None => Ok(Default::default()),
+        }
+    }
+
+    __lazy_part! {
+        content_types -> ContentTypes<'_> = "[Content_Types].xml";
+        app -> App<'_> = "docProps/app.xml";
+        core -> Core<'_> = "docProps/core.xml";
+        custom_properties -> CustomProperties<'_> = "docProps/custom.xml";
+        /// Parses the relationships of the main document part, unfiltered.
+        document_rels -> Relationships<'_> = "word/_rels/document.xml.rels";
+        font_table -> FontTable<'_> = "word/fontTable.xml";
+        settings -> Settings<'_> = "word/settings.xml";
+        footnotes -> FootNotes<'_> = "word/footnotes.xml";
+        endnotes -> EndNotes<'_> = "word/endnotes.xml";
+        comments -> Comments<'_> = "word/comments.xml";
+        numbering -> Numbering<'_> = "word/numbering.xml";
+    }
+
+    pub fn web_settings(&self) -> DocxResult<Option<WebSettings>> {
+        match self.xml("word/webSettings.xml")? {
+            Some(xml) => Ok(Some(parse_part(
+                "word/webSettings.xml",
+                &xml.replace("ns0:", "w:"),
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the names of the headers, like `header1.xml`.
+    pub fn header_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.word_names("header")
+    }
+
+    pub fn header(&self, name: &str) -> DocxResult<Option<Header<'_>>> {
+        let name = format!("word/{}", name);
+        match self.xml(&name)? {
+            Some(xml) => Ok(Some(parse_part(&name, xml)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the names of the footers, like `footer1.xml`.
+    pub fn footer_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.word_names("footer")
+    }
+
+    pub fn footer(&self, name: &str) -> DocxResult<Option<Footer<'_>>> {
+        let name = format!("word/{}", name);
+        match self.xml(&name)? {
+            Some(xml) => Ok(Some(parse_part(&name, xml)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the names of the themes, like `theme/theme1.xml`.
+    pub fn theme_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.word_names("theme/theme")
+    }
+
+    pub fn theme(&self, name: &str) -> DocxResult<Option<Theme<'_>>> {
+        let name = format!("word/{}", name);
+        match self.xml(&name)? {
+            Some(xml) => Ok(Some(parse_part(&name, xml)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the names of the media files, like `media/image1.png`.
+    pub fn media_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.media.iter().map(|n| &n["word/".len()..])
+    }
+
+    /// Reads a media file, which isn't kept in memory afterwards.
+    pub fn media_bytes(&self, name: &str) -> DocxResult<Option<Vec<u8>>> {
+        let mut zip = self.zip.borrow_mut();
+        let mut file = match zip.by_name(&format!("word/{}", name)) {
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(e.into()),
+            Ok(file) => file,
+        };
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        Ok(Some(buffer))
+    }
+
+    /// Names in `word/` starting with `prefix`, in the order of their numbers.
+    fn word_names<'s>(&'s self, prefix: &'s str) -> impl Iterator<Item = &'s str> + 's {
+        let mut names: Vec<&str> = self
+            .parts
+            .keys()
+            .filter_map(|n| n.strip_prefix("word/"))
+            .filter(|n| n.starts_with(prefix) && n.ends_with(".xml"))
+            .collect();
+        names.sort_by_key(|n| (n.len(), *n));
+        names.into_iter()
+    }
+}
+
+impl LazyDocxFile<File> {
+    /// Opens a file without reading any of its parts.
+    #[inline]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> DocxResult<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+}
@@ -80,18 +80,46 @@
 mod macros;
 
 pub mod app;
+mod base64;
+pub mod bookmark;
+pub mod content_control;
 pub mod content_type;
 pub mod core;
+pub mod custom_properties;
+pub mod custom_xml;
 pub mod document;
 mod docx;
+#[cfg(feature = "encryption")]
+pub mod encryption;
 mod error;
+pub mod field;
 pub mod font_table;
+pub mod form;
 pub mod formatting;
+pub mod html;
+mod import;
+pub mod lazy;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 pub mod media;
+pub mod page_setup;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod parse;
+pub mod protection;
+pub mod reader;
 pub mod rels;
+pub mod repair;
+pub mod sanitize;
 mod schema;
+pub mod section;
 pub mod settings;
+pub mod statistics;
+pub mod stream;
 pub mod styles;
+pub mod text;
+pub mod toc;
+pub mod validate;
 pub mod web_settings;
 
 use std::io::Write;
@@ -99,7 +127,9 @@ use std::io::Write;
 use hard_xml::{XmlWrite, XmlWriter};
 
 pub use crate::docx::{Docx, DocxFile};
-pub use crate::error::{DocxError, DocxResult};
+pub use crate::error::{DocxError, DocxResult, ParseError};
+pub use crate::lazy::LazyDocxFile;
+pub use crate::stream::DocxStreamWriter;
 
 pub fn write_attr<W: Write, T: XmlWrite>(
     element: &Option<T>,
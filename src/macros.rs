@@ -66,8 +66,7 @@ macro_rules! __define_struct {
     };
 
     ( ($tag:expr, $name:ident) { $($value:expr, $variant:ident, $ty: ty)* }) => {
-        #[derive(Debug, XmlRead, XmlWrite, Clone, Default)]
-        #[cfg_attr(test, derive(PartialEq))]
+        #[derive(Debug, XmlRead, XmlWrite, Clone, Default, PartialEq)]
         #[xml(tag = $tag)]
         pub struct $name {
             $(
@@ -126,8 +125,7 @@ macro_rules! __define_struct {
 #[doc(hidden)]
 macro_rules! __define_enum {
     ($name:ident { $($variant:ident = $value:expr, )* }) => {
-        #[derive(Debug, Clone)]
-        #[cfg_attr(test, derive(PartialEq))]
+        #[derive(Debug, Clone, PartialEq)]
         pub enum $name {
             $( $variant, )*
         }
@@ -189,6 +187,13 @@ macro_rules! __string_enum {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __setter {
+    ($field:ident: Option<$ty:ty> as $unit:ty) => {
+        #[inline(always)]
+        pub fn $field<T: Into<$unit>>(mut self, value: T) -> Self {
+            self.$field = Some(value.into().into());
+            self
+        }
+    };
     ($field:ident: Option<$ty:ty>) => {
         #[inline(always)]
         pub fn $field<T: Into<$ty>>(mut self, value: T) -> Self {
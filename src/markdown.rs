@@ -0,0 +1,505 @@
+//! Markdown import
+//!
+//! Requires the `markdown` feature. [`Docx::from_markdown`] converts CommonMark,
+//! with GFM tables, strikethrough and task lists, into a new document.
+//!
+//! ```no_run
+//! use docx_rust::{markdown::MarkdownOptions, Docx};
+//!
+//! let input = std::fs::read_to_string("README.md").unwrap();
+//! let mut docx = Docx::from_markdown(&input, &MarkdownOptions::default().base_dir("."));
+//! docx.write_file("README.docx").unwrap();
+//! ```
+//!
+//! [`Docx::from_markdown`]: ../struct.Docx.html#method.from_markdown
+
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, Break, Drawing, Hyperlink, Paragraph, Run, Table, TableCell, TableRow,
+        TextSpace,
+    },
+    formatting::{
+        CharacterProperty, Indent, JustificationVal, NumberingProperty, OnOffOnlyType,
+        ParagraphProperty, TableRowProperty,
+    },
+    import, Docx,
+};
+
+/// Options of the Markdown import
+///
+/// ```rust
+/// use docx_rust::markdown::MarkdownOptions;
+///
+/// let options = MarkdownOptions::default()
+///     .base_dir("docs")
+///     .code_font("Courier New");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownOptions {
+    /// Directory relative image paths are resolved against,
+    /// defaults to the current directory.
+    pub base_dir: Option<PathBuf>,
+    /// Font of code blocks and inline code.
+    pub code_font: String,
+    /// Images wider than this, in EMUs, are scaled down. Defaults to six inches.
+    pub max_image_width: u64,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        MarkdownOptions {
+            base_dir: None,
+            code_font: "Consolas".to_string(),
+            max_image_width: 5486400,
+        }
+    }
+}
+
+impl MarkdownOptions {
+    __setter!(base_dir: Option<PathBuf>);
+    __setter!(code_font: String);
+    __setter!(max_image_width: u64);
+}
+
+impl Docx<'static> {
+    /// Creates a document from Markdown.
+    ///
+    /// Headings, code, block quotes and links use the `Heading1` to `Heading6`,
+    /// `SourceCode`, `VerbatimChar`, `Quote` and `Hyperlink` styles, which are
+    /// added to the document as needed. Images that cannot be read are replaced
+    /// by their alternative text.
+    pub fn from_markdown(input: &str, options: &MarkdownOptions) -> Docx<'static> {
+        let parser = Parser::new_ext(
+            input,
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+        );
+
+        let mut importer = MarkdownImporter::new(options);
+        for event in parser {
+            importer.event(event);
+        }
+        importer.finish_paragraph();
+        importer.docx
+    }
+}
+
+struct ListState {
+    num_id: isize,
+    /// The next paragraph is the first one of an item and gets the number
+    item_started: bool,
+}
+
+#[derive(Default)]
+struct TableState {
+    alignments: Vec<Alignment>,
+    rows: Vec<TableRow<'static>>,
+    cells: Vec<TableCell<'static>>,
+    in_head: bool,
+}
+
+struct LinkState {
+    id: Option<String>,
+    anchor: Option<String>,
+    text: String,
+    property: Option<CharacterProperty<'static>>,
+}
+
+struct MarkdownImporter<'o> {
+    options: &'o MarkdownOptions,
+    docx: Docx<'static>,
+    paragraph: Option<Paragraph<'static>>,
+    heading: Option<usize>,
+    code_block: bool,
+    quote_depth: usize,
+    lists: Vec<ListState>,
+    table: Option<TableState>,
+    link: Option<LinkState>,
+    /// Destination and alternative text of the image being read
+    image: Option<(String, String)>,
+    bold: usize,
+    italics: usize,
+    strike: usize,
+}
+
+impl<'o> MarkdownImporter<'o> {
+    fn new(options: &'o MarkdownOptions) -> Self {
+        MarkdownImporter {
+            options,
+            docx: Docx::default(),
+            paragraph: None,
+            heading: None,
+            code_block: false,
+            quote_depth: 0,
+            lists: Vec::new(),
+            table: None,
+            link: None,
+            image: None,
+            bold: 0,
+            italics: 0,
+            strike: 0,
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some((_, alt)) = &mut self.image {
+                    alt.push_str(&text);
+                } else if self.code_block {
+                    let text = text.strip_suffix('\n').unwrap_or(&text);
+                    for (i, line) in text.split('\n').enumerate() {
+                        if i > 0 {
+                            self.push_run(Run::default().push_break(Break { ty: None }));
+                        }
+                        self.push_text(line, None);
+                    }
+                } else {
+                    self.push_text(&text, None);
+                }
+            }
+            Event::Code(code) => {
+                self.ensure_style("VerbatimChar");
+                self.push_text(&code, Some("VerbatimChar"));
+            }
+            Event::SoftBreak => self.push_text(" ", None),
+            Event::HardBreak => self.push_run(Run::default().push_break(Break { ty: None })),
+            Event::TaskListMarker(checked) => {
+                self.push_text(if checked { "☒ " } else { "☐ " }, None)
+            }
+            Event::Rule => {
+                self.finish_paragraph();
+                self.paragraph = Some(import::horizontal_rule());
+                self.finish_paragraph();
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                log::debug!("[Markdown] Skipped raw HTML {:?}.", html);
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.finish_paragraph(),
+            Tag::Heading { level, .. } => {
+                self.finish_paragraph();
+                self.heading = Some(heading_level(level));
+            }
+            Tag::CodeBlock(kind) => {
+                self.finish_paragraph();
+                self.code_block = true;
+                if let CodeBlockKind::Fenced(lang) = kind {
+                    log::debug!("[Markdown] Code block in {:?}.", lang);
+                }
+            }
+            Tag::BlockQuote(_) => {
+                self.finish_paragraph();
+                self.quote_depth += 1;
+            }
+            Tag::List(start) => {
+                self.finish_paragraph();
+                let numbering = self.docx.numbering.get_or_insert_with(Default::default);
+                let num_id = numbering.add_list(start.is_some());
+                if let Some(start) = start.filter(|s| *s != 1) {
+                    import::set_list_start(
+                        numbering,
+                        num_id,
+                        self.lists.len() as isize,
+                        start as isize,
+                    );
+                }
+                self.lists.push(ListState {
+                    num_id,
+                    item_started: false,
+                });
+            }
+            Tag::Item => {
+                self.finish_paragraph();
+                if let Some(list) = self.lists.last_mut() {
+                    list.item_started = true;
+                }
+            }
+            Tag::Table(alignments) => {
+                self.finish_paragraph();
+                self.table = Some(TableState {
+                    alignments,
+                    ..Default::default()
+                });
+            }
+            Tag::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.in_head = true;
+                }
+            }
+            Tag::TableCell => self.finish_paragraph(),
+            Tag::Emphasis => self.italics += 1,
+            Tag::Strong => self.bold += 1,
+            Tag::Strikethrough => self.strike += 1,
+            Tag::Link { dest_url, .. } => {
+                let (id, anchor) = match dest_url.strip_prefix('#') {
+                    Some(anchor) => (None, Some(anchor.to_string())),
+                    None => (Some(self.docx.add_hyperlink(dest_url.to_string())), None),
+                };
+                self.ensure_style("Hyperlink");
+                self.link = Some(LinkState {
+                    id,
+                    anchor,
+                    text: String::new(),
+                    property: self.character_property(Some("Hyperlink")),
+                });
+            }
+            Tag::Image { dest_url, .. } => {
+                self.image = Some((dest_url.to_string(), String::new()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.finish_paragraph(),
+            TagEnd::Heading(_) => {
+                self.finish_paragraph();
+                self.heading = None;
+            }
+            TagEnd::CodeBlock => {
+                // keeps empty code blocks
+                self.ensure_paragraph();
+                self.finish_paragraph();
+                self.code_block = false;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.finish_paragraph();
+                self.quote_depth -= 1;
+            }
+            TagEnd::List(_) => {
+                self.finish_paragraph();
+                self.lists.pop();
+            }
+            TagEnd::Item => self.finish_paragraph(),
+            TagEnd::TableHead | TagEnd::TableRow => {
+                if let Some(table) = &mut self.table {
+                    let mut row = TableRow::default();
+                    for cell in table.cells.drain(..) {
+                        row = row.push_cell(cell);
+                    }
+                    if table.in_head {
+                        row = row
+                            .property(TableRowProperty::default().table_header(OnOffOnlyType::On));
+                        table.in_head = false;
+                    }
+                    table.rows.push(row);
+                }
+            }
+            TagEnd::TableCell => {
+                let paragraph = self.paragraph.take().unwrap_or_default();
+                if let Some(table) = &mut self.table {
+                    table.cells.push(TableCell::paragraph(paragraph));
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.push_block(build_table(table));
+                }
+            }
+            TagEnd::Emphasis => self.italics -= 1,
+            TagEnd::Strong => self.bold -= 1,
+            TagEnd::Strikethrough => self.strike -= 1,
+            TagEnd::Link => {
+                if let Some(link) = self.link.take() {
+                    let run = Run {
+                        property: link.property,
+                        ..Default::default()
+                    }
+                    .push_text((link.text, TextSpace::Preserve));
+                    let hyperlink = Hyperlink {
+                        id: link.id.map(Into::into),
+                        anchor: link.anchor.map(Into::into),
+                        content: Some(run),
+                        bidirectional_embedding: None,
+                    };
+                    self.ensure_paragraph();
+                    if let Some(paragraph) = self.paragraph.take() {
+                        self.paragraph = Some(paragraph.push(hyperlink));
+                    }
+                }
+            }
+            TagEnd::Image => {
+                if let Some((dest, alt)) = self.image.take() {
+                    match self.load_image(&dest, &alt) {
+                        Some(drawing) => self.push_run(Run::default().push(drawing)),
+                        None => {
+                            log::warn!("[Markdown] Could not load image {:?}.", dest);
+                            self.push_text(&alt, None);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn push_text(&mut self, text: &str, style: Option<&'static str>) {
+        if let Some(link) = &mut self.link {
+            link.text.push_str(text);
+            return;
+        }
+        let run = Run {
+            property: self.character_property(style),
+            ..Default::default()
+        };
+        self.push_run(run.push_text((text.to_string(), TextSpace::Preserve)));
+    }
+
+    fn push_run(&mut self, run: Run<'static>) {
+        self.ensure_paragraph();
+        if let Some(paragraph) = self.paragraph.take() {
+            self.paragraph = Some(paragraph.push(run));
+        }
+    }
+
+    fn character_property(
+        &self,
+        style: Option<&'static str>,
+    ) -> Option<CharacterProperty<'static>> {
+        let in_head = self.table.as_ref().is_some_and(|t| t.in_head);
+        if style.is_none() && self.bold == 0 && self.italics == 0 && self.strike == 0 && !in_head {
+            return None;
+        }
+
+        let mut property = CharacterProperty::default();
+        if let Some(style) = style {
+            property = property.style_id(style);
+        }
+        if self.bold > 0 || in_head {
+            property = property.bold(true);
+        }
+        if self.italics > 0 {
+            property = property.italics(true);
+        }
+        if self.strike > 0 {
+            property = property.strike(true);
+        }
+        Some(property)
+    }
+
+    /// Starts a paragraph formatted after the surrounding blocks, if none is open.
+    fn ensure_paragraph(&mut self) {
+        if self.paragraph.is_some() {
+            return;
+        }
+
+        let mut property = ParagraphProperty::default();
+        if let Some(level) = self.heading {
+            let style = format!("Heading{}", level);
+            self.ensure_style(&style);
+            property = property.style_id(style);
+        } else if self.code_block {
+            self.ensure_style("SourceCode");
+            property = property.style_id("SourceCode");
+        } else if self.quote_depth > 0 && self.lists.is_empty() {
+            self.ensure_style("Quote");
+            property = property.style_id("Quote");
+        }
+
+        if let Some(table) = &self.table {
+            let alignment = table.alignments.get(table.cells.len());
+            let justification = match alignment {
+                Some(Alignment::Center) => Some(JustificationVal::Center),
+                Some(Alignment::Right) => Some(JustificationVal::Right),
+                Some(Alignment::Left) => Some(JustificationVal::Left),
+                _ => None,
+            };
+            if let Some(justification) = justification {
+                property = property.justification(justification);
+            }
+        } else if !self.lists.is_empty() {
+            let level = self.lists.len() as isize - 1;
+            let list = self.lists.last_mut().unwrap();
+            if list.item_started {
+                list.item_started = false;
+                property = property.numbering(NumberingProperty::from((list.num_id, level)));
+            } else {
+                // continuation paragraphs line up with the text of the item
+                property = property.indent(Indent {
+                    left: Some(720 * (level + 1) + 720 * self.quote_depth as isize),
+                    ..Default::default()
+                });
+            }
+        } else if self.quote_depth > 1 {
+            property = property.indent(Indent {
+                left: Some(720 * self.quote_depth as isize),
+                ..Default::default()
+            });
+        }
+
+        self.paragraph = Some(Paragraph::default().property(property));
+    }
+
+    fn finish_paragraph(&mut self) {
+        if self.table.is_some() {
+            // cells take their paragraph when they end
+            return;
+        }
+        if let Some(paragraph) = self.paragraph.take() {
+            self.push_block(paragraph);
+        }
+    }
+
+    fn push_block<T: Into<BodyContent<'static>>>(&mut self, content: T) {
+        self.docx.document.push(content);
+    }
+
+    fn load_image(&mut self, dest: &str, alt: &str) -> Option<Drawing<'static>> {
+        if dest.contains("://") {
+            return None;
+        }
+        let path = match &self.options.base_dir {
+            Some(base) => base.join(dest),
+            None => Path::new(dest).to_path_buf(),
+        };
+        let bytes = std::fs::read(&path).ok()?;
+        let file_name = path.file_name()?.to_str()?;
+        import::picture(
+            &mut self.docx,
+            file_name,
+            bytes,
+            alt,
+            None,
+            self.options.max_image_width,
+        )
+    }
+
+    fn ensure_style(&mut self, style_id: &str) {
+        import::ensure_style(&mut self.docx, style_id, &self.options.code_font);
+    }
+}
+
+fn heading_level(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn build_table(state: TableState) -> Table<'static> {
+    let mut table = Table {
+        grids: import::table_grid(state.alignments.len()),
+        ..Default::default()
+    }
+    .property(import::table_property());
+    for row in state.rows {
+        table = table.push_row(row);
+    }
+    table
+}
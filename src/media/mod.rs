@@ -19,9 +19,72 @@ pub fn get_media_type(filename: &str) -> Option<MediaType> {
         | filename.ends_with("jpg")
         | filename.ends_with("jpeg")
         | filename.ends_with("bmp")
+        | filename.ends_with("gif")
     {
         Some(MediaType::Image)
     } else {
         None
     }
 }
+
+/// Returns the MIME type used in `[Content_Types].xml` for a media file name.
+pub fn get_media_content_type(filename: &str) -> Option<&'static str> {
+    let ext = filename.rsplit('.').next()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "bmp" => Some("image/bmp"),
+        _ => None,
+    }
+}
+
+/// Reads the pixel dimensions from the header of a PNG, JPEG, GIF or BMP image.
+pub fn get_image_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| Some(u16::from_be_bytes([*bytes.get(i)?, *bytes.get(i + 1)?]) as u32);
+    let le16 = |i: usize| Some(u16::from_le_bytes([*bytes.get(i)?, *bytes.get(i + 1)?]) as u32);
+    let be32 = |i: usize| Some(u32::from_be_bytes(bytes.get(i..i + 4)?.try_into().ok()?));
+    let le32 = |i: usize| Some(i32::from_le_bytes(bytes.get(i..i + 4)?.try_into().ok()?));
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // IHDR is always the first chunk
+        Some((be32(16)?, be32(20)?))
+    } else if bytes.starts_with(b"GIF8") {
+        Some((le16(6)?, le16(8)?))
+    } else if bytes.starts_with(b"BM") {
+        Some((le32(18)?.unsigned_abs(), le32(22)?.unsigned_abs()))
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        let mut i = 2;
+        while i + 9 < bytes.len() {
+            if bytes[i] != 0xFF {
+                return None;
+            }
+            let marker = bytes[i + 1];
+            // SOF0..SOF15, except DHT, JPG and DAC
+            if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+                return Some((be16(i + 7)?, be16(i + 5)?));
+            }
+            i += 2 + be16(i + 2)? as usize;
+        }
+        None
+    } else {
+        None
+    }
+}
+
+#[test]
+fn get_image_size_test() {
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend_from_slice(&[0, 0, 1, 0, 0, 0, 0, 32]);
+    assert_eq!(get_image_size(&png), Some((256, 32)));
+
+    assert_eq!(get_image_size(b"GIF89a\x0a\x00\x14\x00"), Some((10, 20)));
+
+    let jpeg = [
+        0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x30,
+        0x00, 0x40, 0x03,
+    ];
+    assert_eq!(get_image_size(&jpeg), Some((64, 48)));
+
+    assert_eq!(get_image_size(b"not an image"), None);
+}
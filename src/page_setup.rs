@@ -0,0 +1,296 @@
+//! Page setup
+//!
+//! [`PageSetup`] gathers the size, orientation, margins and decorations of the pages of
+//! a section, in the units of your choice. [`Docx::set_page_setup`] applies it to a
+//! section, and [`Docx::page_setup`] reads back the page setup in effect.
+//!
+//! ```rust
+//! use docx_rust::{formatting::*, page_setup::*, Docx};
+//!
+//! let mut docx = Docx::default();
+//! let setup = PageSetup::new(Paper::A4)
+//!     .landscape()
+//!     .margins(Cm(2.0))
+//!     .gutter(Cm(1.0))
+//!     .mirror_margins(true)
+//!     .v_align(VAlignType::Center);
+//! assert!(docx.set_page_setup(0, &setup));
+//!
+//! let setup = docx.page_setup(0).unwrap();
+//! assert_eq!(setup.paper(), Some(Paper::A4));
+//! assert_eq!(setup.orientation, PageOrientation::Landscape);
+//! assert_eq!(setup.margin.gutter, Some(567));
+//! ```
+//!
+//! [`Docx::set_page_setup`]: ../struct.Docx.html#method.set_page_setup
+//! [`Docx::page_setup`]: ../struct.Docx.html#method.page_setup
+
+use crate::{
+    __setter,
+    formatting::{
+        Cm, Inch, PageGrid, PageMargin, PageOrientation, PageSize, PgBorders, PgLnNumType,
+        SectionProperty, Twips, VAlign, VAlignType,
+    },
+    settings::MirrorMargins,
+    Docx,
+};
+
+/// Paper sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paper {
+    A3,
+    A4,
+    A5,
+    /// ISO B5, 176 × 250 mm
+    B5,
+    Letter,
+    Legal,
+    Tabloid,
+}
+
+impl Paper {
+    const ALL: [Paper; 7] = [
+        Paper::A3,
+        Paper::A4,
+        Paper::A5,
+        Paper::B5,
+        Paper::Letter,
+        Paper::Legal,
+        Paper::Tabloid,
+    ];
+
+    /// Returns the width and height of the paper in portrait.
+    pub fn size(self) -> (Twips, Twips) {
+        let (width, height) = match self {
+            Paper::A3 => (Cm(29.7).into(), Cm(42.0).into()),
+            Paper::A4 => (Cm(21.0).into(), Cm(29.7).into()),
+            Paper::A5 => (Cm(14.8).into(), Cm(21.0).into()),
+            Paper::B5 => (Cm(17.6).into(), Cm(25.0).into()),
+            Paper::Letter => (Inch(8.5).into(), Inch(11.0).into()),
+            Paper::Legal => (Inch(8.5).into(), Inch(14.0).into()),
+            Paper::Tabloid => (Inch(11.0).into(), Inch(17.0).into()),
+        };
+        (width, height)
+    }
+
+    /// Returns the paper of this size, in either orientation.
+    pub fn from_size(width: Twips, height: Twips) -> Option<Paper> {
+        // sizes written by other applications may be rounded differently
+        const TOLERANCE: isize = 20;
+        let (short, long) = (width.min(height), width.max(height));
+        Paper::ALL.into_iter().find(|paper| {
+            let (w, h) = paper.size();
+            (w.0 - short.0).abs() <= TOLERANCE && (h.0 - long.0).abs() <= TOLERANCE
+        })
+    }
+}
+
+/// Page setup of a section
+///
+/// Starts from a portrait page with margins of one inch, and headers and footers half
+/// an inch from the edges, which is what Word uses when a section doesn't say.
+#[derive(Debug, Clone)]
+pub struct PageSetup {
+    /// Width of the pages, as laid out
+    pub width: Twips,
+    /// Height of the pages, as laid out
+    pub height: Twips,
+    pub orientation: PageOrientation,
+    pub margin: PageMargin,
+    /// Whether the inner and outer margins of facing pages mirror each other
+    ///
+    /// This is a setting of the whole document.
+    pub mirror_margins: bool,
+    pub borders: Option<PgBorders>,
+    pub line_numbering: Option<PgLnNumType>,
+    pub v_align: Option<VAlignType>,
+    pub grid: Option<PageGrid>,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        PageSetup::new(Paper::Letter)
+    }
+}
+
+impl PageSetup {
+    pub fn new(paper: Paper) -> Self {
+        let (width, height) = paper.size();
+        PageSetup {
+            width,
+            height,
+            orientation: PageOrientation::Portrait,
+            margin: PageMargin::default()
+                .top(Inch(1.0))
+                .right(Inch(1.0))
+                .bottom(Inch(1.0))
+                .left(Inch(1.0))
+                .header(Inch(0.5))
+                .footer(Inch(0.5))
+                .gutter(0isize),
+            mirror_margins: false,
+            borders: None,
+            line_numbering: None,
+            v_align: None,
+            grid: None,
+        }
+    }
+
+    /// Turns the pages, so that they are wider than high in landscape.
+    pub fn orientation(mut self, orientation: PageOrientation) -> Self {
+        let landscape = self.width > self.height;
+        if landscape != (orientation == PageOrientation::Landscape) {
+            std::mem::swap(&mut self.width, &mut self.height);
+        }
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn landscape(self) -> Self {
+        self.orientation(PageOrientation::Landscape)
+    }
+
+    pub fn portrait(self) -> Self {
+        self.orientation(PageOrientation::Portrait)
+    }
+
+    /// Sets the top, right, bottom and left margins.
+    pub fn margins<T: Into<Twips>>(mut self, margin: T) -> Self {
+        let margin = margin.into().0;
+        self.margin.top = Some(margin);
+        self.margin.right = Some(margin);
+        self.margin.bottom = Some(margin);
+        self.margin.left = Some(margin);
+        self
+    }
+
+    pub fn top<T: Into<Twips>>(mut self, margin: T) -> Self {
+        self.margin.top = Some(margin.into().0);
+        self
+    }
+
+    pub fn right<T: Into<Twips>>(mut self, margin: T) -> Self {
+        self.margin.right = Some(margin.into().0);
+        self
+    }
+
+    pub fn bottom<T: Into<Twips>>(mut self, margin: T) -> Self {
+        self.margin.bottom = Some(margin.into().0);
+        self
+    }
+
+    pub fn left<T: Into<Twips>>(mut self, margin: T) -> Self {
+        self.margin.left = Some(margin.into().0);
+        self
+    }
+
+    /// Sets the extra space left for binding, besides the left margin.
+    pub fn gutter<T: Into<Twips>>(mut self, gutter: T) -> Self {
+        self.margin.gutter = Some(gutter.into().0);
+        self
+    }
+
+    /// Sets the distance from the top edge of the page to the header.
+    pub fn header_distance<T: Into<Twips>>(mut self, distance: T) -> Self {
+        self.margin.header = Some(distance.into().0);
+        self
+    }
+
+    /// Sets the distance from the bottom edge of the page to the footer.
+    pub fn footer_distance<T: Into<Twips>>(mut self, distance: T) -> Self {
+        self.margin.footer = Some(distance.into().0);
+        self
+    }
+
+    __setter!(mirror_margins: bool);
+    __setter!(borders: Option<PgBorders>);
+    __setter!(line_numbering: Option<PgLnNumType>);
+    __setter!(v_align: Option<VAlignType>);
+    __setter!(grid: Option<PageGrid>);
+
+    /// Returns the paper the pages are, if a known one.
+    pub fn paper(&self) -> Option<Paper> {
+        Paper::from_size(self.width, self.height)
+    }
+
+    /// Reads the page setup of a section, filling in what it leaves out.
+    fn read(property: &SectionProperty, mirror_margins: bool) -> Self {
+        let mut setup = PageSetup::default();
+        if let Some(size) = &property.page_size {
+            setup.width = Twips(size.weight);
+            setup.height = Twips(size.height);
+            setup.orientation = size.orient.unwrap_or(if size.weight > size.height {
+                PageOrientation::Landscape
+            } else {
+                PageOrientation::Portrait
+            });
+        }
+        if let Some(margin) = &property.page_margin {
+            let defaults = setup.margin;
+            setup.margin = PageMargin {
+                top: margin.top.or(defaults.top),
+                right: margin.right.or(defaults.right),
+                bottom: margin.bottom.or(defaults.bottom),
+                left: margin.left.or(defaults.left),
+                header: margin.header.or(defaults.header),
+                footer: margin.footer.or(defaults.footer),
+                gutter: margin.gutter.or(defaults.gutter),
+            };
+        }
+        setup.mirror_margins = mirror_margins;
+        setup.borders = property.page_borders.clone();
+        setup.line_numbering = property.line_numbering.clone();
+        setup.v_align = property.v_align.as_ref().map(|v| v.val.clone());
+        setup.grid = property.grid.clone();
+        setup
+    }
+
+    /// Writes the page setup to a section, leaving the borders, line numbering,
+    /// vertical alignment and grid of the section alone when it doesn't set them.
+    pub(crate) fn apply(&self, property: &mut SectionProperty) {
+        property.page_size = Some(PageSize::new(self.width, self.height).orient(self.orientation));
+        property.page_margin = Some(self.margin.clone());
+        if let Some(borders) = &self.borders {
+            property.page_borders = Some(borders.clone());
+        }
+        if let Some(line_numbering) = &self.line_numbering {
+            property.line_numbering = Some(line_numbering.clone());
+        }
+        if let Some(v_align) = &self.v_align {
+            property.v_align = Some(VAlign::from(v_align.clone()));
+        }
+        if let Some(grid) = &self.grid {
+            property.grid = Some(grid.clone());
+        }
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the page setup in effect in a section.
+    pub fn page_setup(&self, section: usize) -> Option<PageSetup> {
+        let mirror_margins = self
+            .settings
+            .as_ref()
+            .and_then(|s| s.mirror_margins.as_ref())
+            .is_some_and(|m| m.val != Some(false));
+        let section = self.sections().into_iter().nth(section)?;
+        Some(PageSetup::read(&section.property, mirror_margins))
+    }
+
+    /// Applies a page setup to a section, and returns `false` if there is no such
+    /// section.
+    ///
+    /// The borders, line numbering, vertical alignment and grid of the section stay
+    /// as they are unless the setup sets them. Mirrored margins apply to the whole
+    /// document.
+    pub fn set_page_setup(&mut self, section: usize, setup: &PageSetup) -> bool {
+        let Some(property) = self.section_mut(section) else {
+            return false;
+        };
+        setup.apply(property);
+        self.settings
+            .get_or_insert_with(Default::default)
+            .mirror_margins = setup.mirror_margins.then(MirrorMargins::default);
+        true
+    }
+}
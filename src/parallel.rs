@@ -0,0 +1,149 @@
+//! Parallel parsing of the main document part
+//!
+//! The top-level elements of `<w:body>` don't depend on each other, so they're
+//! located with a light scan of the markup and parsed on the thread pool.
+
+use hard_xml::XmlRead;
+use rayon::prelude::*;
+
+use crate::{
+    document::{Body, BodyContent, Document},
+    error::DocxResult,
+    parse::{next_markup, parse_part, Markup},
+};
+
+/// Children of `<w:body>` read by [`Body`], the others are skipped as well
+const BODY_CHILDREN: &[&str] = &[
+    "w:p",
+    "w:tbl",
+    "w:sectPr",
+    "w:sdt",
+    "w:bookmarkStart",
+    "w:bookmarkEnd",
+    "w:permStart",
+    "w:permEnd",
+];
+
+/// Parses the main document part, its body on the thread pool.
+///
+/// Falls back to a sequential parse when the markup isn't understood or an
+/// element fails, which locates the error.
+pub(crate) fn parse_document(xml: &str) -> DocxResult<Document<'_>> {
+    let Some(children) = body_children(xml) else {
+        return parse_part("word/document.xml", xml);
+    };
+    let content = children
+        .into_par_iter()
+        .filter(|(name, _)| BODY_CHILDREN.contains(name))
+        .map(|(_, xml)| BodyContent::from_str(xml))
+        .collect::<Result<Vec<_>, _>>();
+    match content {
+        Ok(content) => Ok(Document {
+            body: Body { content },
+        }),
+        Err(_) => parse_part("word/document.xml", xml),
+    }
+}
+
+/// Returns the names and markup of the children of `<w:body>`.
+fn body_children(xml: &str) -> Option<Vec<(&str, &str)>> {
+    let mut children = Vec::new();
+    let mut depth = 0usize;
+    let mut in_body = false;
+    let mut child = None;
+    let mut pos = 0;
+
+    while let Some(offset) = xml[pos..].find('<') {
+        let start = pos + offset;
+        let (markup, end) = next_markup(xml, start)?;
+        pos = end;
+        match markup {
+            Markup::Other => {}
+            Markup::Start(name) => {
+                if depth == 0 && name != "w:document" {
+                    return None;
+                } else if in_body && depth == 2 {
+                    child = Some((name, start));
+                } else if depth == 1 && name == "w:body" {
+                    in_body = true;
+                }
+                depth += 1;
+            }
+            Markup::Empty(name) => {
+                if in_body && depth == 2 {
+                    children.push((name, &xml[start..end]));
+                } else if depth == 1 && name == "w:body" {
+                    return Some(children);
+                }
+            }
+            Markup::End => {
+                depth = depth.checked_sub(1)?;
+                if in_body && depth == 2 {
+                    let (name, start) = child.take()?;
+                    children.push((name, &xml[start..end]));
+                } else if in_body && depth == 1 {
+                    return Some(children);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[test]
+fn parse_document_test() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<!-- <w:body><w:p/></w:body> -->
+<w:body>
+  <w:p><w:r><w:t xml:space="preserve">a > b</w:t></w:r></w:p>
+  <w:customXml w:element="skipped"><w:p/></w:customXml>
+  <w:tbl><w:tblGrid><w:gridCol w:w="2000"/></w:tblGrid><w:tr><w:tc><w:p><w:r><w:t>cell</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+  <w:bookmarkStart w:id="0" w:name="end"/>
+  <w:p w:rsidR='00AB1234'><![CDATA[<w:p>]]></w:p>
+  <w:sectPr><w:pgSz w:w="12240" w:h="15840"/></w:sectPr>
+</w:body>
+</w:document>"#;
+
+    let document = parse_document(xml).unwrap();
+    assert_eq!(document.body.content.len(), 5);
+    assert_eq!(document, Document::from_str(xml).unwrap());
+
+    let empty = r#"<w:document><w:body/></w:document>"#;
+    assert!(parse_document(empty).unwrap().body.content.is_empty());
+
+    assert!(parse_document("<w:document><w:body><w:p></w:body></w:document>").is_err());
+    assert!(parse_document("<w:document></w:document>").is_err());
+}
+
+#[test]
+fn fixtures_test() {
+    use std::io::Read;
+
+    for dir in ["tests/pandoc", "tests/aaa"] {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|ext| ext != "docx") {
+                continue;
+            }
+            let file = std::fs::File::open(&path).unwrap();
+            let mut zip = zip::ZipArchive::new(file).unwrap();
+            let mut xml = String::new();
+            zip.by_name("word/document.xml")
+                .unwrap()
+                .read_to_string(&mut xml)
+                .unwrap();
+
+            let sequential = parse_part::<Document>("word/document.xml", &xml);
+            match (parse_document(&xml), sequential) {
+                (Ok(parallel), Ok(sequential)) => {
+                    assert_eq!(parallel, sequential, "{}", path.display())
+                }
+                (Err(_), Err(_)) => {}
+                _ => panic!("{}: only one parse succeeded", path.display()),
+            }
+            assert!(body_children(&xml).is_some(), "{}", path.display());
+        }
+    }
+}
@@ -0,0 +1,505 @@
+//! Locating parse errors and recovering from them
+//!
+//! Errors of [`DocxFile::parse`](crate::DocxFile::parse) are reported as
+//! [`ParseError`]s, which name the part, the line and the path of the element
+//! that couldn't be read.
+//!
+//! [`DocxFile::parse_lenient`](crate::DocxFile::parse_lenient) recovers from
+//! them instead: the required children some producers leave out, like the
+//! `w:nsid` of abstract numberings, are added with default values, and other
+//! malformed elements are left out. Each recovery is reported as a
+//! [`ParseWarning`].
+//!
+//! ```no_run
+//! use docx_rust::DocxFile;
+//!
+//! let file = DocxFile::from_file("generated.docx").unwrap();
+//! let (docx, warnings) = file.parse_lenient().unwrap();
+//! for warning in &warnings {
+//!     eprintln!("{}", warning);
+//! }
+//! ```
+
+use hard_xml::{xmlparser::Token, XmlError, XmlRead, XmlReader};
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::error::{DocxError, DocxResult, ParseError};
+
+/// Children added when missing, by name of the element and of the field
+const DEFAULTS: &[(&str, &str, &str)] = &[
+    ("AbstractNum", "nsid", r#"<w:nsid w:val="00000000"/>"#),
+    (
+        "AbstractNum",
+        "multi_level_type",
+        r#"<w:multiLevelType w:val="hybridMultilevel"/>"#,
+    ),
+    ("WrapPolygon", "start", r#"<wp:start x="0" y="0"/>"#),
+];
+
+/// How [`DocxFile::parse_lenient`](crate::DocxFile::parse_lenient) recovered from an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recovery {
+    /// The missing child was added with a default value, like `<w:nsid w:val="00000000"/>`
+    Defaulted(&'static str),
+    /// The element was left out
+    Skipped,
+}
+
+/// An error recovered from by [`DocxFile::parse_lenient`](crate::DocxFile::parse_lenient)
+#[derive(Debug)]
+pub struct ParseWarning {
+    pub error: ParseError,
+    pub recovery: Recovery,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.recovery {
+            Recovery::Defaulted(child) => write!(f, "{}, added {}", self.error, child),
+            Recovery::Skipped => write!(f, "{}, element skipped", self.error),
+        }
+    }
+}
+
+/// Parses a part, locating the error if any.
+pub(crate) fn parse_part<'a, T: XmlRead<'a>>(part: &str, xml: &'a str) -> DocxResult<T> {
+    let mut reader = XmlReader::new(xml);
+    T::from_reader(&mut reader).map_err(|source| {
+        // where the reader stopped, usually right after the faulty markup
+        let stop = match &source {
+            XmlError::Parser(e) => offset_of(xml, e.pos().row, e.pos().col),
+            _ => match reader.peek() {
+                Some(Ok(token)) => token_start(token),
+                Some(Err(e)) => offset_of(xml, e.pos().row, e.pos().col),
+                None => xml.len(),
+            },
+        };
+        locate(part, xml, stop, source).into()
+    })
+}
+
+/// Parses an optional part, locating the error if any.
+pub(crate) fn parse_option<'a, T: XmlRead<'a>>(
+    part: &str,
+    xml: &'a Option<String>,
+) -> DocxResult<Option<T>> {
+    xml.as_deref().map(|xml| parse_part(part, xml)).transpose()
+}
+
+/// Fixes a copy of a part until `check` succeeds, adding default children or
+/// leaving out the elements that fail.
+///
+/// A missing child is added to all the elements that lack it at once, so the part
+/// is parsed again once per kind of missing child and per element left out, not
+/// once per error. The part is borrowed as is if it needs no fix.
+///
+/// Errors that can't be pinned on an element below the root are returned.
+pub(crate) fn recover_part<'a, F>(
+    part: &str,
+    xml: &'a str,
+    warnings: &mut Vec<ParseWarning>,
+    check: F,
+) -> DocxResult<Cow<'a, str>>
+where
+    F: Fn(&str, &str) -> DocxResult<()>,
+{
+    let mut xml = Cow::Borrowed(xml);
+    let mut defaulted = Vec::new();
+    loop {
+        let error = match check(part, &xml) {
+            Ok(()) => return Ok(xml),
+            Err(DocxError::Parse(error)) => *error,
+            Err(error) => return Err(error),
+        };
+        let Some(element) = error.element.clone() else {
+            return Err(error.into());
+        };
+        if error.path.len() < 2 {
+            return Err(error.into());
+        }
+
+        let default = match &error.source {
+            XmlError::MissingField { name, field } => {
+                DEFAULTS.iter().find(|(n, f, _)| n == name && f == field)
+            }
+            _ => None,
+        };
+        match default {
+            // the same error again means the default didn't help
+            Some(default) if !defaulted.contains(&default) => {
+                let tag = &error.path[error.path.len() - 1];
+                xml = Cow::Owned(add_children(part, &xml, tag, default, warnings));
+                defaulted.push(default);
+            }
+            _ => {
+                xml.to_mut().replace_range(element, "");
+                warnings.push(ParseWarning {
+                    error,
+                    recovery: Recovery::Skipped,
+                });
+            }
+        }
+    }
+}
+
+/// Inserts the default child at the start of the content of the elements named
+/// `name` which have no such child, in a single pass.
+fn add_children(
+    part: &str,
+    xml: &str,
+    name: &str,
+    &(ty, field, child): &(&str, &str, &'static str),
+    warnings: &mut Vec<ParseWarning>,
+) -> String {
+    let child_name = name_of(&child[1..]);
+
+    // open elements, with whether they are to be fixed and have the child
+    let mut open: Vec<(&str, usize, usize, bool)> = Vec::new();
+    let mut missing = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = xml[pos..].find('<') {
+        let start = pos + offset;
+        let Some((markup, end)) = next_markup(xml, start) else {
+            break;
+        };
+        pos = end;
+        let tag = match markup {
+            Markup::Start(tag) | Markup::Empty(tag) => tag,
+            Markup::End => {
+                if let Some((tag, start, content, false)) = open.pop() {
+                    if tag == name {
+                        missing.push((start, content, false, ancestors(&open)));
+                    }
+                }
+                continue;
+            }
+            Markup::Other => continue,
+        };
+        if tag == child_name {
+            if let Some(parent) = open.last_mut() {
+                parent.3 = true;
+            }
+        }
+        match markup {
+            Markup::Start(_) => open.push((tag, start, end, false)),
+            _ if tag == name => missing.push((start, end, true, ancestors(&open))),
+            _ => {}
+        }
+    }
+    missing.sort_unstable_by_key(|(start, ..)| *start);
+
+    let mut fixed = String::with_capacity(xml.len() + missing.len() * child.len());
+    let (mut copied, mut line, mut line_start) = (0, 1, 0);
+    for (start, end, empty, mut path) in missing {
+        let before = &xml[copied..start];
+        line += before.matches('\n').count();
+        if let Some(i) = before.rfind('\n') {
+            line_start = copied + i + 1;
+        }
+        path.push(name.to_string());
+        warnings.push(ParseWarning {
+            error: ParseError {
+                part: part.to_string(),
+                offset: start,
+                line,
+                column: xml[line_start..start].chars().count() + 1,
+                path,
+                source: XmlError::MissingField {
+                    name: ty.to_string(),
+                    field: field.to_string(),
+                },
+                element: None,
+            },
+            recovery: Recovery::Defaulted(child),
+        });
+
+        if empty {
+            let start_tag = xml[start..end - 2].trim_end();
+            fixed.push_str(&xml[copied..start]);
+            fixed.push_str(start_tag);
+            fixed.push('>');
+            fixed.push_str(child);
+            fixed.push_str("</");
+            fixed.push_str(name);
+            fixed.push('>');
+        } else {
+            fixed.push_str(&xml[copied..end]);
+            fixed.push_str(child);
+        }
+        copied = end;
+    }
+    fixed.push_str(&xml[copied..]);
+    fixed
+}
+
+fn ancestors(open: &[(&str, usize, usize, bool)]) -> Vec<String> {
+    open.iter().map(|(tag, ..)| tag.to_string()).collect()
+}
+
+/// Builds the error of the element around the offset where the reader stopped.
+fn locate(part: &str, xml: &str, stop: usize, source: XmlError) -> ParseError {
+    let mut open: Vec<(&str, usize)> = Vec::new();
+    let mut closed = None;
+    let mut pos = 0;
+    while let Some(offset) = xml[pos..].find('<') {
+        let start = pos + offset;
+        if start >= stop {
+            break;
+        }
+        let Some((markup, end)) = next_markup(xml, start) else {
+            break;
+        };
+        pos = end;
+        match markup {
+            Markup::Start(name) => {
+                open.push((name, start));
+                closed = None;
+            }
+            Markup::Empty(name) => closed = Some((name, start..end)),
+            Markup::End => closed = open.pop().map(|(name, start)| (name, start..end)),
+            Markup::Other => {}
+        }
+    }
+
+    let mut path: Vec<String> = open.iter().map(|(name, _)| name.to_string()).collect();
+    let (offset, element) = match (closed, open.last()) {
+        (Some((name, element)), _) => {
+            path.push(name.to_string());
+            (element.start, Some(element))
+        }
+        (None, Some(&(_, start))) => (start, element_end(xml, start).map(|end| start..end)),
+        (None, None) => (stop.min(xml.len()), None),
+    };
+
+    let before = &xml[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    ParseError {
+        part: part.to_string(),
+        offset,
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        path,
+        source,
+        element,
+    }
+}
+
+/// Returns the end of the element starting at `start`.
+fn element_end(xml: &str, start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut pos = start;
+    while let Some(offset) = xml[pos..].find('<') {
+        let (markup, end) = next_markup(xml, pos + offset)?;
+        pos = end;
+        match markup {
+            Markup::Start(_) => depth += 1,
+            Markup::Empty(_) if depth == 0 => return Some(end),
+            Markup::End => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(end);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Converts a position of xmlparser, counted in characters from 1, to an offset.
+fn offset_of(xml: &str, row: u32, col: u32) -> usize {
+    let line_start = match row {
+        0 | 1 => 0,
+        row => xml
+            .match_indices('\n')
+            .nth(row as usize - 2)
+            .map_or(xml.len(), |(i, _)| i + 1),
+    };
+    xml[line_start..]
+        .char_indices()
+        .nth(col.saturating_sub(1) as usize)
+        .map_or(xml.len(), |(i, _)| line_start + i)
+}
+
+fn token_start(token: &Token) -> usize {
+    match token {
+        Token::Declaration { span, .. }
+        | Token::ProcessingInstruction { span, .. }
+        | Token::Comment { span, .. }
+        | Token::DtdStart { span, .. }
+        | Token::EmptyDtd { span, .. }
+        | Token::EntityDeclaration { span, .. }
+        | Token::DtdEnd { span }
+        | Token::ElementStart { span, .. }
+        | Token::Attribute { span, .. }
+        | Token::ElementEnd { span, .. }
+        | Token::Cdata { span, .. } => span.start(),
+        Token::Text { text } => text.start(),
+    }
+}
+
+pub(crate) enum Markup<'a> {
+    Start(&'a str),
+    End,
+    Empty(&'a str),
+    /// Comments, processing instructions, CDATA sections and declarations
+    Other,
+}
+
+/// Reads the markup starting at `start`, returning it with the position after it.
+pub(crate) fn next_markup(xml: &str, start: usize) -> Option<(Markup<'_>, usize)> {
+    let rest = &xml[start..];
+    for (open, close) in [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>")] {
+        if rest.starts_with(open) {
+            let end = rest.find(close)? + close.len();
+            return Some((Markup::Other, start + end));
+        }
+    }
+
+    // attribute values may contain `>`
+    let mut quote = None;
+    let mut len = None;
+    for (i, b) in rest.bytes().enumerate().skip(1) {
+        match (quote, b) {
+            (Some(q), b) if b == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(b),
+            (None, b'>') => {
+                len = Some(i + 1);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let end = start + len?;
+    let tag = &xml[start + 1..end - 1];
+
+    if tag.starts_with('!') {
+        return Some((Markup::Other, end));
+    }
+    let markup = if tag.starts_with('/') {
+        Markup::End
+    } else if let Some(tag) = tag.strip_suffix('/') {
+        Markup::Empty(name_of(tag))
+    } else {
+        Markup::Start(name_of(tag))
+    };
+    Some((markup, end))
+}
+
+fn name_of(tag: &str) -> &str {
+    let len = tag
+        .find(|c: char| c.is_whitespace() || c == '/')
+        .unwrap_or(tag.len());
+    &tag[..len]
+}
+
+#[test]
+fn parse_part_test() {
+    use crate::document::Numbering;
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+  <w:abstractNum w:abstractNumId="0">
+    <w:multiLevelType w:val="hybridMultilevel"/>
+  </w:abstractNum>
+  <w:abstractNum w:abstractNumId="1"/>
+  <w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+  <w:num w:numId="2"><w:abstractNumId w:val="x"/></w:num>
+</w:numbering>"#;
+
+    let Err(DocxError::Parse(error)) = parse_part::<Numbering>("word/numbering.xml", xml) else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(error.path, ["w:numbering", "w:abstractNum"]);
+    assert_eq!((error.line, error.column), (3, 3));
+    assert_eq!(&xml[error.offset..error.offset + 14], "<w:abstractNum");
+    assert_eq!(
+        error.to_string(),
+        "word/numbering.xml at line 3, column 3, in w:numbering/w:abstractNum: \
+        missing field in XML of \"AbstractNum\": \"nsid\""
+    );
+
+    let mut warnings = Vec::new();
+    let fixed = recover_part("word/numbering.xml", xml, &mut warnings, |part, xml| {
+        parse_part::<Numbering>(part, xml).map(drop)
+    })
+    .unwrap();
+    let recoveries: Vec<_> = warnings.iter().map(|w| w.recovery.clone()).collect();
+    assert_eq!(
+        recoveries,
+        [
+            Recovery::Defaulted(DEFAULTS[0].2),
+            Recovery::Defaulted(DEFAULTS[0].2),
+            Recovery::Defaulted(DEFAULTS[1].2),
+            Recovery::Skipped,
+        ]
+    );
+    assert_eq!(
+        warnings[3].error.path,
+        ["w:numbering", "w:num", "w:abstractNumId"]
+    );
+    assert_eq!(warnings[3].error.line, 8);
+    assert_eq!(warnings[1].error.path, ["w:numbering", "w:abstractNum"]);
+    assert_eq!((warnings[1].error.line, warnings[1].error.column), (6, 3));
+
+    let numbering = Numbering::from_str(&fixed).unwrap();
+    assert_eq!(numbering.abstract_numberings.len(), 2);
+    assert_eq!(numbering.numberings.len(), 2);
+    assert!(numbering.numberings[1].abstract_num_id.is_none());
+
+    let xml = "<w:numbering>\n  <w:num w:numId=1/>\n</w:numbering>";
+    let Err(DocxError::Parse(error)) = parse_part::<Numbering>("word/numbering.xml", xml) else {
+        panic!("expected a parse error");
+    };
+    assert!(matches!(error.source, XmlError::Parser(_)));
+    assert_eq!(error.path, ["w:numbering", "w:num"]);
+    assert_eq!((error.line, error.column), (2, 3));
+
+    warnings.clear();
+    let fixed = recover_part("word/numbering.xml", xml, &mut warnings, |part, xml| {
+        parse_part::<Numbering>(part, xml).map(drop)
+    })
+    .unwrap();
+    assert_eq!(warnings[0].recovery, Recovery::Skipped);
+    assert_eq!(fixed, "<w:numbering>\n  \n</w:numbering>");
+
+    // the root can't be left out
+    assert!(recover_part(
+        "word/numbering.xml",
+        "<w:numbering><w:num>",
+        &mut warnings,
+        |part, xml| parse_part::<Numbering>(part, xml).map(drop)
+    )
+    .is_err());
+
+    // parts that parse are borrowed as is
+    let xml = "<w:numbering></w:numbering>";
+    let fixed = recover_part("word/numbering.xml", xml, &mut warnings, |part, xml| {
+        parse_part::<Numbering>(part, xml).map(drop)
+    });
+    assert!(matches!(fixed, Ok(Cow::Borrowed(_))));
+
+    // a missing child is added everywhere in a single pass
+    let xml = format!(
+        "<w:numbering>{}</w:numbering>",
+        r#"<w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="singleLevel"/></w:abstractNum>"#
+            .repeat(100)
+    );
+    let checks = std::cell::Cell::new(0);
+    warnings.clear();
+    recover_part("word/numbering.xml", &xml, &mut warnings, |part, xml| {
+        checks.set(checks.get() + 1);
+        parse_part::<Numbering>(part, xml).map(drop)
+    })
+    .unwrap();
+    assert_eq!(checks.get(), 2);
+    assert_eq!(warnings.len(), 100);
+    let last = xml.rfind("<w:abstractNum ").unwrap();
+    assert_eq!(warnings[99].error.offset, last);
+    assert_eq!(
+        (warnings[99].error.line, warnings[99].error.column),
+        (1, last + 1)
+    );
+}
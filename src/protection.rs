@@ -0,0 +1,507 @@
+//! Document protection
+//!
+//! [`Docx::protect`] restricts editing to reading, commenting, tracked changes or
+//! filling in forms, optionally behind a password. Passwords are hashed like Word
+//! does, with SHA-512, a random salt and 100 000 rounds, on top of the legacy Word
+//! hash of the password. Ranges added with [`Docx::add_editable_range`] stay editable
+//! for everyone, or for some users.
+//!
+//! ```rust
+//! use docx_rust::{document::*, protection::Editor, settings::DocumentProtectionType, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document
+//!     .push(Paragraph::default().push_text("Terms"))
+//!     .push(Paragraph::default().push_text("Your comments:"));
+//! docx.add_editable_range(1..2, EditorGroup::Everyone);
+//! docx.protect(DocumentProtectionType::ReadOnly, Some("secret"));
+//!
+//! assert!(docx.check_protection_password("secret"));
+//! assert!(!docx.check_protection_password("guess"));
+//! assert!(matches!(
+//!     docx.editable_ranges()[0].editor,
+//!     Editor::Group(EditorGroup::Everyone)
+//! ));
+//! ```
+//!
+//! [`Docx::protect`]: ../struct.Docx.html#method.protect
+//! [`Docx::add_editable_range`]: ../struct.Docx.html#method.add_editable_range
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::{
+    base64,
+    bookmark::{markers, wrap_blocks, Marker},
+    document::{EditorGroup, PermEnd, PermStart},
+    settings::{DocumentProtection, DocumentProtectionType, WriteProtection},
+    Docx,
+};
+
+/// Rounds of hashing Word uses
+const SPIN_COUNT: u32 = 100_000;
+
+const SALT_LENGTH: usize = 16;
+
+/// Longest password the legacy hash takes into account
+const MAX_PASSWORD_LENGTH: usize = 15;
+
+const INITIAL_CODES: [u16; MAX_PASSWORD_LENGTH] = [
+    0xE1F0, 0x1D0F, 0xCC9C, 0x84C0, 0x110C, 0x0E10, 0xF1CE, 0x313E, 0x1872, 0xE139, 0xD40F, 0x84F9,
+    0x280C, 0xA96A, 0x4EC3,
+];
+
+const ENCRYPTION_MATRIX: [[u16; 7]; MAX_PASSWORD_LENGTH] = [
+    [0xAEFC, 0x4DD9, 0x9BB2, 0x2745, 0x4E8A, 0x9D14, 0x2A09],
+    [0x7B61, 0xF6C2, 0xFDA5, 0xEB6B, 0xC6F7, 0x9DCF, 0x2BBF],
+    [0x4563, 0x8AC6, 0x05AD, 0x0B5A, 0x16B4, 0x2D68, 0x5AD0],
+    [0x0375, 0x06EA, 0x0DD4, 0x1BA8, 0x3750, 0x6EA0, 0xDD40],
+    [0xD849, 0xA0B3, 0x5147, 0xA28E, 0x553D, 0xAA7A, 0x44D5],
+    [0x6F45, 0xDE8A, 0xAD35, 0x4A4B, 0x9496, 0x390D, 0x721A],
+    [0xEB23, 0xC667, 0x9CEF, 0x29FF, 0x53FE, 0xA7FC, 0x5FD9],
+    [0x47D3, 0x8FA6, 0x0F6D, 0x1EDA, 0x3DB4, 0x7B68, 0xF6D0],
+    [0xB861, 0x60E3, 0xC1C6, 0x93AD, 0x377B, 0x6EF6, 0xDDEC],
+    [0x45A0, 0x8B40, 0x06A1, 0x0D42, 0x1A84, 0x3508, 0x6A10],
+    [0xAA51, 0x4483, 0x8906, 0x022D, 0x045A, 0x08B4, 0x1168],
+    [0x76B4, 0xED68, 0xCAF1, 0x85C3, 0x1BA7, 0x374E, 0x6E9C],
+    [0x3730, 0x6E60, 0xDCC0, 0xA9A1, 0x4363, 0x86C6, 0x1DAD],
+    [0x3331, 0x6662, 0xCCC4, 0x89A9, 0x0373, 0x06E6, 0x0DCC],
+    [0x1021, 0x2042, 0x4084, 0x0881, 0x1102, 0x2204, 0x4408],
+];
+
+/// Hash algorithms of protection passwords
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Returns the algorithm of a `w:cryptAlgorithmSid`.
+    pub fn from_sid(sid: u32) -> Option<Self> {
+        match sid {
+            4 => Some(HashAlgorithm::Sha1),
+            12 => Some(HashAlgorithm::Sha256),
+            13 => Some(HashAlgorithm::Sha384),
+            14 => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    pub fn sid(self) -> u32 {
+        match self {
+            HashAlgorithm::Sha1 => 4,
+            HashAlgorithm::Sha256 => 12,
+            HashAlgorithm::Sha384 => 13,
+            HashAlgorithm::Sha512 => 14,
+        }
+    }
+
+    /// Returns the algorithm of a `w:algorithmName`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "SHA-1" | "SHA1" => Some(HashAlgorithm::Sha1),
+            "SHA-256" | "SHA256" => Some(HashAlgorithm::Sha256),
+            "SHA-384" | "SHA384" => Some(HashAlgorithm::Sha384),
+            "SHA-512" | "SHA512" => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn digest(self, parts: &[&[u8]]) -> Vec<u8> {
+        fn digest<D: Digest>(parts: &[&[u8]]) -> Vec<u8> {
+            let mut hasher = D::new();
+            for part in parts {
+                hasher.update(part);
+            }
+            hasher.finalize().to_vec()
+        }
+
+        match self {
+            HashAlgorithm::Sha1 => digest::<Sha1>(parts),
+            HashAlgorithm::Sha256 => digest::<Sha256>(parts),
+            HashAlgorithm::Sha384 => digest::<Sha384>(parts),
+            HashAlgorithm::Sha512 => digest::<Sha512>(parts),
+        }
+    }
+}
+
+/// Returns the legacy Word hash of a password, which only looks at its first 15
+/// characters.
+pub fn legacy_hash(password: &str) -> u32 {
+    // one byte per character, its low byte unless it is zero
+    let bytes: Vec<u8> = password
+        .encode_utf16()
+        .take(MAX_PASSWORD_LENGTH)
+        .map(|c| match c.to_le_bytes() {
+            [0, high] => high,
+            [low, _] => low,
+        })
+        .collect();
+    if bytes.is_empty() {
+        return 0;
+    }
+
+    let mut high = INITIAL_CODES[bytes.len() - 1];
+    for (i, byte) in bytes.iter().enumerate() {
+        let row = &ENCRYPTION_MATRIX[MAX_PASSWORD_LENGTH - bytes.len() + i];
+        for (bit, code) in row.iter().enumerate() {
+            if byte & (1 << bit) != 0 {
+                high ^= code;
+            }
+        }
+    }
+
+    let rotate = |v: u16| ((v >> 14) & 1) | ((v << 1) & 0x7FFF);
+    let mut low = 0;
+    for byte in bytes.iter().rev() {
+        low = rotate(low) ^ *byte as u16;
+    }
+    low = rotate(low) ^ bytes.len() as u16 ^ 0xCE4B;
+
+    (high as u32) << 16 | low as u32
+}
+
+/// Hashes a password for document or write protection.
+///
+/// The password is first turned into its legacy hash, whose bytes are written in
+/// hexadecimal from the lowest, then hashed with the salt in front, then hashed
+/// again `spin_count` times with the round number after it.
+pub fn hash_password(
+    password: &str,
+    salt: &[u8],
+    spin_count: u32,
+    algorithm: HashAlgorithm,
+) -> Vec<u8> {
+    let key = legacy_hash(password).to_le_bytes();
+    let key = format!("{:02X}{:02X}{:02X}{:02X}", key[0], key[1], key[2], key[3]);
+    let key: Vec<u8> = key.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut hash = algorithm.digest(&[salt, &key]);
+    for round in 0..spin_count {
+        hash = algorithm.digest(&[&hash, &round.to_le_bytes()]);
+    }
+    hash
+}
+
+fn random_salt() -> [u8; SALT_LENGTH] {
+    let mut salt = [0; SALT_LENGTH];
+    getrandom::getrandom(&mut salt).expect("no random source available for the salt");
+    salt
+}
+
+macro_rules! __password {
+    ($name:ident) => {
+        impl $name {
+            /// Sets the password, hashed with SHA-512 and a random salt.
+            pub fn set_password(&mut self, password: &str) -> &mut Self {
+                self.set_password_with(password, &random_salt(), SPIN_COUNT, HashAlgorithm::Sha512)
+            }
+
+            /// Sets the password, hashed with this salt and algorithm.
+            pub fn set_password_with(
+                &mut self,
+                password: &str,
+                salt: &[u8],
+                spin_count: u32,
+                algorithm: HashAlgorithm,
+            ) -> &mut Self {
+                let hash = hash_password(password, salt, spin_count, algorithm);
+                self.clear_password();
+                let provider = match algorithm {
+                    HashAlgorithm::Sha1 => "rsaFull",
+                    _ => "rsaAES",
+                };
+                self.crypt_provider_type = Some(provider.to_string());
+                self.crypt_algorithm_class = Some("hash".to_string());
+                self.crypt_algorithm_type = Some("typeAny".to_string());
+                self.crypt_algorithm_sid = Some(algorithm.sid());
+                self.crypt_spin_count = Some(spin_count);
+                self.hash = Some(base64::encode(&hash));
+                self.salt = Some(base64::encode(salt));
+                self
+            }
+
+            pub fn clear_password(&mut self) -> &mut Self {
+                self.crypt_provider_type = None;
+                self.crypt_algorithm_class = None;
+                self.crypt_algorithm_type = None;
+                self.crypt_algorithm_sid = None;
+                self.crypt_spin_count = None;
+                self.hash = None;
+                self.salt = None;
+                self.algorithm_name = None;
+                self.hash_value = None;
+                self.salt_value = None;
+                self.spin_count = None;
+                self
+            }
+
+            pub fn has_password(&self) -> bool {
+                self.hash.is_some() || self.hash_value.is_some()
+            }
+
+            /// Checks a password against the stored hash, in any of the forms Word
+            /// writes. Any password fits when there is no hash.
+            pub fn check_password(&self, password: &str) -> bool {
+                if let Some(hash) = &self.hash_value {
+                    let algorithm = self
+                        .algorithm_name
+                        .as_deref()
+                        .and_then(HashAlgorithm::from_name);
+                    return check_hash(
+                        password,
+                        hash,
+                        self.salt_value.as_deref(),
+                        algorithm,
+                        self.spin_count,
+                    );
+                }
+                let Some(hash) = &self.hash else {
+                    return true;
+                };
+                if self.salt.is_none() {
+                    // Word 2003 kept the legacy hash alone
+                    let legacy = legacy_hash(password);
+                    return u32::from_str_radix(hash, 16) == Ok(legacy)
+                        || u32::from_str_radix(hash, 16) == Ok(legacy.swap_bytes());
+                }
+                let algorithm = self
+                    .crypt_algorithm_sid
+                    .map_or(Some(HashAlgorithm::Sha1), HashAlgorithm::from_sid);
+                check_hash(
+                    password,
+                    hash,
+                    self.salt.as_deref(),
+                    algorithm,
+                    self.crypt_spin_count,
+                )
+            }
+        }
+    };
+}
+
+__password!(DocumentProtection);
+__password!(WriteProtection);
+
+fn check_hash(
+    password: &str,
+    hash: &str,
+    salt: Option<&str>,
+    algorithm: Option<HashAlgorithm>,
+    spin_count: Option<u32>,
+) -> bool {
+    let (Some(algorithm), Some(hash), Some(salt)) = (
+        algorithm,
+        base64::decode(hash),
+        base64::decode(salt.unwrap_or_default()),
+    ) else {
+        return false;
+    };
+    hash_password(password, &salt, spin_count.unwrap_or(0), algorithm) == hash
+}
+
+/// Users allowed to edit a range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Editor {
+    Group(EditorGroup),
+    /// A single user, as an e-mail address or `DOMAIN\user`
+    User(String),
+}
+
+impl From<EditorGroup> for Editor {
+    fn from(group: EditorGroup) -> Self {
+        Editor::Group(group)
+    }
+}
+
+impl From<&str> for Editor {
+    fn from(user: &str) -> Self {
+        Editor::User(user.to_string())
+    }
+}
+
+impl From<String> for Editor {
+    fn from(user: String) -> Self {
+        Editor::User(user)
+    }
+}
+
+/// A range of the body that stays editable in a protected document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditableRange {
+    pub id: String,
+    pub editor: Editor,
+    /// Index of the block of [`Body::content`] the range starts in
+    ///
+    /// [`Body::content`]: ../document/struct.Body.html#structfield.content
+    pub start_block: usize,
+    /// Index of the block the range ends in, if it ends
+    pub end_block: Option<usize>,
+}
+
+impl<'a> Docx<'a> {
+    /// Restricts editing of the document, behind a password if there is one.
+    pub fn protect(&mut self, edit: DocumentProtectionType, password: Option<&str>) {
+        let mut protection = DocumentProtection {
+            edit: Some(edit),
+            enforcement: Some(true),
+            ..Default::default()
+        };
+        if let Some(password) = password {
+            protection.set_password(password);
+        }
+        self.settings
+            .get_or_insert_with(Default::default)
+            .document_protection = Some(protection);
+    }
+
+    /// Lifts the editing restrictions if the password fits, and returns whether it
+    /// did.
+    pub fn unprotect(&mut self, password: &str) -> bool {
+        if !self.check_protection_password(password) {
+            return false;
+        }
+        if let Some(settings) = &mut self.settings {
+            settings.document_protection = None;
+        }
+        true
+    }
+
+    /// Checks a password against the editing restrictions of the document.
+    pub fn check_protection_password(&self, password: &str) -> bool {
+        self.settings
+            .as_ref()
+            .and_then(|s| s.document_protection.as_ref())
+            .is_none_or(|p| p.check_password(password))
+    }
+
+    /// Asks for a password to save changes to the document, or only recommends
+    /// opening it read-only without a password.
+    pub fn protect_write(&mut self, password: Option<&str>, recommended: bool) {
+        let mut protection = WriteProtection {
+            recommended: recommended.then_some(true),
+            ..Default::default()
+        };
+        if let Some(password) = password {
+            protection.set_password(password);
+        }
+        self.settings
+            .get_or_insert_with(Default::default)
+            .write_protection = Some(protection);
+    }
+
+    /// Returns the editable ranges of the body, in the order they start.
+    pub fn editable_ranges(&self) -> Vec<EditableRange> {
+        let mut ranges: Vec<EditableRange> = Vec::new();
+        // indices in `ranges` by id
+        let mut open = HashMap::new();
+        for (i, block) in self.document.body.content.iter().enumerate() {
+            markers(block, &mut |marker| match marker {
+                Marker::PermStart(start) => {
+                    let editor = match (&start.editor, start.editor_group) {
+                        (Some(user), _) => Editor::User(user.to_string()),
+                        (None, Some(group)) => Editor::Group(group),
+                        (None, None) => return,
+                    };
+                    let id = start.id.as_deref().unwrap_or_default().to_string();
+                    open.insert(id.clone(), ranges.len());
+                    ranges.push(EditableRange {
+                        id,
+                        editor,
+                        start_block: i,
+                        end_block: None,
+                    });
+                }
+                Marker::PermEnd(end) => {
+                    if let Some(index) = end.id.as_ref().and_then(|id| open.remove(id.as_ref())) {
+                        ranges[index].end_block = Some(i);
+                    }
+                }
+                Marker::Start(_) | Marker::End(_) => {}
+            });
+        }
+        ranges
+    }
+
+    /// Keeps some blocks of the body editable for these users once the document is
+    /// protected, and returns the id of the range.
+    ///
+    /// Returns `None` if the blocks are out of the body.
+    pub fn add_editable_range<E: Into<Editor>>(
+        &mut self,
+        blocks: Range<usize>,
+        editor: E,
+    ) -> Option<String> {
+        if blocks.is_empty() || blocks.end > self.document.body.content.len() {
+            return None;
+        }
+        let mut next = 0;
+        for block in &self.document.body.content {
+            markers(block, &mut |marker| {
+                if let Marker::PermStart(start) = marker {
+                    if let Some(id) = start.id.as_ref().and_then(|id| id.parse::<usize>().ok()) {
+                        next = next.max(id + 1);
+                    }
+                }
+            });
+        }
+        let id = next.to_string();
+
+        let start = PermStart::default().id(id.clone());
+        let start = match editor.into() {
+            Editor::Group(group) => start.editor_group(group),
+            Editor::User(user) => start.editor(user),
+        };
+        let end = PermEnd::default().id(id.clone());
+        wrap_blocks(&mut self.document.body.content, blocks, start, end);
+        Some(id)
+    }
+}
+
+#[test]
+fn legacy_hash_test() {
+    // the low word is the sheet protection hash of Excel
+    assert_eq!(legacy_hash("password") & 0xFFFF, 0x83AF);
+    assert_eq!(legacy_hash(""), 0);
+    assert_eq!(
+        legacy_hash("a password longer than fifteen"),
+        legacy_hash("a password long")
+    );
+}
+
+#[test]
+fn password_test() {
+    let mut protection = DocumentProtection::default();
+    assert!(protection.check_password("anything"));
+
+    protection.set_password_with("secret", b"0123456789abcdef", 10, HashAlgorithm::Sha512);
+    assert_eq!(protection.crypt_algorithm_sid, Some(14));
+    assert_eq!(protection.salt.as_deref(), Some("MDEyMzQ1Njc4OWFiY2RlZg=="));
+    assert!(protection.check_password("secret"));
+    assert!(!protection.check_password("Secret"));
+
+    // the same hash in the ISO form of the attributes
+    let iso = DocumentProtection {
+        algorithm_name: Some("SHA-512".to_string()),
+        hash_value: protection.hash.clone(),
+        salt_value: protection.salt.clone(),
+        spin_count: Some(10),
+        ..Default::default()
+    };
+    assert!(iso.check_password("secret"));
+
+    let legacy = DocumentProtection {
+        hash: Some(format!("{:08X}", legacy_hash("secret"))),
+        ..Default::default()
+    };
+    assert!(legacy.check_password("secret"));
+    assert!(!legacy.check_password("public"));
+
+    protection.clear_password();
+    assert!(!protection.has_password());
+}
@@ -0,0 +1,543 @@
+//! Streaming reader of the main document part
+//!
+//! [`DocumentReader`] pulls [`Event`]s out of `word/document.xml` without
+//! building the whole [`Body`](crate::document::Body). Runs and properties are
+//! parsed into the usual types one at a time, so the memory used is bounded by
+//! the largest of them rather than by the size of the document.
+//!
+//! ```no_run
+//! use docx_rust::{reader::Event, LazyDocxFile};
+//!
+//! let mut docx = LazyDocxFile::from_file("report.docx").unwrap();
+//! let mut reader = docx.document_reader().unwrap();
+//!
+//! let mut paragraphs = 0;
+//! while let Some(event) = reader.next_event().unwrap() {
+//!     match event {
+//!         Event::ParagraphEnd => paragraphs += 1,
+//!         Event::Text(text) => print!("{}", text),
+//!         _ => {}
+//!     }
+//! }
+//! println!("\n{} paragraphs", paragraphs);
+//! ```
+
+use hard_xml::XmlRead;
+use std::collections::VecDeque;
+use std::io::{self, Read};
+
+use crate::{
+    document::{Run, RunContent},
+    error::{DocxError, DocxResult},
+    formatting::{
+        ParagraphProperty, SectionProperty, TableCellProperty, TableProperty, TableRowProperty,
+    },
+};
+
+/// Size of the chunks read from the underlying reader
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// An event of [`DocumentReader`]
+///
+/// Starts and ends are always balanced. Paragraphs, tables, rows and cells
+/// nested in content controls or other containers are reported as if they
+/// weren't.
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Event<'a> {
+    /// Starts a paragraph, with its properties
+    ParagraphStart(Option<ParagraphProperty<'a>>),
+    /// A run of the current paragraph, including those in hyperlinks, fields
+    /// and content controls
+    Run(Run<'a>),
+    /// Text of the previous run, with tabs and breaks as `\t` and `\n`
+    ///
+    /// Runs without any text aren't followed by this event.
+    Text(&'a str),
+    ParagraphEnd,
+    /// Starts a table, with its properties
+    TableStart(Option<TableProperty<'a>>),
+    /// Starts a row of the current table, with its properties
+    RowStart(Option<TableRowProperty>),
+    /// Starts a cell of the current row, with its properties
+    CellStart(Option<TableCellProperty<'a>>),
+    CellEnd,
+    RowEnd,
+    TableEnd,
+    /// Properties of the section ending here
+    SectionProperty(SectionProperty<'a>),
+}
+
+/// Events waiting to be returned, which don't borrow from the reader
+enum Pending {
+    Text(String),
+    ParagraphEnd,
+    CellEnd,
+    RowEnd,
+    TableEnd,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    /// Before the body
+    Outside,
+    /// Where paragraphs and tables are, like the body or a cell
+    Block,
+    Paragraph,
+    Table,
+    Row,
+    Cell,
+}
+
+struct Frame {
+    name: String,
+    /// What children are expected, the kind of the closest structural element
+    kind: Kind,
+    /// Whether the frame is a structural element rather than a container
+    structural: bool,
+    /// Whether the start event was returned
+    started: bool,
+}
+
+/// A tag read from the document
+struct Tag {
+    name: String,
+    end: bool,
+    empty: bool,
+    start: usize,
+}
+
+/// A pull parser of the main document part
+pub struct DocumentReader<R: Read> {
+    reader: R,
+    buf: Vec<u8>,
+    /// Offset of the next unread byte in `buf`
+    pos: usize,
+    eof: bool,
+    stack: Vec<Frame>,
+    pending: VecDeque<Pending>,
+    /// XML of the element the last event was parsed from
+    element: String,
+    /// Text of the last `Text` event
+    text: String,
+}
+
+impl<R: Read> DocumentReader<R> {
+    /// Reads the events of `reader`, which yields the content of `word/document.xml`.
+    pub fn new(reader: R) -> Self {
+        DocumentReader {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            stack: Vec::new(),
+            pending: VecDeque::new(),
+            element: String::new(),
+            text: String::new(),
+        }
+    }
+
+    /// Returns the next event, or `None` at the end of the document.
+    pub fn next_event(&mut self) -> DocxResult<Option<Event<'_>>> {
+        if let Some(pending) = self.pending.pop_front() {
+            return Ok(Some(match pending {
+                Pending::Text(text) => {
+                    self.text = text;
+                    Event::Text(&self.text)
+                }
+                Pending::ParagraphEnd => Event::ParagraphEnd,
+                Pending::CellEnd => Event::CellEnd,
+                Pending::RowEnd => Event::RowEnd,
+                Pending::TableEnd => Event::TableEnd,
+            }));
+        }
+
+        loop {
+            let Some(tag) = self.next_tag()? else {
+                return Ok(None);
+            };
+
+            if tag.end {
+                let Some(frame) = self.stack.pop() else {
+                    return Err(invalid_data(format!("unexpected </{}>", tag.name)));
+                };
+                if frame.name != tag.name {
+                    return Err(invalid_data(format!(
+                        "expected </{}>, found </{}>",
+                        frame.name, tag.name
+                    )));
+                }
+                if !frame.structural {
+                    continue;
+                }
+                if !frame.started {
+                    self.pending.push_back(end_of(frame.kind));
+                    return Ok(Some(start_of(frame.kind)));
+                }
+                return Ok(Some(match frame.kind {
+                    Kind::Paragraph => Event::ParagraphEnd,
+                    Kind::Table => Event::TableEnd,
+                    Kind::Row => Event::RowEnd,
+                    Kind::Cell => Event::CellEnd,
+                    Kind::Outside | Kind::Block => unreachable!(),
+                }));
+            }
+
+            let kind = self.stack.last().map_or(Kind::Outside, |f| f.kind);
+
+            // the properties come first, anything else means there are none
+            let property = match kind {
+                Kind::Paragraph => "w:pPr",
+                Kind::Table => "w:tblPr",
+                Kind::Row => "w:trPr",
+                Kind::Cell => "w:tcPr",
+                Kind::Outside | Kind::Block => "",
+            };
+            if !property.is_empty() {
+                let frame = self.stack.last_mut().unwrap();
+                if frame.structural && !frame.started {
+                    frame.started = true;
+                    if tag.name == property {
+                        self.capture(tag)?;
+                        return Ok(Some(match kind {
+                            Kind::Paragraph => {
+                                Event::ParagraphStart(Some(XmlRead::from_str(&self.element)?))
+                            }
+                            Kind::Table => {
+                                Event::TableStart(Some(XmlRead::from_str(&self.element)?))
+                            }
+                            Kind::Row => Event::RowStart(Some(XmlRead::from_str(&self.element)?)),
+                            _ => Event::CellStart(Some(XmlRead::from_str(&self.element)?)),
+                        }));
+                    }
+                    // read the tag again after the start
+                    self.pos = tag.start;
+                    return Ok(Some(start_of(kind)));
+                }
+            }
+
+            let structural = match (kind, tag.name.as_str()) {
+                (Kind::Outside, "w:body") => Some(Kind::Block),
+                (Kind::Block | Kind::Cell, "w:p") => Some(Kind::Paragraph),
+                (Kind::Block | Kind::Cell, "w:tbl") => Some(Kind::Table),
+                (Kind::Table, "w:tr") => Some(Kind::Row),
+                (Kind::Row, "w:tc") => Some(Kind::Cell),
+                _ => None,
+            };
+            if let Some(child) = structural {
+                if child == Kind::Block {
+                    if !tag.empty {
+                        self.push(tag, Kind::Block, false);
+                    }
+                    continue;
+                }
+                if tag.empty {
+                    self.pending.push_back(end_of(child));
+                    return Ok(Some(start_of(child)));
+                }
+                self.push(tag, child, true);
+                continue;
+            }
+
+            match (kind, tag.name.as_str()) {
+                (Kind::Paragraph, "w:r") => {
+                    self.capture(tag)?;
+                    let run = Run::from_str(&self.element)?;
+                    let text = run_text(&run);
+                    if !text.is_empty() {
+                        self.pending.push_back(Pending::Text(text));
+                    }
+                    return Ok(Some(Event::Run(run)));
+                }
+                (Kind::Block | Kind::Cell, "w:sectPr") => {
+                    self.capture(tag)?;
+                    return Ok(Some(Event::SectionProperty(SectionProperty::from_str(
+                        &self.element,
+                    )?)));
+                }
+                // the root element is the only one outside to look into
+                (Kind::Outside, _) if !self.stack.is_empty() => self.capture(tag)?,
+                // properties of containers, and alternatives to what was read already
+                (_, "w:sdtPr" | "w:sdtEndPr" | "w:tblGrid" | "w:tblPrEx" | "mc:Fallback") => {
+                    self.capture(tag)?
+                }
+                _ if tag.empty => {}
+                _ => self.push(tag, kind, false),
+            }
+        }
+    }
+
+    fn push(&mut self, tag: Tag, kind: Kind, structural: bool) {
+        self.stack.push(Frame {
+            name: tag.name,
+            kind,
+            structural,
+            started: false,
+        });
+    }
+
+    /// Reads the element starting with `tag` into `element`.
+    fn capture(&mut self, tag: Tag) -> DocxResult<()> {
+        self.element.clear();
+        self.push_raw(tag.start)?;
+        let mut depth = usize::from(!tag.empty);
+        while depth > 0 {
+            let start = self.pos;
+            let Some(tag) = self.next_markup()? else {
+                return Err(unexpected_eof());
+            };
+            if let Some(tag) = tag {
+                if tag.end {
+                    depth -= 1;
+                } else if !tag.empty {
+                    depth += 1;
+                }
+            }
+            self.push_raw(start)?;
+        }
+        Ok(())
+    }
+
+    fn push_raw(&mut self, start: usize) -> DocxResult<()> {
+        let raw = std::str::from_utf8(&self.buf[start..self.pos])
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.element.push_str(raw);
+        Ok(())
+    }
+
+    /// Skips to the next start or end tag.
+    fn next_tag(&mut self) -> DocxResult<Option<Tag>> {
+        // forget what was read, the elements being captured are kept until they end
+        if self.pos > CHUNK_SIZE {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        loop {
+            match self.next_markup()? {
+                None => return Ok(None),
+                Some(Some(tag)) => return Ok(Some(tag)),
+                Some(None) => {}
+            }
+        }
+    }
+
+    /// Reads the next piece of markup, `Some(None)` for text, comments,
+    /// declarations and processing instructions.
+    fn next_markup(&mut self) -> DocxResult<Option<Option<Tag>>> {
+        if !self.ensure(self.pos + 1)? {
+            return Ok(None);
+        }
+
+        let start = self.pos;
+        if self.buf[start] != b'<' {
+            let end = self.find(start, b"<")?.unwrap_or(self.buf.len());
+            self.pos = end;
+            return Ok(Some(None));
+        }
+
+        for (open, close) in [
+            (&b"<!--"[..], &b"-->"[..]),
+            (b"<![CDATA[", b"]]>"),
+            (b"<?", b"?>"),
+            (b"<!", b">"),
+        ] {
+            if self.starts_with(start, open)? {
+                let end = self
+                    .find(start + open.len(), close)?
+                    .ok_or_else(unexpected_eof)?;
+                self.pos = end + close.len();
+                return Ok(Some(None));
+            }
+        }
+
+        // a tag, whose attributes can contain '>' in quotes
+        let mut i = start + 1;
+        let mut quote = None;
+        loop {
+            if !self.ensure(i + 1)? {
+                return Err(unexpected_eof());
+            }
+            match (self.buf[i], quote) {
+                (b'"' | b'\'', None) => quote = Some(self.buf[i]),
+                (c, Some(q)) if c == q => quote = None,
+                (b'>', None) => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        self.pos = i + 1;
+
+        let raw = &self.buf[start + 1..i];
+        let end = raw.first() == Some(&b'/');
+        let empty = raw.last() == Some(&b'/');
+        let name: Vec<u8> = raw[usize::from(end)..]
+            .iter()
+            .take_while(|c| !c.is_ascii_whitespace() && **c != b'/')
+            .copied()
+            .collect();
+        let name =
+            String::from_utf8(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Some(Some(Tag {
+            name,
+            end,
+            empty,
+            start,
+        })))
+    }
+
+    fn starts_with(&mut self, start: usize, prefix: &[u8]) -> DocxResult<bool> {
+        self.ensure(start + prefix.len())?;
+        Ok(self.buf[start..].starts_with(prefix))
+    }
+
+    /// Returns the offset of `needle` from `start`, reading as much as needed.
+    fn find(&mut self, start: usize, needle: &[u8]) -> DocxResult<Option<usize>> {
+        let mut from = start;
+        loop {
+            if let Some(i) = self.buf[from..]
+                .windows(needle.len())
+                .position(|w| w == needle)
+            {
+                return Ok(Some(from + i));
+            }
+            from = self.buf.len().saturating_sub(needle.len() - 1).max(start);
+            if !self.ensure(self.buf.len() + 1)? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Reads until `buf` holds `len` bytes, returns `false` if the input ends before.
+    fn ensure(&mut self, len: usize) -> DocxResult<bool> {
+        while self.buf.len() < len {
+            if self.eof {
+                return Ok(false);
+            }
+            let filled = self.buf.len();
+            self.buf.resize(filled + CHUNK_SIZE, 0);
+            let read = loop {
+                match self.reader.read(&mut self.buf[filled..]) {
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    result => break result,
+                }
+            };
+            let read = read.inspect_err(|_| self.buf.truncate(filled))?;
+            self.buf.truncate(filled + read);
+            self.eof = read == 0;
+        }
+        Ok(true)
+    }
+}
+
+fn start_of(kind: Kind) -> Event<'static> {
+    match kind {
+        Kind::Paragraph => Event::ParagraphStart(None),
+        Kind::Table => Event::TableStart(None),
+        Kind::Row => Event::RowStart(None),
+        _ => Event::CellStart(None),
+    }
+}
+
+fn end_of(kind: Kind) -> Pending {
+    match kind {
+        Kind::Paragraph => Pending::ParagraphEnd,
+        Kind::Table => Pending::TableEnd,
+        Kind::Row => Pending::RowEnd,
+        _ => Pending::CellEnd,
+    }
+}
+
+fn run_text(run: &Run) -> String {
+    let mut text = String::new();
+    for content in &run.content {
+        match content {
+            RunContent::Text(t) => text.push_str(&t.text),
+            RunContent::Tab(_) | RunContent::PTab(_) => text.push('\t'),
+            RunContent::Break(_) | RunContent::CarriageReturn(_) => text.push('\n'),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn invalid_data(message: String) -> DocxError {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+fn unexpected_eof() -> DocxError {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "the document ends in the middle of an element",
+    )
+    .into()
+}
+
+#[test]
+fn document_reader_test() {
+    let xml = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+        r#"<w:body>"#,
+        r#"<w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:t>Title &amp; more</w:t></w:r></w:p>"#,
+        r#"<w:p/>"#,
+        r#"<w:tbl><w:tblGrid><w:gridCol w:w="100"/></w:tblGrid><w:tr><w:tc>"#,
+        r#"<w:p><w:hyperlink w:anchor="a"><w:r><w:rPr><w:b/></w:rPr><w:t>cell</w:t><w:tab/></w:r></w:hyperlink></w:p>"#,
+        r#"</w:tc></w:tr></w:tbl>"#,
+        r#"<!-- a comment with <w:p> in it -->"#,
+        r#"<w:sectPr><w:pgSz w:w="12240" w:h="15840"/></w:sectPr>"#,
+        r#"</w:body></w:document>"#,
+    );
+
+    // a reader returning a few bytes at a time, to split tags across reads
+    struct Trickle<'a>(&'a [u8]);
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    let mut reader = DocumentReader::new(Trickle(xml.as_bytes()));
+    let mut events = Vec::new();
+    while let Some(event) = reader.next_event().unwrap() {
+        events.push(match event {
+            Event::ParagraphStart(p) => format!("p {}", p.is_some()),
+            Event::Run(r) => format!("r {}", r.property.is_some()),
+            Event::Text(t) => format!("t {:?}", t),
+            Event::ParagraphEnd => "/p".to_string(),
+            Event::TableStart(p) => format!("tbl {}", p.is_some()),
+            Event::RowStart(p) => format!("tr {}", p.is_some()),
+            Event::CellStart(p) => format!("tc {}", p.is_some()),
+            Event::CellEnd => "/tc".to_string(),
+            Event::RowEnd => "/tr".to_string(),
+            Event::TableEnd => "/tbl".to_string(),
+            Event::SectionProperty(s) => format!("sectPr {}", s.page_size.is_some()),
+        });
+    }
+
+    assert_eq!(
+        events,
+        [
+            "p true",
+            "r false",
+            r#"t "Title & more""#,
+            "/p",
+            "p false",
+            "/p",
+            "tbl false",
+            "tr false",
+            "tc false",
+            "p false",
+            "r true",
+            r#"t "cell\t""#,
+            "/p",
+            "/tc",
+            "/tr",
+            "/tbl",
+            "sectPr true",
+        ]
+    );
+}
@@ -45,69 +45,48 @@ impl<'a> XmlWrite for Relationships<'a> {
 }
 
 impl<'a> Relationships<'a> {
-    pub fn add_rel(&mut self, schema: &'a str, target: &'a str) {
-        let has = self.relationships.iter().find(|r| r.target == target);
-        if has.is_none() {
-            let ids: Vec<_> = self
-                .relationships
-                .iter()
-                .map(|r| r.id.to_string())
-                .collect();
-
-            let len = self.relationships.len();
-
-            let mut available = false;
-            let mut id = len;
-            while !available {
-                id += 1;
-                let idstr = format!("rId{}", id);
-                available = !ids.contains(&idstr);
-            }
-
-            //hack
-            //let target = target.replace("jpeg","png");
-            self.relationships.push(Relationship {
-                id: format!("rId{}", id).into(),
-                target: target.into(),
-                ty: schema.into(),
-                target_mode: None,
-            });
-        }
+    /// Adds a relationship to `target` and returns its id.
+    ///
+    /// If a relationship with the same target already exists, its id is returned instead.
+    pub fn add_rel<T: Into<Cow<'a, str>>>(&mut self, schema: &'a str, target: T) -> String {
+        self.add_rel_with_target_mode(schema, target, None)
     }
 
-    pub fn add_rel_with_target_mode(
+    pub fn add_rel_with_target_mode<T: Into<Cow<'a, str>>>(
         &mut self,
         schema: &'a str,
-        target: &'a str,
+        target: T,
         target_mode: Option<&'a str>,
-    ) {
-        let has = self.relationships.iter().find(|r| r.target == target);
-        if has.is_none() {
-            let ids: Vec<_> = self
-                .relationships
-                .iter()
-                .map(|r| r.id.to_string())
-                .collect();
-
-            let len = self.relationships.len();
-
-            let mut available = false;
-            let mut id = len;
-            while !available {
-                id += 1;
-                let idstr = format!("rId{}", id);
-                available = !ids.contains(&idstr);
-            }
+    ) -> String {
+        let target = target.into();
+        if let Some(has) = self.relationships.iter().find(|r| r.target == target) {
+            return has.id.to_string();
+        }
 
-            //hack
-            //let target = target.replace("jpeg","png");
-            self.relationships.push(Relationship {
-                id: format!("rId{}", id).into(),
-                target: target.into(),
-                ty: schema.into(),
-                target_mode: TargetMode::from_str(target_mode),
-            });
+        let ids: Vec<_> = self
+            .relationships
+            .iter()
+            .map(|r| r.id.to_string())
+            .collect();
+
+        let len = self.relationships.len();
+
+        let mut available = false;
+        let mut id = len;
+        while !available {
+            id += 1;
+            let idstr = format!("rId{}", id);
+            available = !ids.contains(&idstr);
         }
+
+        let id = format!("rId{}", id);
+        self.relationships.push(Relationship {
+            id: id.clone().into(),
+            target,
+            ty: schema.into(),
+            target_mode: TargetMode::from_str(target_mode),
+        });
+        id
     }
 
     pub fn get_target(&self, id: &str) -> Option<&str> {
@@ -143,10 +122,7 @@ impl From<&str> for TargetMode {
 
 impl TargetMode {
     fn from_str(option_str: Option<&str>) -> Option<Self> {
-        match option_str {
-            Some(s) => Some(s.into()),
-            None => None,
-        }
+        option_str.map(|s| s.into())
     }
 }
 
@@ -0,0 +1,727 @@
+//! Repair of structural problems
+//!
+//! [`Docx::repair`] fixes what makes Word report unreadable content in generated
+//! documents: empty table cells, grids that don't match the rows, duplicate
+//! drawing ids, references to missing relationships, unbalanced fields and
+//! comment ranges, and a body without the properties of its last section.
+//!
+//! ```no_run
+//! use docx_rust::DocxFile;
+//!
+//! let docx = DocxFile::from_file("generated.docx").unwrap();
+//! let mut docx = docx.parse().unwrap();
+//!
+//! let report = docx.repair();
+//! if !report.is_empty() {
+//!     println!("{:?}", report);
+//! }
+//!
+//! docx.write_file("repaired.docx").unwrap();
+//! ```
+//!
+//! [`Docx::repair`]: ../struct.Docx.html#method.repair
+
+use std::collections::HashSet;
+
+use crate::{
+    document::{
+        BodyContent, CharType, CommentRangeEnd, DocPr, Drawing, FieldChar, Graphic,
+        HeaderFooterReference, Paragraph, ParagraphContent, Run, RunContent, Table, TableCell,
+        TableCellContent, TableRow, TableRowContent, SDT,
+    },
+    formatting::{SectionProperty, TableWidthUnit},
+    page_setup::PageSetup,
+    rels::{Relationships, TargetMode},
+    schema::{SCHEMA_FOOTER, SCHEMA_HEADER, SCHEMA_IMAGE},
+    Docx,
+};
+
+/// What [`Docx::repair`](crate::Docx::repair) fixed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Number of paragraphs inserted into empty table cells and after a table
+    /// ending the body
+    pub paragraphs: usize,
+    /// Number of table grids rebuilt from the widths of the cells
+    pub table_grids: usize,
+    /// Number of drawings given a unique id
+    pub drawing_ids: usize,
+    /// Number of hyperlinks, header and footer references and pictures whose
+    /// relationship was missing, and that were removed
+    pub references: usize,
+    /// Number of relationships to missing headers, footers and images removed
+    pub relationships: usize,
+    /// Number of media removed since no relationship targets them
+    pub media: usize,
+    /// Number of content types added for the extensions of media
+    pub content_types: usize,
+    /// Number of field characters removed or added to end fields
+    pub field_chars: usize,
+    /// Number of comment range starts and ends removed, or ends added to end ranges
+    pub comment_ranges: usize,
+    /// Whether properties of the last section were added at the end of the body
+    pub section_property: bool,
+}
+
+impl RepairReport {
+    /// Returns `true` if nothing was fixed.
+    pub fn is_empty(&self) -> bool {
+        *self == RepairReport::default()
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Fixes the structural problems Word rejects, and reports what was fixed.
+    ///
+    /// Like [`validate`](Docx::validate), the relationships of headers,
+    /// footers, notes and comments aren't known, so references from these
+    /// parts are left alone.
+    pub fn repair(&mut self) -> RepairReport {
+        let mut report = RepairReport::default();
+
+        self.repair_media(&mut report);
+
+        let content = &self.document.body.content;
+        if !matches!(content.last(), Some(BodyContent::SectionProperty(_))) {
+            let sections = self.sections();
+            let property = match sections.len() {
+                n if n >= 2 => sections[n - 2].property.clone(),
+                _ => {
+                    let mut property = SectionProperty::default();
+                    PageSetup::default().apply(&mut property);
+                    property
+                }
+            };
+            self.document.body.content.push(property.into());
+            report.section_property = true;
+        }
+
+        let content = &mut self.document.body.content;
+        blocks(content, &mut report);
+        let last = content
+            .iter()
+            .rposition(|c| !matches!(c, BodyContent::SectionProperty(_)));
+        if let Some(i) = last.filter(|i| matches!(content[*i], BodyContent::Table(_))) {
+            content.insert(i + 1, Paragraph::default().into());
+            report.paragraphs += 1;
+        }
+        for header in self.headers.values_mut() {
+            blocks(&mut header.content, &mut report);
+        }
+        for footer in self.footers.values_mut() {
+            blocks(&mut footer.content, &mut report);
+        }
+        for note in self.footnotes.iter_mut().flat_map(|n| &mut n.content) {
+            blocks(&mut note.content, &mut report);
+        }
+        for note in self.endnotes.iter_mut().flat_map(|n| &mut n.content) {
+            blocks(&mut note.content, &mut report);
+        }
+
+        let no_rels = Relationships::default();
+        let mut repairer = Repairer {
+            report,
+            rels: Some(self.document_rels.as_ref().unwrap_or(&no_rels)),
+            headers: self.headers.keys().cloned().collect(),
+            footers: self.footers.keys().cloned().collect(),
+            media: self.media.keys().cloned().collect(),
+            doc_pr_ids: HashSet::new(),
+            next_doc_pr_id: 0,
+            fields: Vec::new(),
+            comment_ranges: Vec::new(),
+        };
+        for c in &mut self.document.body.content {
+            if let BodyContent::SectionProperty(section) = c {
+                repairer.section(section);
+            }
+        }
+
+        let mut document = Vec::new();
+        paragraphs(&mut self.document.body.content, &mut document);
+        let mut stories = Vec::new();
+        for header in self.headers.values_mut() {
+            let mut story = Vec::new();
+            paragraphs(&mut header.content, &mut story);
+            stories.push(story);
+        }
+        for footer in self.footers.values_mut() {
+            let mut story = Vec::new();
+            paragraphs(&mut footer.content, &mut story);
+            stories.push(story);
+        }
+        for note in self.footnotes.iter_mut().flat_map(|n| &mut n.content) {
+            let mut story = Vec::new();
+            paragraphs(&mut note.content, &mut story);
+            stories.push(story);
+        }
+        for note in self.endnotes.iter_mut().flat_map(|n| &mut n.content) {
+            let mut story = Vec::new();
+            paragraphs(&mut note.content, &mut story);
+            stories.push(story);
+        }
+        for comment in self.comments.iter_mut().flat_map(|c| &mut c.comments) {
+            stories.push(vec![&mut comment.content]);
+        }
+
+        let mut max_id = 0;
+        for p in document.iter_mut().chain(stories.iter_mut().flatten()) {
+            for_each_run(p, &mut |run| {
+                for c in &run.content {
+                    if let RunContent::Drawing(drawing) = c {
+                        for (doc_property, _) in frames(drawing) {
+                            max_id = max_id.max(doc_property.id.unwrap_or_default());
+                        }
+                    }
+                }
+            });
+        }
+        repairer.next_doc_pr_id = max_id + 1;
+
+        repairer.story(document);
+        repairer.rels = None;
+        for story in stories {
+            repairer.story(story);
+        }
+
+        repairer.report
+    }
+
+    /// Removes orphaned media and relationships to missing parts, and adds the
+    /// content types of media.
+    fn repair_media(&mut self, report: &mut RepairReport) {
+        let targets: HashSet<&str> = self
+            .document_rels
+            .iter()
+            .flat_map(|r| &r.relationships)
+            .map(|r| target_part(&r.target))
+            .collect();
+        let count = self.media.len();
+        self.media.retain(|name, _| targets.contains(name.as_str()));
+        report.media += count - self.media.len();
+
+        if let Some(rels) = &mut self.document_rels {
+            let count = rels.relationships.len();
+            rels.relationships.retain(|r| {
+                let target = target_part(&r.target);
+                r.target_mode == Some(TargetMode::External)
+                    || match &*r.ty {
+                        SCHEMA_IMAGE => self.media.contains_key(target),
+                        SCHEMA_HEADER | SCHEMA_FOOTER => {
+                            self.headers.contains_key(target) || self.footers.contains_key(target)
+                        }
+                        _ => true,
+                    }
+            });
+            report.relationships += count - rels.relationships.len();
+        }
+
+        let mut names: Vec<_> = self.media.keys().collect();
+        names.sort();
+        for name in names {
+            let ext = name
+                .rsplit_once('.')
+                .map(|(_, ext)| ext)
+                .unwrap_or_default();
+            let part = format!("/word/{}", name);
+            let content_types = &mut self.content_types;
+            let typed = content_types
+                .defaults
+                .iter()
+                .any(|d| d.ext.eq_ignore_ascii_case(ext))
+                || content_types.overrides.iter().any(|o| o.part == part);
+            if let Some(ty) = crate::media::get_media_content_type(name).filter(|_| !typed) {
+                content_types.add_default(ext.to_ascii_lowercase(), ty);
+                report.content_types += 1;
+            }
+        }
+    }
+}
+
+/// Returns the name of the part a relationship of the main document part targets.
+fn target_part(target: &str) -> &str {
+    target.trim_start_matches("/word/")
+}
+
+/// Returns `true` if relationship `id` is missing from `rels`, or doesn't target
+/// one of `parts`. Always `false` when the relationships aren't checked.
+fn is_missing(rels: Option<&Relationships>, id: &str, parts: Option<&HashSet<String>>) -> bool {
+    let Some(rels) = rels else {
+        return false;
+    };
+    match rels.get_target(id) {
+        None => true,
+        Some(target) => parts.is_some_and(|parts| !parts.contains(target_part(target))),
+    }
+}
+
+/// Fills empty table cells and rebuilds grids.
+fn blocks(content: &mut [BodyContent], report: &mut RepairReport) {
+    for c in content {
+        match c {
+            BodyContent::Table(table) => {
+                for row in &mut table.rows {
+                    for cell in &mut row.cells {
+                        match cell {
+                            TableRowContent::TableCell(cell) => fill_cell(cell, report),
+                            TableRowContent::SDT(sdt) => {
+                                if let Some(content) = &mut sdt.content {
+                                    blocks(&mut content.content, report);
+                                }
+                            }
+                        }
+                    }
+                }
+                if table_grid(table) {
+                    report.table_grids += 1;
+                }
+            }
+            BodyContent::Sdt(sdt) => {
+                if let Some(content) = &mut sdt.content {
+                    blocks(&mut content.content, report);
+                }
+            }
+            BodyContent::TableCell(cell) => fill_cell(cell, report),
+            _ => {}
+        }
+    }
+}
+
+fn fill_cell(cell: &mut TableCell, report: &mut RepairReport) {
+    if cell.content.is_empty() {
+        cell.content.push(Paragraph::default().into());
+        report.paragraphs += 1;
+    }
+}
+
+/// Rebuilds the grid of a table if its number of columns doesn't match the rows,
+/// returns `true` if it was rebuilt.
+///
+/// Columns take the width of the cells they hold, spanning cells share theirs
+/// between the columns no other cell gives a width to.
+fn table_grid(table: &mut Table) -> bool {
+    let rows: Vec<Vec<(usize, Option<isize>)>> = table.rows.iter().map(row_cells).collect();
+    let columns = rows
+        .iter()
+        .map(|cells| cells.iter().map(|(span, _)| span).sum())
+        .max()
+        .unwrap_or(0);
+    if columns == 0 || columns == table.grids.columns.len() {
+        return false;
+    }
+
+    let mut widths = vec![None; columns];
+    for spanning in [false, true] {
+        for cells in &rows {
+            let mut column = 0;
+            for &(span, width) in cells {
+                let shared = &mut widths[column..column + span];
+                if let Some(width) = width.filter(|_| (span > 1) == spanning) {
+                    if shared.iter().all(Option::is_none) {
+                        shared.fill(Some(width / span as isize));
+                    }
+                }
+                column += span;
+            }
+        }
+    }
+
+    let known: Vec<isize> = widths.iter().flatten().copied().collect();
+    if known.is_empty() {
+        table.grids = crate::import::table_grid(columns);
+    } else {
+        let average = known.iter().sum::<isize>() / known.len() as isize;
+        table.grids.columns = widths
+            .into_iter()
+            .map(|width| width.unwrap_or(average).into())
+            .collect();
+    }
+    true
+}
+
+/// Returns the number of grid columns and the width in twips of the cells of a row.
+fn row_cells(row: &TableRow) -> Vec<(usize, Option<isize>)> {
+    let cell = |cell: &TableCell| {
+        let span = cell
+            .property
+            .grid_span
+            .as_ref()
+            .map_or(1, |s| s.value.max(1));
+        let width = cell
+            .property
+            .wide
+            .as_ref()
+            .filter(|w| matches!(w.unit, None | Some(TableWidthUnit::Dxa)))
+            .and_then(|w| w.value);
+        (span as usize, width)
+    };
+    let mut cells = Vec::new();
+    for c in &row.cells {
+        match c {
+            TableRowContent::TableCell(c) => cells.push(cell(c)),
+            TableRowContent::SDT(sdt) => {
+                for c in sdt.content.iter().flat_map(|c| &c.content) {
+                    if let BodyContent::TableCell(c) = c {
+                        cells.push(cell(c));
+                    }
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// Collects the paragraphs of blocks, including those of tables, in order.
+fn paragraphs<'x, 'a>(content: &'x mut [BodyContent<'a>], found: &mut Vec<&'x mut Paragraph<'a>>) {
+    fn cell<'x, 'a>(cell: &'x mut TableCell<'a>, found: &mut Vec<&'x mut Paragraph<'a>>) {
+        found.extend(
+            cell.content
+                .iter_mut()
+                .map(|TableCellContent::Paragraph(p)| p),
+        );
+    }
+
+    for c in content {
+        match c {
+            BodyContent::Paragraph(p) => found.push(p),
+            BodyContent::Table(table) => {
+                for c in table.rows.iter_mut().flat_map(|r| &mut r.cells) {
+                    match c {
+                        TableRowContent::TableCell(c) => cell(c, found),
+                        TableRowContent::SDT(sdt) => {
+                            if let Some(content) = &mut sdt.content {
+                                paragraphs(&mut content.content, found);
+                            }
+                        }
+                    }
+                }
+            }
+            BodyContent::Sdt(sdt) => {
+                if let Some(content) = &mut sdt.content {
+                    paragraphs(&mut content.content, found);
+                }
+            }
+            BodyContent::TableCell(c) => cell(c, found),
+            _ => {}
+        }
+    }
+}
+
+/// Calls `f` on the runs of a paragraph, including those of hyperlinks, simple
+/// fields and content controls.
+fn for_each_run<'a, F: FnMut(&mut Run<'a>)>(p: &mut Paragraph<'a>, f: &mut F) {
+    for c in &mut p.content {
+        match c {
+            ParagraphContent::Run(run) => f(run),
+            ParagraphContent::Link(link) => {
+                if let Some(run) = &mut link.content {
+                    f(run);
+                }
+                let mut levels: Vec<_> = link.bidirectional_embedding.iter_mut().collect();
+                while let Some(level) = levels.pop() {
+                    level.runs.iter_mut().for_each(&mut *f);
+                    levels.extend(level.nested_levels.iter_mut());
+                }
+            }
+            ParagraphContent::SimpleField(field) => field.runs.iter_mut().for_each(&mut *f),
+            ParagraphContent::SDT(sdt) => for_each_sdt_run(sdt, f),
+            _ => {}
+        }
+    }
+}
+
+/// Calls `f` on the runs of a content control, in order.
+fn for_each_sdt_run<'a, F: FnMut(&mut Run<'a>)>(sdt: &mut SDT<'a>, f: &mut F) {
+    for c in sdt.content.iter_mut().flat_map(|c| &mut c.content) {
+        match c {
+            BodyContent::Run(run) => f(run),
+            BodyContent::Sdt(sdt) => for_each_sdt_run(sdt, f),
+            c => {
+                let mut found = Vec::new();
+                paragraphs(std::slice::from_mut(c), &mut found);
+                for p in found {
+                    for_each_run(p, f);
+                }
+            }
+        }
+    }
+}
+
+/// Drops the comment range starts of a paragraph that are already open and
+/// the ends that aren't, including those in content controls, and returns
+/// how many were dropped.
+fn comment_ranges(content: &mut Vec<ParagraphContent>, open: &mut Vec<String>) -> usize {
+    let count = content.len();
+    let mut dropped = 0;
+    content.retain_mut(|c| match c {
+        ParagraphContent::CommentRangeStart(range) => {
+            if open.iter().any(|open| *open == range.id) {
+                return false;
+            }
+            open.push(range.id.to_string());
+            true
+        }
+        ParagraphContent::CommentRangeEnd(range) => {
+            match open.iter().position(|open| *open == range.id) {
+                Some(pos) => {
+                    open.remove(pos);
+                    true
+                }
+                None => false,
+            }
+        }
+        ParagraphContent::SDT(sdt) => {
+            let mut found = Vec::new();
+            if let Some(content) = &mut sdt.content {
+                paragraphs(&mut content.content, &mut found);
+            }
+            for p in found {
+                dropped += comment_ranges(&mut p.content, open);
+            }
+            true
+        }
+        _ => true,
+    });
+    dropped + count - content.len()
+}
+
+/// Returns the properties of the frames of a drawing, with the relationship
+/// ids of their pictures.
+fn frames<'d, 'a>(drawing: &'d Drawing<'a>) -> Vec<(&'d DocPr<'a>, Vec<&'d str>)> {
+    fn embeds<'d>(graphic: &'d Option<Graphic>) -> Vec<&'d str> {
+        graphic
+            .iter()
+            .flat_map(|g| &g.data.children)
+            .map(|p| &*p.fill.blip.embed)
+            .collect()
+    }
+
+    let anchors = drawing
+        .anchor
+        .iter()
+        .map(|a| (&a.doc_property, embeds(&a.graphic)));
+    let inlines = drawing
+        .inline
+        .iter()
+        .map(|i| (&i.doc_property, embeds(&i.graphic)));
+    anchors.chain(inlines).collect()
+}
+
+struct Repairer<'r, 'a> {
+    report: RepairReport,
+    /// Relationships of the current part, `None` if they aren't checked
+    rels: Option<&'r Relationships<'a>>,
+    headers: HashSet<String>,
+    footers: HashSet<String>,
+    media: HashSet<String>,
+    doc_pr_ids: HashSet<isize>,
+    next_doc_pr_id: isize,
+    /// Fields started in the current story, and whether they're separated
+    fields: Vec<bool>,
+    /// Comment ranges started in the current story
+    comment_ranges: Vec<String>,
+}
+
+impl<'a> Repairer<'_, 'a> {
+    /// Repairs the paragraphs of a story, like the body or a footnote, ending
+    /// its open fields and comment ranges in its last paragraph.
+    fn story(&mut self, mut paragraphs: Vec<&mut Paragraph<'a>>) {
+        for p in &mut paragraphs {
+            self.paragraph(p);
+        }
+
+        if let Some(last) = paragraphs.last_mut() {
+            for _ in self.fields.drain(..) {
+                last.content
+                    .push(Run::default().push(FieldChar::from(CharType::End)).into());
+                self.report.field_chars += 1;
+            }
+            for id in self.comment_ranges.drain(..) {
+                last.content.push(CommentRangeEnd::default().id(id).into());
+                self.report.comment_ranges += 1;
+            }
+        }
+    }
+
+    fn paragraph(&mut self, p: &mut Paragraph<'a>) {
+        if let Some(section) = p
+            .property
+            .as_mut()
+            .and_then(|p| p.section_property.as_mut())
+        {
+            self.section(section);
+        }
+
+        self.report.comment_ranges += comment_ranges(&mut p.content, &mut self.comment_ranges);
+
+        for c in &mut p.content {
+            if let ParagraphContent::Link(link) = c {
+                if link
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| is_missing(self.rels, id, None))
+                {
+                    link.id = None;
+                    self.report.references += 1;
+                }
+            }
+        }
+
+        for_each_run(p, &mut |run| self.run(run));
+    }
+
+    fn section(&mut self, section: &mut SectionProperty) {
+        let count = section.header_footer_references.len();
+        let rels = self.rels;
+        section.header_footer_references.retain(|reference| {
+            let (id, parts) = match reference {
+                HeaderFooterReference::Header(r) => (&r.id, &self.headers),
+                HeaderFooterReference::Footer(r) => (&r.id, &self.footers),
+            };
+            id.as_ref()
+                .is_some_and(|id| !is_missing(rels, id, Some(parts)))
+        });
+        self.report.references += count - section.header_footer_references.len();
+    }
+
+    fn run(&mut self, run: &mut Run) {
+        let count = run.content.len();
+        let mut references = 0;
+        let rels = self.rels;
+        run.content.retain(|c| match c {
+            RunContent::FieldChar(c) => match c.ty {
+                Some(CharType::Begin) => {
+                    self.fields.push(false);
+                    true
+                }
+                Some(CharType::Separate) => match self.fields.last_mut() {
+                    Some(separated) if !*separated => {
+                        *separated = true;
+                        true
+                    }
+                    _ => false,
+                },
+                Some(CharType::End) => self.fields.pop().is_some(),
+                None => false,
+            },
+            RunContent::Drawing(drawing) => {
+                let missing = frames(drawing)
+                    .into_iter()
+                    .flat_map(|(_, embeds)| embeds)
+                    .any(|id| is_missing(rels, id, Some(&self.media)));
+                references += missing as usize;
+                !missing
+            }
+            _ => true,
+        });
+        self.report.references += references;
+        self.report.field_chars += count - run.content.len() - references;
+
+        for c in &mut run.content {
+            if let RunContent::Drawing(drawing) = c {
+                let frames = drawing
+                    .anchor
+                    .iter_mut()
+                    .map(|a| &mut a.doc_property)
+                    .chain(drawing.inline.iter_mut().map(|i| &mut i.doc_property));
+                for doc_property in frames {
+                    if !doc_property.id.is_some_and(|id| self.doc_pr_ids.insert(id)) {
+                        doc_property.id = Some(self.next_doc_pr_id);
+                        self.doc_pr_ids.insert(self.next_doc_pr_id);
+                        self.next_doc_pr_id += 1;
+                        self.report.drawing_ids += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn repair_test() {
+    use crate::{
+        document::{Comment, CommentRangeStart, Hyperlink, InstrText, SDTContent},
+        formatting::{TableCellProperty, TableCellWidth},
+    };
+
+    let mut docx = Docx::default();
+    docx.document.push(
+        Paragraph::default()
+            .push(CommentRangeStart::default().id("0"))
+            .push(Run::default().push(FieldChar::from(CharType::Begin)))
+            .push(Run::default().push(FieldChar::from(CharType::Separate)))
+            .push(Run::default().push(FieldChar::from(CharType::Separate)))
+            .push(Hyperlink::default().id("rId9"))
+            .push(Run::default().push(Drawing::inline_picture(1, "a", "rId8", 10i64, 10i64))),
+    );
+    // a field and a comment range that end in a content control are closed
+    docx.document.push(
+        Paragraph::default()
+            .push(CommentRangeStart::default().id("1"))
+            .push(
+                Run::default()
+                    .push(FieldChar::from(CharType::Begin))
+                    .push(InstrText::from(" PAGE ")),
+            )
+            .push(
+                SDT::default().content(
+                    SDTContent::default()
+                        .push(
+                            Run::default()
+                                .push(FieldChar::from(CharType::Separate))
+                                .push_text("1")
+                                .push(FieldChar::from(CharType::End)),
+                        )
+                        .push(Paragraph::default().push(CommentRangeEnd::default().id("1"))),
+                ),
+            ),
+    );
+    for id in 0..2 {
+        docx.comments
+            .get_or_insert_with(Default::default)
+            .comments
+            .push(Comment {
+                id: Some(id),
+                ..Default::default()
+            });
+    }
+    let cell = TableCell {
+        property: TableCellProperty::default().wide(TableCellWidth {
+            value: Some(3000),
+            unit: Some(TableWidthUnit::Dxa),
+        }),
+        ..Default::default()
+    };
+    docx.document.push(
+        Table::default().push_row(
+            TableRow::default()
+                .push_cell(cell)
+                .push_cell(TableCell::default()),
+        ),
+    );
+
+    let report = docx.repair();
+    assert_eq!(
+        report,
+        RepairReport {
+            paragraphs: 3,
+            table_grids: 1,
+            references: 2,
+            field_chars: 2,
+            comment_ranges: 1,
+            section_property: true,
+            ..Default::default()
+        }
+    );
+    assert!(docx.validate().is_empty(), "{:?}", docx.validate());
+
+    let content = &docx.document.body.content;
+    let BodyContent::Table(table) = &content[2] else {
+        panic!("expected a table");
+    };
+    let widths: Vec<_> = table.grids.columns.iter().map(|c| c.width).collect();
+    assert_eq!(widths, [3000, 3000]);
+    assert!(matches!(content[3], BodyContent::Paragraph(_)));
+    assert!(matches!(content[4], BodyContent::SectionProperty(_)));
+
+    assert!(docx.repair().is_empty());
+}
@@ -0,0 +1,545 @@
+//! Removal of personal information
+//!
+//! [`Docx::sanitize`] strips a document of what Word's "Inspect Document" finds:
+//! the names of authors, revision dates and ids, hidden text, the attached
+//! template, document variables and custom properties.
+//!
+//! ```no_run
+//! use docx_rust::{sanitize::SanitizeOptions, DocxFile};
+//!
+//! let docx = DocxFile::from_file("origin.docx").unwrap();
+//! let mut docx = docx.parse().unwrap();
+//!
+//! let report = docx.sanitize(
+//!     &SanitizeOptions::default()
+//!         .author("Reviewer")
+//!         .remove_personal_information(true),
+//! );
+//! println!("{} hidden runs removed", report.hidden_runs);
+//!
+//! docx.write_file("sanitized.docx").unwrap();
+//! ```
+//!
+//! [`Docx::sanitize`]: ../struct.Docx.html#method.sanitize
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    __setter,
+    app::App,
+    core::Core,
+    document::{
+        BodyContent, Hyperlink, Paragraph, ParagraphContent, Run, RunContent, TableCellContent,
+        TableRowContent,
+    },
+    formatting::SectionProperty,
+    settings::{RemoveDateAndTime, RemovePersonalInformation},
+    styles::Styles,
+    Docx,
+};
+
+/// Options of [`Docx::sanitize`](crate::Docx::sanitize)
+///
+/// Everything is removed by default, except that `w:removePersonalInformation`
+/// isn't set.
+#[derive(Debug, Clone)]
+pub struct SanitizeOptions {
+    /// Name given to the authors of comments and revisions.
+    pub author: String,
+    /// Removes the dates of revisions.
+    pub revision_dates: bool,
+    /// Removes the creator and last editor of the core properties, and the
+    /// company and template of the extended properties.
+    pub document_properties: bool,
+    /// Removes the revision save ids of the settings, paragraphs, runs and sections.
+    pub rsids: bool,
+    /// Removes the runs formatted as hidden.
+    pub hidden_text: bool,
+    /// Removes the template attached in the settings.
+    pub attached_template: bool,
+    /// Removes the document variables.
+    pub doc_vars: bool,
+    /// Removes the custom properties.
+    pub custom_properties: bool,
+    /// Asks Word to remove personal information and annotation dates on every save.
+    pub remove_personal_information: bool,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        SanitizeOptions {
+            author: "Author".to_string(),
+            revision_dates: true,
+            document_properties: true,
+            rsids: true,
+            hidden_text: true,
+            attached_template: true,
+            doc_vars: true,
+            custom_properties: true,
+            remove_personal_information: false,
+        }
+    }
+}
+
+impl SanitizeOptions {
+    __setter!(author: String);
+    __setter!(revision_dates: bool);
+    __setter!(document_properties: bool);
+    __setter!(rsids: bool);
+    __setter!(hidden_text: bool);
+    __setter!(attached_template: bool);
+    __setter!(doc_vars: bool);
+    __setter!(custom_properties: bool);
+    __setter!(remove_personal_information: bool);
+}
+
+/// What [`Docx::sanitize`](crate::Docx::sanitize) removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    /// Number of comments whose author was replaced
+    pub comment_authors: usize,
+    /// Number of revisions whose author was replaced
+    pub revision_authors: usize,
+    /// Number of revisions whose date was removed
+    pub revision_dates: usize,
+    /// Names of the removed document properties, like `creator` or `company`
+    pub properties: Vec<&'static str>,
+    /// Number of removed revision save ids, in the settings and as attributes
+    pub rsids: usize,
+    /// Whether an attached template was removed
+    pub attached_template: bool,
+    /// Number of removed hidden runs
+    ///
+    /// Hidden runs with field characters keep them, so that fields stay balanced.
+    pub hidden_runs: usize,
+    /// Number of removed document variables
+    pub doc_vars: usize,
+    /// Number of removed custom properties
+    pub custom_properties: usize,
+}
+
+impl SanitizeReport {
+    /// Returns `true` if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        *self == SanitizeReport::default()
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Removes personal information from the document, and reports what was removed.
+    pub fn sanitize(&mut self, options: &SanitizeOptions) -> SanitizeReport {
+        let mut sanitizer = Sanitizer {
+            options,
+            hidden_styles: hidden_styles(&self.styles),
+            report: SanitizeReport::default(),
+        };
+
+        sanitizer.blocks(&mut self.document.body.content);
+        for header in self.headers.values_mut() {
+            sanitizer.blocks(&mut header.content);
+        }
+        for footer in self.footers.values_mut() {
+            sanitizer.blocks(&mut footer.content);
+        }
+        for note in self.footnotes.iter_mut().flat_map(|n| &mut n.content) {
+            sanitizer.blocks(&mut note.content);
+        }
+        for note in self.endnotes.iter_mut().flat_map(|n| &mut n.content) {
+            sanitizer.blocks(&mut note.content);
+        }
+        for comment in self.comments.iter_mut().flat_map(|c| &mut c.comments) {
+            if sanitizer.author(&mut comment.author) {
+                sanitizer.report.comment_authors += 1;
+            }
+            sanitizer.paragraph(&mut comment.content);
+        }
+
+        if options.document_properties {
+            sanitizer.document_properties(self.core.as_mut(), self.app.as_mut());
+        }
+
+        if let Some(settings) = &mut self.settings {
+            let report = &mut sanitizer.report;
+            if options.rsids {
+                if let Some(rsids) = settings.rsids.take() {
+                    report.rsids += rsids.ro.iter().count() + rsids.rsids.len();
+                }
+            }
+            if options.attached_template {
+                if let Some(template) = settings.attached_template.take() {
+                    if let Some(rels) = &mut self.settings_rels {
+                        rels.relationships.retain(|r| r.id != template.val);
+                    }
+                    report.attached_template = true;
+                }
+            }
+            if options.doc_vars {
+                if let Some(vars) = settings.doc_vars.take() {
+                    report.doc_vars += vars.vars.len();
+                }
+            }
+            if options.remove_personal_information {
+                settings.remove_personal_information = Some(RemovePersonalInformation::default());
+                settings.remove_date_and_time = Some(RemoveDateAndTime::default());
+            }
+        }
+
+        if options.custom_properties {
+            if let Some(properties) = self.custom_properties.take() {
+                sanitizer.report.custom_properties += properties.properties.len();
+            }
+        }
+
+        sanitizer.report
+    }
+}
+
+struct Sanitizer<'o> {
+    options: &'o SanitizeOptions,
+    /// Ids of the styles that hide text, directly or through the styles they are based on
+    hidden_styles: HashSet<String>,
+    report: SanitizeReport,
+}
+
+impl Sanitizer<'_> {
+    /// Replaces an author, returns `true` if it changed.
+    fn author(&self, author: &mut Cow<'_, str>) -> bool {
+        if *author == self.options.author {
+            return false;
+        }
+        *author = Cow::Owned(self.options.author.clone());
+        true
+    }
+
+    fn revision(&mut self, author: Option<&mut Cow<'_, str>>, date: &mut Option<Cow<'_, str>>) {
+        if let Some(author) = author {
+            if self.author(author) {
+                self.report.revision_authors += 1;
+            }
+        }
+        if self.options.revision_dates && date.take().is_some() {
+            self.report.revision_dates += 1;
+        }
+    }
+
+    fn rsids(
+        &mut self,
+        rsid_r: &mut Option<Cow<'_, str>>,
+        rsid_r_default: &mut Option<Cow<'_, str>>,
+    ) {
+        if self.options.rsids {
+            self.report.rsids +=
+                rsid_r.take().iter().count() + rsid_r_default.take().iter().count();
+        }
+    }
+
+    fn is_hidden(&self, run: &Run) -> bool {
+        let Some(property) = &run.property else {
+            return false;
+        };
+        match &property.vanish {
+            Some(vanish) => vanish.value != Some(false),
+            None => property
+                .style_id
+                .as_ref()
+                .is_some_and(|id| self.hidden_styles.contains(id.value.as_ref())),
+        }
+    }
+
+    /// Strips a hidden run down to its field characters, and returns whether
+    /// anything is left of it.
+    fn keep_run(&mut self, run: &mut Run) -> bool {
+        let is_field_char = |c: &RunContent| matches!(c, RunContent::FieldChar(_));
+        if !self.options.hidden_text
+            || !self.is_hidden(run)
+            || (!run.content.is_empty() && run.content.iter().all(is_field_char))
+        {
+            return true;
+        }
+        self.report.hidden_runs += 1;
+        run.content.retain(is_field_char);
+        !run.content.is_empty()
+    }
+
+    fn retain_runs(&mut self, runs: &mut Vec<Run>) {
+        runs.retain_mut(|r| self.keep_run(r));
+        for run in runs {
+            self.run(run);
+        }
+    }
+
+    fn blocks(&mut self, content: &mut Vec<BodyContent>) {
+        content.retain_mut(|c| match c {
+            BodyContent::Run(r) => self.keep_run(r),
+            _ => true,
+        });
+
+        for c in content {
+            match c {
+                BodyContent::Paragraph(p) => self.paragraph(p),
+                BodyContent::Table(t) => {
+                    for row in &mut t.rows {
+                        for cell in &mut row.cells {
+                            match cell {
+                                TableRowContent::TableCell(cell) => {
+                                    for TableCellContent::Paragraph(p) in &mut cell.content {
+                                        self.paragraph(p);
+                                    }
+                                }
+                                TableRowContent::SDT(sdt) => {
+                                    if let Some(content) = &mut sdt.content {
+                                        self.blocks(&mut content.content);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                BodyContent::Sdt(sdt) => {
+                    if let Some(content) = &mut sdt.content {
+                        self.blocks(&mut content.content);
+                    }
+                }
+                BodyContent::SectionProperty(section) => self.section(section),
+                BodyContent::Run(r) => self.run(r),
+                BodyContent::TableCell(cell) => {
+                    for TableCellContent::Paragraph(p) in &mut cell.content {
+                        self.paragraph(p);
+                    }
+                }
+                BodyContent::BookmarkStart(_)
+                | BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => {}
+            }
+        }
+    }
+
+    fn paragraph(&mut self, p: &mut Paragraph) {
+        self.rsids(&mut p.rsid_r, &mut p.rsid_r_default);
+
+        if let Some(property) = &mut p.property {
+            if let Some(change) = &mut property.p_pr_change {
+                self.revision(Some(&mut change.author), &mut change.date);
+            }
+            if let Some(numbering) = &mut property.numbering {
+                if let Some(change) = &mut numbering.numbering_change {
+                    self.revision(change.author.as_mut(), &mut change.date);
+                }
+                if let Some(ins) = &mut numbering.ins {
+                    self.revision(ins.author.as_mut(), &mut ins.date);
+                }
+            }
+            if let Some(section) = &mut property.section_property {
+                self.section(section);
+            }
+        }
+
+        p.content.retain_mut(|c| match c {
+            ParagraphContent::Run(r) => self.keep_run(r),
+            _ => true,
+        });
+
+        for c in &mut p.content {
+            match c {
+                ParagraphContent::Run(r) => self.run(r),
+                ParagraphContent::Link(link) => self.link(link),
+                ParagraphContent::SimpleField(field) => self.retain_runs(&mut field.runs),
+                ParagraphContent::SDT(sdt) => {
+                    if let Some(content) = &mut sdt.content {
+                        self.blocks(&mut content.content);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn link(&mut self, link: &mut Hyperlink) {
+        if link.content.as_mut().is_some_and(|r| !self.keep_run(r)) {
+            link.content = None;
+        }
+        if let Some(run) = &mut link.content {
+            self.run(run);
+        }
+        let mut levels: Vec<_> = link.bidirectional_embedding.iter_mut().collect();
+        while let Some(level) = levels.pop() {
+            self.retain_runs(&mut level.runs);
+            levels.extend(level.nested_levels.iter_mut());
+        }
+    }
+
+    fn run(&mut self, run: &mut Run) {
+        self.rsids(&mut run.rsid_r, &mut run.rsid_r_default);
+    }
+
+    fn section(&mut self, section: &mut SectionProperty) {
+        self.rsids(&mut section.rsid_r, &mut section.rsid_r_default);
+        if let Some(revision) = &mut section.revision {
+            self.revision(Some(&mut revision.author), &mut revision.date);
+            if let Some(previous) = &mut revision.section_property {
+                self.rsids(&mut previous.rsid_r, &mut previous.rsid_r_default);
+            }
+        }
+    }
+
+    fn document_properties(&mut self, core: Option<&mut Core>, app: Option<&mut App>) {
+        let mut removed = Vec::new();
+        if let Some(core) = core {
+            let (creator, last_modified_by) = match core {
+                Core::CoreNamespace(c) => (&mut c.creator, &mut c.last_modified_by),
+                Core::CoreNoNamespace(c) => (&mut c.creator, &mut c.last_modified_by),
+            };
+            removed.push(("creator", creator.take().is_some()));
+            removed.push(("lastModifiedBy", last_modified_by.take().is_some()));
+        }
+        if let Some(app) = app {
+            let (company, template) = match app {
+                App::AppNoApNamespace(a) => (&mut a.company, &mut a.template),
+                App::AppWithApNamespace(a) => (&mut a.company, &mut a.template),
+            };
+            removed.push(("company", company.take().is_some()));
+            removed.push(("template", template.take().is_some()));
+        }
+        self.report.properties.extend(
+            removed
+                .into_iter()
+                .filter(|(_, removed)| *removed)
+                .map(|(name, _)| name),
+        );
+    }
+}
+
+/// Returns the ids of the styles whose text is hidden, following the styles they
+/// are based on when they don't say.
+fn hidden_styles(styles: &Styles) -> HashSet<String> {
+    let by_id: HashMap<&str, _> = styles
+        .styles
+        .iter()
+        .map(|s| (s.style_id.as_ref(), s))
+        .collect();
+    let mut hidden = HashSet::new();
+    for style in &styles.styles {
+        let mut seen = HashSet::new();
+        let mut next = Some(style);
+        while let Some(current) = next.filter(|s| seen.insert(s.style_id.as_ref())) {
+            if let Some(vanish) = current.character.as_ref().and_then(|c| c.vanish.as_ref()) {
+                if vanish.value != Some(false) {
+                    hidden.insert(style.style_id.to_string());
+                }
+                break;
+            }
+            next = current
+                .base
+                .as_ref()
+                .and_then(|b| by_id.get(b.value.as_ref()).copied());
+        }
+    }
+    hidden
+}
+
+#[test]
+fn sanitize_test() {
+    use crate::{
+        document::{CharType, Comment, FieldChar},
+        formatting::{CharacterProperty, RevisionParagraphProperty, Vanish},
+        styles::{BasedOn, Style, StyleType},
+    };
+
+    let mut docx = Docx::default();
+    let mut paragraph = Paragraph::default().push_text("visible").push(
+        Run::default()
+            .property(CharacterProperty::default().vanish(Vanish::default()))
+            .push_text("secret"),
+    );
+    paragraph.rsid_r = Some("00A1B2C3".into());
+    paragraph.property = Some(Default::default());
+    paragraph.property.as_mut().unwrap().p_pr_change = Some(RevisionParagraphProperty {
+        author: "Jane Doe".into(),
+        date: Some("2024-03-01T10:00:00Z".into()),
+        ..Default::default()
+    });
+    docx.document.push(paragraph);
+    docx.comments
+        .get_or_insert_with(Default::default)
+        .comments
+        .push(Comment {
+            author: "Jane Doe".into(),
+            ..Default::default()
+        });
+
+    let report = docx.sanitize(&SanitizeOptions::default());
+    assert_eq!(report.hidden_runs, 1);
+    assert_eq!(report.rsids, 1);
+    assert_eq!(report.revision_authors, 1);
+    assert_eq!(report.revision_dates, 1);
+    assert_eq!(report.comment_authors, 1);
+    assert_eq!(docx.document.body.text(), "visible");
+    assert_eq!(docx.comments.as_ref().unwrap().comments[0].author, "Author");
+
+    assert!(docx.sanitize(&SanitizeOptions::default()).is_empty());
+
+    // hidden through a style, with a field separated in the hidden run
+    let mut docx = Docx::default();
+    docx.styles.push(
+        Style::new(StyleType::Character, "Hidden")
+            .character(CharacterProperty::default().vanish(Vanish::default())),
+    );
+    docx.styles.push(Style {
+        base: Some(BasedOn {
+            value: "Hidden".into(),
+        }),
+        ..Style::new(StyleType::Character, "Secret")
+    });
+    docx.document.push(
+        Paragraph::default()
+            .push(Run::default().push(FieldChar::from(CharType::Begin)))
+            .push(
+                Run::default()
+                    .property(CharacterProperty::default().style_id("Secret"))
+                    .push(FieldChar::from(CharType::Separate))
+                    .push_text("secret"),
+            )
+            .push(Run::default().push(FieldChar::from(CharType::End)))
+            .push(
+                Run::default()
+                    .property(CharacterProperty::default().style_id("Secret"))
+                    .push_text("also secret"),
+            )
+            .push(
+                Run::default()
+                    .property(
+                        CharacterProperty::default()
+                            .style_id("Secret")
+                            .vanish(Vanish { value: Some(false) }),
+                    )
+                    .push_text("shown"),
+            ),
+    );
+
+    let report = docx.sanitize(&SanitizeOptions::default());
+    assert_eq!(report.hidden_runs, 2);
+    assert_eq!(docx.document.body.text(), "shown");
+    let BodyContent::Paragraph(p) = &docx.document.body.content[0] else {
+        panic!("expected a paragraph");
+    };
+    let field_chars: Vec<_> = p
+        .content
+        .iter()
+        .filter_map(|c| match c {
+            ParagraphContent::Run(r) => Some(r),
+            _ => None,
+        })
+        .flat_map(|r| &r.content)
+        .filter_map(|c| match c {
+            RunContent::FieldChar(c) => c.ty.clone(),
+            _ => None,
+        })
+        .collect();
+    assert!(matches!(
+        field_chars[..],
+        [CharType::Begin, CharType::Separate, CharType::End]
+    ));
+    assert!(docx.sanitize(&SanitizeOptions::default()).is_empty());
+}
@@ -2,6 +2,9 @@
 
 pub const SCHEMA_XML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\r\n";
 pub const SCHEMA_DC: &str = "http://purl.org/dc/elements/1.1/";
+pub const SCHEMA_DC_TERMS: &str = "http://purl.org/dc/terms/";
+pub const SCHEMA_DCMI_TYPE: &str = "http://purl.org/dc/dcmitype/";
+pub const SCHEMA_XSI: &str = "http://www.w3.org/2001/XMLSchema-instance";
 pub const SCHEMA_CORE_2: &str =
     "http://schemas.openxmlformats.org/package/2006/metadata/core-properties";
 pub const SCHEMA_DOC_PROPS_V_TYPES: &str =
@@ -10,13 +13,17 @@ pub const SCHEMA_CONTENT_TYPES: &str =
     "http://schemas.openxmlformats.org/package/2006/content-types";
 pub const SCHEMA_MAIN: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
 pub const SCHEMA_WORDML_14: &str = "http://schemas.microsoft.com/office/word/2010/wordml";
+pub const SCHEMA_WORDML_15: &str = "http://schemas.microsoft.com/office/word/2012/wordml";
 pub const SCHEMA_DRAWINGML: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+pub const SCHEMA_PICTURE: &str = "http://schemas.openxmlformats.org/drawingml/2006/picture";
 pub const SCHEMA_WP: &str =
     "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
 pub const SCHEMA_RELATIONSHIPS_DOCUMENT: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
 pub const SCHEMAS_EXTENDED: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/extended-properties";
+pub const SCHEMAS_CUSTOM: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/custom-properties";
 
 // relationships
 pub const SCHEMA_RELATIONSHIPS: &str =
@@ -29,6 +36,12 @@ pub const SCHEMA_OFFICE_DOCUMENT: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
 pub const SCHEMA_CUSTOM: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/custom-properties";
+pub const SCHEMA_CUSTOM_XML: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml";
+pub const SCHEMA_CUSTOM_XML_PROPS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps";
+pub const SCHEMA_CUSTOM_XML_DATA_STORE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/customXml";
 pub const SCHEMA_FONT_TABLE: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/fontTable";
 pub const SCHEMA_STYLES: &str =
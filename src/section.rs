@@ -0,0 +1,201 @@
+//! Sections
+//!
+//! A section ends at a paragraph holding a `w:sectPr` in its properties, and the last
+//! one ends at the `w:sectPr` closing the body. [`Docx::sections`] lists the sections
+//! along with the blocks of [`Body::content`] they cover, and [`Docx::section_mut`]
+//! changes the page setup of one of them.
+//!
+//! ```rust
+//! use docx_rust::{document::*, formatting::*, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document
+//!     .push(Paragraph::default().push_text("Portrait"))
+//!     .push(Paragraph::default().push_text("Landscape"))
+//!     .push(SectionProperty::default().page_size(PageSize::new(Cm(21.0), Cm(29.7))));
+//!
+//! assert!(docx.insert_section_break(0, SectionType::NextPage));
+//! docx.section_mut(1)
+//!     .unwrap()
+//!     .set_orientation(PageOrientation::Landscape)
+//!     .set_columns(2, 720)
+//!     .restart_page_numbering(1);
+//!
+//! let sections = docx.sections();
+//! assert_eq!(sections.len(), 2);
+//! assert_eq!(sections[0].content, 0..1);
+//! assert_eq!(sections[1].property.page_size.as_ref().unwrap().weight, 16838);
+//! ```
+//!
+//! [`Docx::sections`]: ../struct.Docx.html#method.sections
+//! [`Docx::section_mut`]: ../struct.Docx.html#method.section_mut
+//! [`Body::content`]: ../document/struct.Body.html#structfield.content
+
+use std::ops::Range;
+
+use crate::{
+    document::{BodyContent, HeaderFooterReference, Paragraph},
+    formatting::{ParagraphProperty, SectionProperty, SectionType},
+    Docx,
+};
+
+/// A section of the body
+#[derive(Debug, Clone)]
+pub struct Section<'a> {
+    /// Blocks of [`Body::content`] in the section, including the paragraph ending it
+    ///
+    /// [`Body::content`]: ../document/struct.Body.html#structfield.content
+    pub content: Range<usize>,
+    /// Properties of the section, along with the header and footer references it
+    /// inherits from the sections before
+    pub property: SectionProperty<'a>,
+}
+
+/// Where the properties of a section are
+enum SectionEnd {
+    Paragraph(usize),
+    Body(usize),
+    /// The body ends without a `w:sectPr`
+    Missing,
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the sections of the body, in order.
+    pub fn sections(&self) -> Vec<Section<'a>> {
+        let content = &self.document.body.content;
+        let mut inherited: Vec<HeaderFooterReference<'a>> = Vec::new();
+        section_ends(content)
+            .into_iter()
+            .map(|(range, end)| {
+                let mut property = section_property(content, &end).cloned().unwrap_or_default();
+                for reference in &inherited {
+                    let kind = reference_kind(reference);
+                    let references = &mut property.header_footer_references;
+                    if !references.iter().any(|r| reference_kind(r) == kind) {
+                        references.push(reference.clone());
+                    }
+                }
+                inherited = property.header_footer_references.clone();
+                Section {
+                    content: range,
+                    property,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the properties a section holds, without the references it inherits.
+    ///
+    /// The last section gets a `w:sectPr` at the end of the body if it has none.
+    pub fn section_mut(&mut self, index: usize) -> Option<&mut SectionProperty<'a>> {
+        let (_, end) = section_ends(&self.document.body.content)
+            .into_iter()
+            .nth(index)?;
+        let content = &mut self.document.body.content;
+        let i = match end {
+            SectionEnd::Paragraph(i) | SectionEnd::Body(i) => i,
+            SectionEnd::Missing => {
+                content.push(SectionProperty::default().into());
+                content.len() - 1
+            }
+        };
+        match &mut content[i] {
+            BodyContent::Paragraph(p) => p.property.as_mut()?.section_property.as_mut(),
+            BodyContent::SectionProperty(property) => Some(property),
+            _ => None,
+        }
+    }
+
+    /// Ends a section after the block at `block` in the body, and starts the next one
+    /// as `ty`. Returns `false` if there is no such block, or a section already ends
+    /// there.
+    ///
+    /// The section before the break keeps the properties of the section it was part
+    /// of, including its header and footer references and where its page numbering
+    /// starts, while the section after the break continues the numbering.
+    pub fn insert_section_break(&mut self, block: usize, ty: SectionType) -> bool {
+        let ends = section_ends(&self.document.body.content);
+        let Some(index) = ends.iter().position(|(range, _)| range.contains(&block)) else {
+            return false;
+        };
+        if matches!(ends[index].1, SectionEnd::Paragraph(i) if i == block) {
+            return false;
+        }
+
+        let mut property = self.sections().swap_remove(index).property;
+        property.revision = None;
+        if let Some(next) = self.section_mut(index) {
+            next.ty = Some(ty.into());
+            if let Some(numbering) = &mut next.page_numbering {
+                numbering.start = None;
+            }
+        }
+
+        let content = &mut self.document.body.content;
+        match &mut content[block] {
+            BodyContent::Paragraph(p) => {
+                p.property
+                    .get_or_insert_with(Default::default)
+                    .section_property = Some(property);
+            }
+            _ => content.insert(
+                block + 1,
+                Paragraph::default()
+                    .property(ParagraphProperty::default().section_property(property))
+                    .into(),
+            ),
+        }
+        true
+    }
+}
+
+/// Returns the blocks of each section, and where its properties are.
+fn section_ends(content: &[BodyContent]) -> Vec<(Range<usize>, SectionEnd)> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    for (i, block) in content.iter().enumerate() {
+        match block {
+            BodyContent::Paragraph(p) if paragraph_section(p).is_some() => {
+                sections.push((start..i + 1, SectionEnd::Paragraph(i)));
+                start = i + 1;
+            }
+            BodyContent::SectionProperty(_) => {
+                sections.push((start..i, SectionEnd::Body(i)));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < content.len() || sections.is_empty() {
+        sections.push((start..content.len(), SectionEnd::Missing));
+    }
+    sections
+}
+
+fn paragraph_section<'b, 'a>(p: &'b Paragraph<'a>) -> Option<&'b SectionProperty<'a>> {
+    p.property.as_ref()?.section_property.as_ref()
+}
+
+fn section_property<'b, 'a>(
+    content: &'b [BodyContent<'a>],
+    end: &SectionEnd,
+) -> Option<&'b SectionProperty<'a>> {
+    let (SectionEnd::Paragraph(i) | SectionEnd::Body(i)) = end else {
+        return None;
+    };
+    match &content[*i] {
+        BodyContent::Paragraph(p) => paragraph_section(p),
+        BodyContent::SectionProperty(property) => Some(property),
+        _ => None,
+    }
+}
+
+/// Whether a reference is to a header, and the pages it applies to
+fn reference_kind(reference: &HeaderFooterReference) -> (bool, String) {
+    let (header, ty) = match reference {
+        HeaderFooterReference::Header(r) => (true, &r.ty),
+        HeaderFooterReference::Footer(r) => (false, &r.ty),
+    };
+    let ty = ty.as_ref().map(|ty| ty.to_string());
+    (header, ty.unwrap_or_else(|| "default".to_string()))
+}
@@ -314,10 +314,43 @@ pub struct Settings<'a> {
     pub list_separator: Option<ListSeparator<'a>>,
 }
 
+/// Password needed to save changes to the document
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:writeProtection")]
-pub struct WriteProtection {}
+pub struct WriteProtection {
+    /// Only recommends opening the document read-only.
+    #[xml(attr = "w:recommended")]
+    pub recommended: Option<bool>,
+    /// Cryptographic provider of the password hash, `rsaAES` for SHA-2 hashes
+    #[xml(attr = "w:cryptProviderType")]
+    pub crypt_provider_type: Option<String>,
+    #[xml(attr = "w:cryptAlgorithmClass")]
+    pub crypt_algorithm_class: Option<String>,
+    #[xml(attr = "w:cryptAlgorithmType")]
+    pub crypt_algorithm_type: Option<String>,
+    /// Hash algorithm of the password, 4 for SHA-1 and 14 for SHA-512
+    #[xml(attr = "w:cryptAlgorithmSid")]
+    pub crypt_algorithm_sid: Option<u32>,
+    /// Number of times the password was hashed again
+    #[xml(attr = "w:cryptSpinCount")]
+    pub crypt_spin_count: Option<u32>,
+    /// Base64 password hash
+    #[xml(attr = "w:hash")]
+    pub hash: Option<String>,
+    /// Base64 salt added to the password
+    #[xml(attr = "w:salt")]
+    pub salt: Option<String>,
+    /// Hash algorithm of the password, in the ISO form of the attributes
+    #[xml(attr = "w:algorithmName")]
+    pub algorithm_name: Option<String>,
+    #[xml(attr = "w:hashValue")]
+    pub hash_value: Option<String>,
+    #[xml(attr = "w:saltValue")]
+    pub salt_value: Option<String>,
+    #[xml(attr = "w:spinCount")]
+    pub spin_count: Option<u32>,
+}
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -680,10 +713,70 @@ pub struct DoNotTrackFormatting {
     pub val: Option<bool>,
 }
 
+/// Editing restrictions of the document
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:documentProtection")]
-pub struct DocumentProtection {}
+pub struct DocumentProtection {
+    /// What can still be edited
+    #[xml(attr = "w:edit")]
+    pub edit: Option<DocumentProtectionType>,
+    /// Restricts formatting to the unlocked styles.
+    #[xml(attr = "w:formatting")]
+    pub formatting: Option<bool>,
+    /// The restrictions are applied, instead of only being stored.
+    #[xml(attr = "w:enforcement")]
+    pub enforcement: Option<bool>,
+    /// Cryptographic provider of the password hash, `rsaAES` for SHA-2 hashes
+    #[xml(attr = "w:cryptProviderType")]
+    pub crypt_provider_type: Option<String>,
+    #[xml(attr = "w:cryptAlgorithmClass")]
+    pub crypt_algorithm_class: Option<String>,
+    #[xml(attr = "w:cryptAlgorithmType")]
+    pub crypt_algorithm_type: Option<String>,
+    /// Hash algorithm of the password, 4 for SHA-1 and 14 for SHA-512
+    #[xml(attr = "w:cryptAlgorithmSid")]
+    pub crypt_algorithm_sid: Option<u32>,
+    /// Number of times the password was hashed again
+    #[xml(attr = "w:cryptSpinCount")]
+    pub crypt_spin_count: Option<u32>,
+    /// Base64 password hash
+    #[xml(attr = "w:hash")]
+    pub hash: Option<String>,
+    /// Base64 salt added to the password
+    #[xml(attr = "w:salt")]
+    pub salt: Option<String>,
+    /// Hash algorithm of the password, in the ISO form of the attributes
+    #[xml(attr = "w:algorithmName")]
+    pub algorithm_name: Option<String>,
+    #[xml(attr = "w:hashValue")]
+    pub hash_value: Option<String>,
+    #[xml(attr = "w:saltValue")]
+    pub salt_value: Option<String>,
+    #[xml(attr = "w:spinCount")]
+    pub spin_count: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum DocumentProtectionType {
+    #[default]
+    None, //	No Editing Restrictions
+    ReadOnly,       //	Allow No Editing
+    Comments,       //	Allow Editing of Comments
+    TrackedChanges, //	Allow Editing With Revision Tracking
+    Forms,          //	Allow Editing of Form Fields
+}
+
+__string_enum! {
+    DocumentProtectionType {
+        None = "none",
+        ReadOnly = "readOnly",
+        Comments = "comments",
+        TrackedChanges = "trackedChanges",
+        Forms = "forms",
+    }
+}
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -990,7 +1083,7 @@ pub struct AlwaysMergeEmptyNamespace {
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:updateFields")]
 pub struct UpdateFields {
-    #[xml(attr = "w:value")]
+    #[xml(attr = "w:val")]
     pub val: Option<bool>,
 }
 
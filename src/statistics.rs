@@ -0,0 +1,171 @@
+//! Document statistics
+//!
+//! [`Docx::compute_statistics`] counts pages, words, characters, lines and
+//! paragraphs the way Word reports them in `docProps/app.xml`:
+//!
+//! - each CJK character counts as a word of its own,
+//! - the instructions of fields are left out, their results are counted,
+//! - footnotes, endnotes, headers, footers and list labels are left out.
+//!
+//! Pages and lines depend on the layout, which isn't known here, so they are
+//! estimated from the length of the paragraphs and the page breaks.
+//!
+//! ```rust
+//! use docx_rust::{document::Paragraph, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(Paragraph::default().push_text("Hello world, 你好"));
+//!
+//! let statistics = docx.compute_statistics();
+//! assert_eq!(statistics.words, 4);
+//! assert_eq!(statistics.characters, 13);
+//!
+//! // the statistics in app.xml are updated every time the document is written
+//! docx.refresh_statistics = true;
+//! ```
+//!
+//! [`Docx::compute_statistics`]: ../struct.Docx.html#method.compute_statistics
+
+use crate::{
+    text::{TableLayout, TextOptions},
+    Docx,
+};
+
+/// Characters on a line of a Letter page with one inch margins in 11pt Calibri
+const CHARS_PER_LINE: usize = 90;
+/// Lines on a Letter page with one inch margins in 11pt Calibri
+const LINES_PER_PAGE: usize = 46;
+
+/// Statistics of a document, as found in the extended properties
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// Estimated number of pages
+    pub pages: usize,
+    pub words: usize,
+    /// Number of characters, not counting whitespace
+    pub characters: usize,
+    /// Number of characters, counting spaces and tabs but not paragraph marks
+    pub characters_with_spaces: usize,
+    /// Estimated number of lines
+    pub lines: usize,
+    /// Number of paragraphs with some text
+    pub paragraphs: usize,
+}
+
+impl<'a> Docx<'a> {
+    /// Computes the statistics of the document.
+    pub fn compute_statistics(&self) -> Statistics {
+        let options = TextOptions::default()
+            .field_codes(false)
+            .field_results(true)
+            .tables(TableLayout::Tsv)
+            .list_labels(false);
+        self.compute_statistics_with(&options)
+    }
+
+    /// Computes the statistics of the text extracted with `options`.
+    ///
+    /// For example, footnotes are counted with `TextOptions::footnotes`.
+    pub fn compute_statistics_with(&self, options: &TextOptions) -> Statistics {
+        let extracted = self.extract_text(options);
+        let chars: Vec<char> = extracted.text.chars().collect();
+
+        let mut statistics = Statistics::default();
+        let mut pages = 0usize;
+        let mut page_lines = 0usize;
+        for range in &extracted.paragraphs {
+            let text = &chars[range.start..range.end];
+
+            if text.iter().any(|c| !c.is_whitespace()) {
+                statistics.paragraphs += 1;
+            }
+
+            let mut in_word = false;
+            for &c in text {
+                if c == '\t' || !c.is_control() {
+                    statistics.characters_with_spaces += 1;
+                }
+                if c.is_whitespace() || c.is_control() {
+                    in_word = false;
+                    continue;
+                }
+                statistics.characters += 1;
+                if is_cjk(c) {
+                    statistics.words += 1;
+                    in_word = false;
+                } else if !in_word {
+                    statistics.words += 1;
+                    in_word = true;
+                }
+            }
+
+            // lines wrap at breaks and at the width of the page
+            let mut width = 0usize;
+            for &c in text.iter().chain(Some(&'\n')) {
+                match c {
+                    '\n' | '\r' | '\u{b}' | '\u{c}' => {
+                        let lines = width.max(1).div_ceil(CHARS_PER_LINE);
+                        statistics.lines += lines;
+                        page_lines += lines;
+                        width = 0;
+                        if c == '\u{c}' {
+                            pages += 1;
+                            page_lines = 0;
+                        }
+                    }
+                    // full-width characters take up the room of two
+                    c if is_cjk(c) => width += 2,
+                    _ => width += 1,
+                }
+            }
+        }
+        statistics.pages = (pages + page_lines.div_ceil(LINES_PER_PAGE)).max(1);
+
+        statistics
+    }
+}
+
+/// Returns `true` for Chinese, Japanese and Korean characters, which are words of their own.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{11FF}'
+            | '\u{2E80}'..='\u{2FDF}'
+            | '\u{3040}'..='\u{30FF}'
+            | '\u{3100}'..='\u{31BF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FF66}'..='\u{FF9F}'
+            | '\u{20000}'..='\u{2FFFF}'
+    )
+}
+
+#[test]
+fn statistics_test() {
+    use crate::document::{Paragraph, Run, SimpleField};
+
+    let mut docx = Docx::default();
+    docx.document
+        .push(Paragraph::default().push_text("The quick  brown fox"))
+        .push(Paragraph::default())
+        .push(Paragraph::default().push_text("日本語のテキスト"))
+        .push(
+            Paragraph::default().push_text("Page ").push(
+                SimpleField::default()
+                    .instruction(" PAGE ")
+                    .push(Run::default().push_text("1")),
+            ),
+        )
+        .push(Paragraph::default().push_text("x".repeat(200)));
+
+    let statistics = docx.compute_statistics();
+    assert_eq!(statistics.words, 4 + 8 + 2 + 1);
+    assert_eq!(statistics.characters, 16 + 8 + 5 + 200);
+    assert_eq!(statistics.characters_with_spaces, 20 + 8 + 6 + 200);
+    assert_eq!(statistics.paragraphs, 4);
+    assert_eq!(statistics.lines, 1 + 1 + 1 + 1 + 3);
+    assert_eq!(statistics.pages, 1);
+}
@@ -0,0 +1,145 @@
+//! Streaming writer of packages
+//!
+//! [`DocxStreamWriter`] writes the content of the body as it's pushed, straight
+//! into the compressed `word/document.xml`, so the memory used doesn't grow
+//! with the length of the document. The other parts are taken from a
+//! [`Docx`](crate::Docx) and written when finishing.
+//!
+//! ```no_run
+//! use docx_rust::{
+//!     document::{Paragraph, Table, TableCell, TableRow},
+//!     Docx, DocxStreamWriter,
+//! };
+//! use std::fs::File;
+//!
+//! let file = File::create("report.docx").unwrap();
+//! let mut writer = DocxStreamWriter::new(Docx::default(), file).unwrap();
+//!
+//! writer.push(Paragraph::default().push_text("Transactions")).unwrap();
+//! writer.start_table(Table::default()).unwrap();
+//! for i in 0..100_000 {
+//!     writer
+//!         .push_row(TableRow::default().push_cell(TableCell::paragraph(
+//!             Paragraph::default().push_text(i.to_string()),
+//!         )))
+//!         .unwrap();
+//! }
+//! writer.end_table().unwrap();
+//! writer.finish(None).unwrap();
+//! ```
+
+use hard_xml::{XmlWrite, XmlWriter};
+use std::io::{Seek, Write};
+use std::mem;
+use zip::ZipWriter;
+
+use crate::{
+    document::{BodyContent, Document, Table, TableRow},
+    docx::zip_file_options,
+    error::DocxResult,
+    formatting::SectionProperty,
+    Docx,
+};
+
+/// Writes a package whose body is pushed piece by piece
+///
+/// The content already in the body of the `Docx` is written first.
+/// [`Docx::refresh_statistics`](crate::Docx::refresh_statistics) is ignored,
+/// since the body isn't kept.
+pub struct DocxStreamWriter<'a, W: Write + Seek> {
+    docx: Docx<'a>,
+    writer: XmlWriter<ZipWriter<W>>,
+    /// Whether the start of a table was written but not its end
+    in_table: bool,
+}
+
+impl<'a, W: Write + Seek> DocxStreamWriter<'a, W> {
+    /// Starts writing the main document part of `docx` into `writer`.
+    pub fn new(mut docx: Docx<'a>, writer: W) -> DocxResult<Self> {
+        let mut writer = XmlWriter::new(ZipWriter::new(writer));
+        writer
+            .inner
+            .start_file("word/document.xml", zip_file_options())?;
+
+        log::debug!("[Document] Started streaming.");
+        Document::write_start(&mut writer)?;
+        writer.write_element_start("w:body")?;
+        writer.write_element_end_open()?;
+
+        let content = mem::take(&mut docx.document.body.content);
+        let mut stream = DocxStreamWriter {
+            docx,
+            writer,
+            in_table: false,
+        };
+        for c in content {
+            stream.push(c)?;
+        }
+        Ok(stream)
+    }
+
+    /// Writes a paragraph, a table or any other content of the body,
+    /// ending the current table if any.
+    pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> DocxResult<&mut Self> {
+        self.end_table()?;
+        content.into().to_writer(&mut self.writer)?;
+        Ok(self)
+    }
+
+    /// Writes the properties, grid and rows of `table`, leaving it open
+    /// for [`push_row`](Self::push_row).
+    pub fn start_table(&mut self, table: Table<'a>) -> DocxResult<&mut Self> {
+        self.end_table()?;
+
+        let Table {
+            property,
+            grids,
+            rows,
+        } = table;
+        self.writer.write_element_start("w:tbl")?;
+        self.writer.write_element_end_open()?;
+        property.to_writer(&mut self.writer)?;
+        grids.to_writer(&mut self.writer)?;
+        self.in_table = true;
+
+        for row in rows {
+            self.push_row(row)?;
+        }
+        Ok(self)
+    }
+
+    /// Writes a row of the current table, starting a table with default
+    /// properties if there's none.
+    pub fn push_row<T: Into<TableRow<'a>>>(&mut self, row: T) -> DocxResult<&mut Self> {
+        if !self.in_table {
+            self.start_table(Table::default())?;
+        }
+        row.into().to_writer(&mut self.writer)?;
+        Ok(self)
+    }
+
+    /// Ends the current table, if any.
+    pub fn end_table(&mut self) -> DocxResult<&mut Self> {
+        if self.in_table {
+            self.writer.write_element_end_close("w:tbl")?;
+            self.in_table = false;
+        }
+        Ok(self)
+    }
+
+    /// Ends the body with the properties of its last section, writes the other
+    /// parts of the package and returns the underlying writer.
+    pub fn finish(mut self, section: Option<SectionProperty<'a>>) -> DocxResult<W> {
+        self.end_table()?;
+        if let Some(section) = section {
+            section.to_writer(&mut self.writer)?;
+        }
+        self.writer.write_element_end_close("w:body")?;
+        self.writer.write_element_end_close("w:document")?;
+        log::debug!("[Document] Finished streaming.");
+
+        self.docx.refresh_statistics = false;
+        self.docx.write_parts(&mut self.writer, false)?;
+        Ok(self.writer.inner.finish()?)
+    }
+}
@@ -0,0 +1,607 @@
+//! Plain text extraction
+//!
+//! [`Docx::to_text`] turns a document into plain text, with options for
+//! field codes, tables, list labels and the parts besides the main body.
+//! [`Docx::extract_text`] additionally reports where each paragraph ends up
+//! in the text, which is what search indexes need.
+//!
+//! ```no_run
+//! use docx_rust::{text::{TableLayout, TextOptions}, DocxFile};
+//!
+//! let docx = DocxFile::from_file("origin.docx").unwrap();
+//! let docx = docx.parse().unwrap();
+//!
+//! let options = TextOptions::default()
+//!     .tables(TableLayout::Grid)
+//!     .footnotes(true);
+//! println!("{}", docx.to_text(&options));
+//! ```
+//!
+//! [`Docx::to_text`]: ../struct.Docx.html#method.to_text
+//! [`Docx::extract_text`]: ../struct.Docx.html#method.extract_text
+
+use std::collections::HashMap;
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, BreakType, CharType, Hyperlink, Paragraph, ParagraphContent, Run, RunContent,
+        Table, TableCellContent, TableRowContent,
+    },
+    formatting::ParagraphProperty,
+    html::to_roman,
+    Docx,
+};
+
+/// Options of the text extraction
+///
+/// ```rust
+/// use docx_rust::text::{TableLayout, TextOptions};
+///
+/// let options = TextOptions::default()
+///     .field_codes(true)
+///     .field_results(false)
+///     .tables(TableLayout::Tsv)
+///     .paragraph_separator("\r\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextOptions {
+    /// Includes the instructions of fields, like `PAGE \* MERGEFORMAT`.
+    pub field_codes: bool,
+    /// Includes the current results of fields, like the page number.
+    pub field_results: bool,
+    /// How tables are rendered.
+    pub tables: TableLayout,
+    /// Includes the headers before the body.
+    pub headers: bool,
+    /// Includes the footers after the body.
+    pub footers: bool,
+    /// Includes the footnotes and endnotes after the body.
+    pub footnotes: bool,
+    /// Includes the comments after the body.
+    pub comments: bool,
+    /// Prefixes list items with their number or bullet.
+    pub list_labels: bool,
+    /// Inserted between paragraphs and table rows.
+    pub paragraph_separator: String,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        TextOptions {
+            field_codes: false,
+            field_results: true,
+            tables: TableLayout::Tsv,
+            headers: false,
+            footers: false,
+            footnotes: false,
+            comments: false,
+            list_labels: true,
+            paragraph_separator: "\n".to_string(),
+        }
+    }
+}
+
+impl TextOptions {
+    __setter!(field_codes: bool);
+    __setter!(field_results: bool);
+    __setter!(tables: TableLayout);
+    __setter!(headers: bool);
+    __setter!(footers: bool);
+    __setter!(footnotes: bool);
+    __setter!(comments: bool);
+    __setter!(list_labels: bool);
+    __setter!(paragraph_separator: String);
+}
+
+/// How tables are rendered as text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableLayout {
+    /// Leaves tables out.
+    Skip,
+    /// One line per row, cells separated by tabs.
+    Tsv,
+    /// Cells padded to aligned columns and framed by ASCII borders.
+    Grid,
+}
+
+/// The part of the document some text comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextSource {
+    Body,
+    Header,
+    Footer,
+    /// The footnote with this id
+    Footnote(isize),
+    /// The endnote with this id
+    Endnote(isize),
+    /// The comment with this id
+    Comment(isize),
+}
+
+/// Location of a paragraph, or of a table cell, in the extracted text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextRange {
+    /// Offset of the first character, counted in `char`s
+    pub start: usize,
+    /// Offset after the last character, counted in `char`s
+    pub end: usize,
+    pub source: TextSource,
+}
+
+/// Text of a document along with the location of its paragraphs
+#[derive(Debug, Clone, Default)]
+pub struct ExtractedText {
+    pub text: String,
+    pub paragraphs: Vec<TextRange>,
+}
+
+impl<'a> Docx<'a> {
+    /// Extracts the text of the document.
+    pub fn to_text(&self, options: &TextOptions) -> String {
+        self.extract_text(options).text
+    }
+
+    /// Extracts the text of the document along with the location of each paragraph.
+    ///
+    /// Table cells are reported as a whole, list labels are part of their paragraph.
+    pub fn extract_text(&self, options: &TextOptions) -> ExtractedText {
+        TextExtractor {
+            docx: self,
+            options,
+            source: TextSource::Body,
+            extracted: ExtractedText::default(),
+            chars: 0,
+            counters: HashMap::new(),
+            fields: Vec::new(),
+        }
+        .extract()
+    }
+}
+
+/// Returns the visible text of a paragraph, without its list label.
+pub(crate) fn paragraph_text(docx: &Docx, p: &Paragraph) -> String {
+    let options = TextOptions::default().list_labels(false);
+    TextExtractor {
+        docx,
+        options: &options,
+        source: TextSource::Body,
+        extracted: ExtractedText::default(),
+        chars: 0,
+        counters: HashMap::new(),
+        fields: Vec::new(),
+    }
+    .paragraph_text(p)
+}
+
+struct TextExtractor<'d, 'a> {
+    docx: &'d Docx<'a>,
+    options: &'d TextOptions,
+    source: TextSource,
+    extracted: ExtractedText,
+    /// Length of the extracted text in `char`s
+    chars: usize,
+    /// Last number of each list level, by numbering id
+    counters: HashMap<isize, [Option<usize>; 9]>,
+    /// Fields the current text is nested in, `true` while in their instructions
+    fields: Vec<bool>,
+}
+
+impl<'d, 'a> TextExtractor<'d, 'a> {
+    fn extract(mut self) -> ExtractedText {
+        let docx = self.docx;
+
+        if self.options.headers {
+            self.source = TextSource::Header;
+            for header in sorted_parts(&docx.headers) {
+                self.blocks(&header.content);
+            }
+        }
+
+        self.source = TextSource::Body;
+        self.blocks(&docx.document.body.content);
+
+        if self.options.footers {
+            self.source = TextSource::Footer;
+            for footer in sorted_parts(&docx.footers) {
+                self.blocks(&footer.content);
+            }
+        }
+
+        if self.options.footnotes {
+            let footnotes = docx.footnotes.iter().flat_map(|n| &n.content);
+            for note in footnotes.filter(|n| n.ty.is_none()) {
+                self.source = TextSource::Footnote(note.id.unwrap_or_default());
+                self.blocks(&note.content);
+            }
+            let endnotes = docx.endnotes.iter().flat_map(|n| &n.content);
+            for note in endnotes.filter(|n| n.ty.is_none()) {
+                self.source = TextSource::Endnote(note.id.unwrap_or_default());
+                self.blocks(&note.content);
+            }
+        }
+
+        if self.options.comments {
+            for comment in docx.comments.iter().flat_map(|c| &c.comments) {
+                self.source = TextSource::Comment(comment.id.unwrap_or_default());
+                self.paragraph(&comment.content);
+            }
+        }
+
+        self.extracted
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for c in content {
+            match c {
+                BodyContent::Paragraph(p) => self.paragraph(p),
+                BodyContent::Table(t) => self.table(t),
+                BodyContent::Sdt(sdt) => {
+                    if let Some(content) = &sdt.content {
+                        self.blocks(&content.content);
+                    }
+                }
+                BodyContent::Run(r) => {
+                    let mut text = String::new();
+                    self.run(r, &mut text);
+                    self.push_line(&text, &[(0, text.chars().count())]);
+                }
+                BodyContent::TableCell(cell) => {
+                    for TableCellContent::Paragraph(p) in &cell.content {
+                        self.paragraph(p);
+                    }
+                }
+                BodyContent::SectionProperty(_)
+                | BodyContent::BookmarkStart(_)
+                | BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => {}
+            }
+        }
+    }
+
+    fn paragraph(&mut self, p: &Paragraph) {
+        let text = self.paragraph_text(p);
+        self.push_line(&text, &[(0, text.chars().count())]);
+    }
+
+    fn paragraph_text(&mut self, p: &Paragraph) -> String {
+        let mut text = String::new();
+        if self.options.list_labels {
+            if let Some(label) = self.list_label(p.property.as_ref()) {
+                text.push_str(&label);
+                text.push(' ');
+            }
+        }
+        self.inlines(&p.content, &mut text);
+        text
+    }
+
+    fn inlines(&mut self, content: &[ParagraphContent], out: &mut String) {
+        for c in content {
+            match c {
+                ParagraphContent::Run(r) => self.run(r, out),
+                ParagraphContent::Link(link) => self.link(link, out),
+                ParagraphContent::SimpleField(field) => {
+                    self.fields.push(true);
+                    if self.field_code_visible() {
+                        out.push_str(&field.instruction);
+                    }
+                    self.fields.pop();
+                    self.fields.push(false);
+                    for r in &field.runs {
+                        self.run(r, out);
+                    }
+                    self.fields.pop();
+                }
+                ParagraphContent::SDT(sdt) => {
+                    for c in sdt.content.iter().flat_map(|c| &c.content) {
+                        match c {
+                            BodyContent::Run(r) => self.run(r, out),
+                            BodyContent::Paragraph(p) => self.inlines(&p.content, out),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn link(&mut self, link: &Hyperlink, out: &mut String) {
+        if let Some(r) = &link.content {
+            self.run(r, out);
+        }
+        let mut levels: Vec<_> = link.bidirectional_embedding.iter().collect();
+        while let Some(level) = levels.pop() {
+            for r in &level.runs {
+                self.run(r, out);
+            }
+            levels.extend(level.nested_levels.iter().rev());
+        }
+    }
+
+    fn run(&mut self, run: &Run, out: &mut String) {
+        for c in &run.content {
+            if let RunContent::FieldChar(field_char) = c {
+                match field_char.ty {
+                    Some(CharType::Begin) => self.fields.push(true),
+                    Some(CharType::Separate) => {
+                        if let Some(code) = self.fields.last_mut() {
+                            *code = false;
+                        }
+                    }
+                    Some(CharType::End) => {
+                        self.fields.pop();
+                    }
+                    None => {}
+                }
+                continue;
+            }
+
+            if let RunContent::InstrText(instr) = c {
+                if self.field_code_visible() {
+                    out.push_str(&instr.text);
+                }
+                continue;
+            }
+            if !self.field_result_visible() {
+                continue;
+            }
+
+            match c {
+                RunContent::Text(t) => out.push_str(&t.text),
+                RunContent::Tab(_) | RunContent::PTab(_) => out.push('\t'),
+                RunContent::Break(br) => match br.ty {
+                    Some(BreakType::Page) => out.push('\u{c}'),
+                    _ => out.push('\n'),
+                },
+                RunContent::CarriageReturn(_) => out.push('\n'),
+                RunContent::NoBreakHyphen(_) => out.push('\u{2011}'),
+                RunContent::SoftHyphen(_) => out.push('\u{ad}'),
+                RunContent::Sym(sym) => {
+                    if let Some(ch) = sym
+                        .char
+                        .as_deref()
+                        .and_then(|c| u32::from_str_radix(c, 16).ok())
+                        .and_then(char::from_u32)
+                    {
+                        out.push(ch);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Instructions are shown when the innermost field is in its instructions,
+    /// and the fields around it are shown as well.
+    fn field_code_visible(&self) -> bool {
+        match self.fields.split_last() {
+            Some((true, outer)) => {
+                self.options.field_codes
+                    && outer.iter().all(|code| *code || self.options.field_results)
+            }
+            _ => false,
+        }
+    }
+
+    fn field_result_visible(&self) -> bool {
+        self.fields
+            .iter()
+            .all(|code| !code && self.options.field_results)
+    }
+
+    fn table(&mut self, table: &Table) {
+        if self.options.tables == TableLayout::Skip {
+            return;
+        }
+
+        // cell texts on the grid, spanned columns are left empty
+        let mut rows: Vec<Vec<Option<String>>> = Vec::new();
+        for row in &table.rows {
+            let mut cells = Vec::new();
+            for cell in &row.cells {
+                let TableRowContent::TableCell(cell) = cell else {
+                    continue;
+                };
+                let mut paragraphs = Vec::new();
+                for TableCellContent::Paragraph(p) in &cell.content {
+                    paragraphs.push(self.paragraph_text(p));
+                }
+                let text = paragraphs
+                    .join(" ")
+                    .replace(['\t', '\n', '\r', '\u{c}'], " ");
+                cells.push(Some(text));
+                let span = cell.property.grid_span.as_ref().map_or(1, |s| s.value);
+                for _ in 1..span.max(1) {
+                    cells.push(None);
+                }
+            }
+            rows.push(cells);
+        }
+
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or_default();
+        match self.options.tables {
+            TableLayout::Tsv => {
+                for row in &rows {
+                    let mut line = String::new();
+                    let mut ranges = Vec::new();
+                    for (i, cell) in row.iter().enumerate() {
+                        if i > 0 {
+                            line.push('\t');
+                        }
+                        if let Some(cell) = cell {
+                            let start = line.chars().count();
+                            line.push_str(cell);
+                            ranges.push((start, start + cell.chars().count()));
+                        }
+                    }
+                    self.push_line(&line, &ranges);
+                }
+            }
+            TableLayout::Grid => {
+                let mut widths = vec![0; columns];
+                for row in &rows {
+                    for (i, cell) in row.iter().enumerate() {
+                        let len = cell.as_ref().map_or(0, |c| c.chars().count());
+                        widths[i] = widths[i].max(len);
+                    }
+                }
+                let border = widths.iter().fold(String::from("+"), |mut s, w| {
+                    s.push_str(&"-".repeat(w + 2));
+                    s.push('+');
+                    s
+                });
+
+                self.push_line(&border, &[]);
+                for row in &rows {
+                    let mut line = String::from("|");
+                    let mut ranges = Vec::new();
+                    for (i, width) in widths.iter().enumerate() {
+                        let cell = row.get(i).and_then(|c| c.as_deref());
+                        let len = cell.map_or(0, |c| c.chars().count());
+                        let start = line.chars().count() + 1;
+                        line.push(' ');
+                        if let Some(cell) = cell {
+                            line.push_str(cell);
+                            ranges.push((start, start + len));
+                        }
+                        line.push_str(&" ".repeat(width - len + 1));
+                        line.push('|');
+                    }
+                    self.push_line(&line, &ranges);
+                    self.push_line(&border, &[]);
+                }
+            }
+            TableLayout::Skip => {}
+        }
+    }
+
+    /// Appends a line of text, `ranges` are the `char` offsets of its paragraphs.
+    fn push_line(&mut self, line: &str, ranges: &[(usize, usize)]) {
+        if self.chars > 0 || !self.extracted.paragraphs.is_empty() {
+            self.extracted
+                .text
+                .push_str(&self.options.paragraph_separator);
+            self.chars += self.options.paragraph_separator.chars().count();
+        }
+        for (start, end) in ranges {
+            self.extracted.paragraphs.push(TextRange {
+                start: self.chars + start,
+                end: self.chars + end,
+                source: self.source.clone(),
+            });
+        }
+        self.extracted.text.push_str(line);
+        self.chars += line.chars().count();
+    }
+
+    /// Returns the label of the next item of a list, and advances its counter.
+    fn list_label(&mut self, property: Option<&ParagraphProperty>) -> Option<String> {
+        let numbering = property
+            .and_then(|p| p.numbering.as_ref())
+            .or_else(|| self.style_numbering(property))?;
+        let num_id = numbering.id.as_ref()?.value;
+        let level = numbering.level.as_ref().map_or(0, |l| l.value).clamp(0, 8) as usize;
+        let details = self.docx.numbering.as_ref()?.numbering_details(num_id)?;
+        let level_of = |i: usize| {
+            details
+                .levels
+                .iter()
+                .find(|l| l.i_level == Some(i as isize))
+        };
+        let start_of = |i: usize| {
+            level_of(i)
+                .and_then(|l| l.start.as_ref())
+                .and_then(|s| s.value)
+                .unwrap_or(1)
+                .max(0) as usize
+        };
+
+        let counters = self.counters.entry(num_id).or_insert([None; 9]);
+        counters[level] = Some(counters[level].map_or(start_of(level), |n| n + 1));
+        for counter in counters.iter_mut().skip(level + 1) {
+            *counter = None;
+        }
+        let counters = *counters;
+
+        let lvl = level_of(level)?;
+        let format = lvl.number_format.as_ref().map_or("decimal", |f| &f.value);
+        let text = lvl.level_text.as_ref().map_or("", |t| &t.value);
+        if format == "none" {
+            return None;
+        }
+        if format == "bullet" {
+            // symbol fonts put their glyphs into the private use area
+            let symbol = text.chars().any(|c| ('\u{f000}'..='\u{f0ff}').contains(&c));
+            return Some(if symbol || text.is_empty() {
+                "•".to_string()
+            } else {
+                text.to_string()
+            });
+        }
+
+        let mut label = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let Some(i) = chars
+                .peek()
+                .filter(|_| c == '%')
+                .and_then(|d| d.to_digit(10))
+                .filter(|d| (1..=9).contains(d))
+            else {
+                label.push(c);
+                continue;
+            };
+            chars.next();
+            let i = i as usize - 1;
+            let number = counters[i].unwrap_or_else(|| start_of(i));
+            let format = level_of(i)
+                .and_then(|l| l.number_format.as_ref())
+                .map_or("decimal", |f| &f.value);
+            label.push_str(&format_number(number, format));
+        }
+        (!label.is_empty()).then_some(label)
+    }
+
+    /// Numbering inherited from the paragraph style
+    fn style_numbering(
+        &self,
+        property: Option<&ParagraphProperty>,
+    ) -> Option<&'d crate::formatting::NumberingProperty<'a>> {
+        let styles = &self.docx.styles.styles;
+        let mut id = property?.style_id.as_ref()?.value.as_ref();
+        for _ in 0..16 {
+            let style = styles.iter().find(|s| s.style_id == id)?;
+            if let Some(numbering) = style.paragraph.as_ref().and_then(|p| p.numbering.as_ref()) {
+                return Some(numbering);
+            }
+            id = style.base.as_ref()?.value.as_ref();
+        }
+        None
+    }
+}
+
+/// Headers or footers in the order of their part names.
+fn sorted_parts<T>(parts: &HashMap<String, T>) -> Vec<&T> {
+    let mut names: Vec<_> = parts.keys().collect();
+    names.sort_by_key(|name| (name.len(), name.as_str()));
+    names.into_iter().map(|name| &parts[name]).collect()
+}
+
+pub(crate) fn format_number(n: usize, format: &str) -> String {
+    let letters = |n: usize| {
+        // a, b, ..., z, aa, bb, ...
+        let letter = (b'a' + ((n.max(1) - 1) % 26) as u8) as char;
+        letter.to_string().repeat((n.max(1) - 1) / 26 + 1)
+    };
+    match format {
+        "lowerLetter" => letters(n),
+        "upperLetter" => letters(n).to_uppercase(),
+        "lowerRoman" => to_roman(n),
+        "upperRoman" => to_roman(n).to_uppercase(),
+        "decimalZero" => format!("{:02}", n),
+        _ => n.to_string(),
+    }
+}
@@ -0,0 +1,409 @@
+//! Table of contents
+//!
+//! [`Docx::insert_toc`] adds a `TOC` field listing the headings of the document,
+//! inside a content control Word recognizes as table of contents. The headings get
+//! `_Toc` bookmarks the entries link to, and the document is marked to update its
+//! fields when opened, so that Word fills in the actual page numbers.
+//!
+//! ```rust
+//! use docx_rust::{document::*, formatting::*, toc::TocOptions, Docx};
+//!
+//! let mut docx = Docx::default();
+//! docx.document
+//!     .push(
+//!         Paragraph::default()
+//!             .property(ParagraphProperty::default().style_id("Heading1"))
+//!             .push_text("Introduction"),
+//!     )
+//!     .push(Paragraph::default().push_text("Lorem ipsum"));
+//!
+//! assert_eq!(docx.insert_toc(0, 1..=3, &TocOptions::default()), 1);
+//! ```
+//!
+//! [`Docx::insert_toc`]: ../struct.Docx.html#method.insert_toc
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::ops::RangeInclusive;
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, CharType, DocPartGallery, DocPartObj,
+        DocPartUnique, FieldChar, Hyperlink, InstrText, Paragraph, ParagraphContent, Run,
+        RunContent, SDTContent, SDTProperty, Tab, TableCellContent, TableRowContent, SDT,
+    },
+    field::{bookmark_pages, Field},
+    formatting::ParagraphProperty,
+    import::ensure_style,
+    settings::{Settings, UpdateFields},
+    styles::Styles,
+    text::paragraph_text,
+    Docx,
+};
+
+const GALLERY: &str = "Table of Contents";
+
+/// Options of [`Docx::insert_toc`]
+///
+/// [`Docx::insert_toc`]: ../struct.Docx.html#method.insert_toc
+#[derive(Debug, Clone)]
+pub struct TocOptions {
+    /// Title above the entries, `Contents` by default.
+    pub title: Option<String>,
+    /// Entries link to their headings (`\h` switch), `true` by default.
+    pub hyperlinks: bool,
+    /// Estimates page numbers from the page breaks in the document, instead of
+    /// leaving them blank until Word updates the field. `false` by default.
+    pub page_numbers: bool,
+}
+
+impl Default for TocOptions {
+    fn default() -> Self {
+        TocOptions {
+            title: Some("Contents".to_string()),
+            hyperlinks: true,
+            page_numbers: false,
+        }
+    }
+}
+
+impl TocOptions {
+    __setter!(title: Option<String>);
+    __setter!(hyperlinks: bool);
+    __setter!(page_numbers: bool);
+}
+
+struct Entry {
+    level: usize,
+    text: String,
+    bookmark: String,
+}
+
+impl<'a> Docx<'a> {
+    /// Inserts a table of contents before the body content at `position`, and returns
+    /// the number of entries.
+    ///
+    /// Paragraphs with an outline level in `levels` become entries, where level 1 are
+    /// paragraphs styled `heading 1` or with outline level 0. Styles `TOCHeading` and
+    /// `TOC1` to `TOC9` are added to the document if missing.
+    pub fn insert_toc(
+        &mut self,
+        position: usize,
+        levels: RangeInclusive<usize>,
+        options: &TocOptions,
+    ) -> usize {
+        let (sdt, count) = self.build_toc(levels, options);
+        let position = position.min(self.document.body.content.len());
+        self.document.body.content.insert(position, sdt.into());
+        count
+    }
+
+    /// Rebuilds the first table of contents of the document from the current headings,
+    /// keeping the levels of its `TOC` field, and returns the number of entries.
+    ///
+    /// Returns `None` if the document has no table of contents.
+    pub fn update_toc(&mut self, options: &TocOptions) -> Option<usize> {
+        let position = self
+            .document
+            .body
+            .content
+            .iter()
+            .position(|c| matches!(c, BodyContent::Sdt(sdt) if is_toc(sdt)))?;
+        let levels = match &self.document.body.content[position] {
+            BodyContent::Sdt(sdt) => toc_levels(sdt),
+            _ => 1..=3,
+        };
+        let (sdt, count) = self.build_toc(levels, options);
+        self.document.body.content[position] = sdt.into();
+        Some(count)
+    }
+
+    fn build_toc(
+        &mut self,
+        levels: RangeInclusive<usize>,
+        options: &TocOptions,
+    ) -> (SDT<'a>, usize) {
+        let levels = *levels.start().max(&1)..=*levels.end().min(&9);
+        // taken before the body is, so its bookmarks count too
+        let next_id = self.next_bookmark_id().parse().unwrap_or_default();
+        let mut body = mem::take(&mut self.document.body.content);
+        let entries = self.bookmark_headings(&mut body, &levels, next_id);
+        let pages = if options.page_numbers {
+            bookmark_pages(&body)
+        } else {
+            HashMap::new()
+        };
+        self.document.body.content = body;
+
+        let mut switches = format!(" TOC \\o \"{}-{}\"", levels.start(), levels.end());
+        if options.hyperlinks {
+            switches.push_str(" \\h");
+        }
+        switches.push_str(" \\z \\u ");
+
+        let mut content: Vec<BodyContent<'a>> = Vec::new();
+        if let Some(title) = &options.title {
+            ensure_style(self, "TOCHeading", "");
+            content.push(
+                Paragraph::default()
+                    .property(ParagraphProperty::default().style_id("TOCHeading"))
+                    .push_text(title.clone())
+                    .into(),
+            );
+        }
+
+        let begin = [
+            Run::default().push(FieldChar::from(CharType::Begin)),
+            Run::default().push(InstrText::from(switches)),
+            Run::default().push(FieldChar::from(CharType::Separate)),
+        ];
+        let mut paragraphs: Vec<Paragraph<'a>> = entries
+            .iter()
+            .map(|entry| {
+                let style = format!("TOC{}", entry.level);
+                ensure_style(self, &style, "");
+                let paragraph =
+                    Paragraph::default().property(ParagraphProperty::default().style_id(style));
+                let text = Run::default().push_text(entry.text.clone());
+                let paragraph = if options.hyperlinks {
+                    paragraph.push(
+                        Hyperlink::default()
+                            .anchor(entry.bookmark.clone())
+                            .content(text),
+                    )
+                } else {
+                    paragraph.push(text)
+                };
+                let page = pages
+                    .get(&entry.bookmark)
+                    .map(|p| p.to_string())
+                    .unwrap_or_default();
+                paragraph
+                    .push(Run::default().push(Tab))
+                    .push(Run::default().push(FieldChar::from(CharType::Begin)))
+                    .push(
+                        Run::default()
+                            .push(InstrText::from(format!(" PAGEREF {} \\h ", entry.bookmark))),
+                    )
+                    .push(Run::default().push(FieldChar::from(CharType::Separate)))
+                    .push(Run::default().push_text(page))
+                    .push(Run::default().push(FieldChar::from(CharType::End)))
+            })
+            .collect();
+        if paragraphs.is_empty() {
+            paragraphs.push(Paragraph::default().push_text("No table of contents entries found."));
+        }
+
+        if let Some(first) = paragraphs.first_mut() {
+            let mut runs: Vec<ParagraphContent> = begin.into_iter().map(Into::into).collect();
+            runs.append(&mut first.content);
+            first.content = runs;
+        }
+        if let Some(last) = paragraphs.last_mut() {
+            last.content
+                .push(Run::default().push(FieldChar::from(CharType::End)).into());
+        }
+        content.extend(paragraphs.into_iter().map(Into::into));
+
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .update_fields = Some(UpdateFields { val: Some(true) });
+
+        let sdt = SDT::default()
+            .property(SDTProperty::default().doc_part_obj(DocPartObj {
+                doc_part_gallery: Some(DocPartGallery {
+                    name: Some(GALLERY.into()),
+                }),
+                doc_part_unique: Some(DocPartUnique {}),
+                ..Default::default()
+            }))
+            .content(SDTContent { content });
+        (sdt, entries.len())
+    }
+
+    /// Adds a `_Toc` bookmark to each heading that doesn't have one yet.
+    ///
+    /// New bookmarks take their ids from `next_id` on, which
+    /// [`Docx::next_bookmark_id`] gives.
+    fn bookmark_headings(
+        &self,
+        body: &mut [BodyContent<'a>],
+        levels: &RangeInclusive<usize>,
+        mut next_id: usize,
+    ) -> Vec<Entry> {
+        let mut names = HashSet::new();
+        for_each_paragraph(body, &mut |p| {
+            for content in &p.content {
+                if let ParagraphContent::BookmarkStart(start) = content {
+                    names.extend(start.name.as_ref().map(|n| n.to_string()));
+                }
+            }
+        });
+        let mut next_name = names.len();
+
+        let mut entries = Vec::new();
+        for_each_paragraph(body, &mut |p| {
+            let Some(level) = heading_level(&self.styles, p).filter(|l| levels.contains(l)) else {
+                return;
+            };
+            let text = paragraph_text(self, p).trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+
+            let existing = p.content.iter().find_map(|c| match c {
+                ParagraphContent::BookmarkStart(start) => start
+                    .name
+                    .as_ref()
+                    .filter(|n| n.starts_with("_Toc"))
+                    .map(|n| n.to_string()),
+                _ => None,
+            });
+            let bookmark = existing.unwrap_or_else(|| {
+                let name = loop {
+                    next_name += 1;
+                    let name = format!("_Toc{}", 100000000 + next_name);
+                    if names.insert(name.clone()) {
+                        break name;
+                    }
+                };
+                let id = next_id.to_string();
+                next_id += 1;
+                p.content.insert(
+                    0,
+                    BookmarkStart::default()
+                        .id(id.clone())
+                        .name(name.clone())
+                        .into(),
+                );
+                p.content.push(BookmarkEnd::default().id(id).into());
+                name
+            });
+            entries.push(Entry {
+                level,
+                text,
+                bookmark,
+            });
+        });
+        entries
+    }
+}
+
+/// Visits the paragraphs of the body, except those in tables of contents.
+fn for_each_paragraph<'a, F: FnMut(&mut Paragraph<'a>)>(
+    content: &mut [BodyContent<'a>],
+    f: &mut F,
+) {
+    for c in content {
+        match c {
+            BodyContent::Paragraph(p) => f(p),
+            BodyContent::Table(t) => {
+                for row in &mut t.rows {
+                    for cell in &mut row.cells {
+                        if let TableRowContent::TableCell(cell) = cell {
+                            for TableCellContent::Paragraph(p) in &mut cell.content {
+                                f(p);
+                            }
+                        }
+                    }
+                }
+            }
+            BodyContent::Sdt(sdt) if !is_toc(sdt) => {
+                if let Some(content) = &mut sdt.content {
+                    for_each_paragraph(&mut content.content, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_toc(sdt: &SDT) -> bool {
+    sdt.property
+        .as_ref()
+        .and_then(|p| p.doc_part_obj.as_ref())
+        .and_then(|o| o.doc_part_gallery.as_ref())
+        .and_then(|g| g.name.as_deref())
+        == Some(GALLERY)
+}
+
+/// Reads the `\o` switch of the `TOC` field in a table of contents.
+fn toc_levels(sdt: &SDT) -> RangeInclusive<usize> {
+    let instruction: String = sdt
+        .content
+        .iter()
+        .flat_map(|c| &c.content)
+        .filter_map(|c| match c {
+            BodyContent::Paragraph(p) => Some(p),
+            _ => None,
+        })
+        .flat_map(|p| &p.content)
+        .filter_map(|c| match c {
+            ParagraphContent::Run(r) => Some(r),
+            _ => None,
+        })
+        .flat_map(|r| &r.content)
+        .skip_while(
+            |c| !matches!(c, RunContent::FieldChar(c) if matches!(c.ty, Some(CharType::Begin))),
+        )
+        .take_while(
+            |c| !matches!(c, RunContent::FieldChar(c) if matches!(c.ty, Some(CharType::Separate))),
+        )
+        .filter_map(|c| match c {
+            RunContent::InstrText(text) => Some(text.text.as_ref()),
+            _ => None,
+        })
+        .collect();
+    Field::new(instruction)
+        .switch("o")
+        .and_then(|range| range.split_once('-'))
+        .and_then(|(start, end)| Some(start.trim().parse().ok()?..=end.trim().parse().ok()?))
+        .unwrap_or(1..=3)
+}
+
+/// Returns the outline level of a paragraph, from 1 to 9.
+fn heading_level(styles: &Styles, p: &Paragraph) -> Option<usize> {
+    let to_level = |value: isize| {
+        usize::try_from(value)
+            .ok()
+            .filter(|l| *l < 9)
+            .map(|l| l + 1)
+    };
+    let property = p.property.as_ref();
+    if let Some(level) = property.and_then(|p| p.outline_lvl.as_ref()) {
+        return to_level(level.value);
+    }
+
+    let mut style_id = property
+        .and_then(|p| p.style_id.as_ref())
+        .map(|s| s.value.as_ref());
+    // based-on chains are short, the limit only guards against cycles
+    for _ in 0..16 {
+        let id = style_id?;
+        let Some(style) = styles.styles.iter().find(|s| s.style_id == id) else {
+            // documents without styles part still use the built-in style ids
+            return id
+                .strip_prefix("Heading")
+                .and_then(|l| l.parse::<usize>().ok())
+                .filter(|l| (1..=9).contains(l));
+        };
+        if let Some(level) = style
+            .paragraph
+            .as_ref()
+            .and_then(|p| p.outline_lvl.as_ref())
+        {
+            return to_level(level.value);
+        }
+        let name = style.name.as_ref().map(|n| n.value.to_ascii_lowercase());
+        if let Some(level) = name
+            .as_deref()
+            .and_then(|n| n.strip_prefix("heading "))
+            .and_then(|l| l.parse::<usize>().ok())
+        {
+            return Some(level).filter(|l| (1..=9).contains(l));
+        }
+        style_id = style.base.as_ref().map(|b| b.value.as_ref());
+    }
+    None
+}
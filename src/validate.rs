@@ -0,0 +1,907 @@
+//! Validation of the structure of documents
+//!
+//! [`Docx::validate`] looks for what Word rejects or silently drops when
+//! opening a document: references to missing relationships, media without a
+//! content type, duplicate ids, misordered properties, empty table cells,
+//! unbalanced fields and comment ranges, and undefined numberings and styles.
+//!
+//! The relationships of headers, footers, notes and comments aren't read, so
+//! the references made from these parts aren't checked.
+//!
+//! ```no_run
+//! use docx_rust::{validate::Severity, DocxFile};
+//!
+//! let docx = DocxFile::from_file("origin.docx").unwrap();
+//! let docx = docx.parse().unwrap();
+//!
+//! for finding in docx.validate() {
+//!     if finding.severity == Severity::Error {
+//!         println!("{}", finding);
+//!     }
+//! }
+//! ```
+//!
+//! [`Docx::validate`]: ../struct.Docx.html#method.validate
+
+use hard_xml::XmlWrite;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::{
+    document::{
+        BodyContent, BookmarkStart, CharType, Drawing, FieldChar, Hyperlink, Paragraph,
+        ParagraphContent, Run, RunContent, Table, TableCell, TableCellContent, TableRowContent,
+        SDT,
+    },
+    formatting::{CharacterProperty, ParagraphProperty, SectionProperty},
+    parse::{next_markup, Markup},
+    Docx,
+};
+
+/// Children of `w:pPr`, in the order of the schema
+const PARAGRAPH_PROPERTY_ORDER: &[&str] = &[
+    "w:pStyle",
+    "w:keepNext",
+    "w:keepLines",
+    "w:pageBreakBefore",
+    "w:framePr",
+    "w:widowControl",
+    "w:numPr",
+    "w:suppressLineNumbers",
+    "w:pBdr",
+    "w:shd",
+    "w:tabs",
+    "w:suppressAutoHyphens",
+    "w:kinsoku",
+    "w:wordWrap",
+    "w:overflowPunct",
+    "w:topLinePunct",
+    "w:autoSpaceDE",
+    "w:autoSpaceDN",
+    "w:bidi",
+    "w:adjustRightInd",
+    "w:snapToGrid",
+    "w:spacing",
+    "w:ind",
+    "w:contextualSpacing",
+    "w:mirrorIndents",
+    "w:suppressOverlap",
+    "w:jc",
+    "w:textDirection",
+    "w:textAlignment",
+    "w:textboxTightWrap",
+    "w:outlineLvl",
+    "w:divId",
+    "w:cnfStyle",
+    "w:rPr",
+    "w:sectPr",
+    "w:pPrChange",
+];
+
+/// Children of `w:rPr`, in the order of the schema
+const CHARACTER_PROPERTY_ORDER: &[&str] = &[
+    "w:rStyle",
+    "w:rFonts",
+    "w:b",
+    "w:bCs",
+    "w:i",
+    "w:iCs",
+    "w:caps",
+    "w:smallCaps",
+    "w:strike",
+    "w:dstrike",
+    "w:outline",
+    "w:shadow",
+    "w:emboss",
+    "w:imprint",
+    "w:noProof",
+    "w:snapToGrid",
+    "w:vanish",
+    "w:webHidden",
+    "w:color",
+    "w:spacing",
+    "w:w",
+    "w:kern",
+    "w:position",
+    "w:sz",
+    "w:szCs",
+    "w:highlight",
+    "w:u",
+    "w:effect",
+    "w:bdr",
+    "w:shd",
+    "w:fitText",
+    "w:vertAlign",
+    "w:rtl",
+    "w:cs",
+    "w:em",
+    "w:lang",
+    "w:eastAsianLayout",
+    "w:specVanish",
+    "w:oMath",
+    "w:rPrChange",
+];
+
+/// Children of `w:sectPr`, in the order of the schema
+///
+/// The elements of an entry separated by `|` can be repeated and mixed.
+const SECTION_PROPERTY_ORDER: &[&str] = &[
+    "w:headerReference|w:footerReference",
+    "w:footnotePr",
+    "w:endnotePr",
+    "w:type",
+    "w:pgSz",
+    "w:pgMar",
+    "w:paperSrc",
+    "w:pgBorders",
+    "w:lnNumType",
+    "w:pgNumType",
+    "w:cols",
+    "w:formProt",
+    "w:vAlign",
+    "w:noEndnote",
+    "w:titlePg",
+    "w:textDirection",
+    "w:bidi",
+    "w:rtlGutter",
+    "w:docGrid",
+    "w:printerSettings",
+    "w:sectPrChange",
+];
+
+/// How Word handles a [`Finding`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Word ignores the element or falls back to a default.
+    Warning,
+    /// Word reports unreadable content or refuses to open the document.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Where a [`Finding`] was made
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// Name of the part in the package, like `word/document.xml`
+    pub part: String,
+    /// Path of the element in the part, like `w:body/w:tbl[1]/w:tr[2]/w:tc[1]`,
+    /// the position counting the siblings of the same name from 1. Empty for
+    /// the part itself.
+    pub path: String,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.part)
+        } else {
+            write!(f, "{}, {}", self.part, self.path)
+        }
+    }
+}
+
+/// A problem reported by [`Docx::validate`](crate::Docx::validate)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub location: Location,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Checks the structure of the document, returning the problems found
+    /// part by part.
+    pub fn validate(&self) -> Vec<Finding> {
+        let mut validator = Validator::new(self);
+
+        validator.content_types();
+
+        validator.check_rels = true;
+        validator.part("word/document.xml", "w:body", |v| {
+            let content = &self.document.body.content;
+            v.blocks(content);
+            v.body_end(content);
+        });
+        validator.check_rels = false;
+
+        let mut headers: Vec<_> = self.headers.iter().collect();
+        headers.sort_by_key(|(name, _)| *name);
+        for (name, header) in headers {
+            validator.part(&format!("word/{}", name), "w:hdr", |v| {
+                v.blocks(&header.content)
+            });
+        }
+        let mut footers: Vec<_> = self.footers.iter().collect();
+        footers.sort_by_key(|(name, _)| *name);
+        for (name, footer) in footers {
+            validator.part(&format!("word/{}", name), "w:ftr", |v| {
+                v.blocks(&footer.content)
+            });
+        }
+
+        if let Some(footnotes) = &self.footnotes {
+            validator.part("word/footnotes.xml", "w:footnotes", |v| {
+                let mut siblings = Siblings::default();
+                for note in &footnotes.content {
+                    v.within(siblings.step("w:footnote"), |v| v.blocks(&note.content));
+                }
+            });
+        }
+        if let Some(endnotes) = &self.endnotes {
+            validator.part("word/endnotes.xml", "w:endnotes", |v| {
+                let mut siblings = Siblings::default();
+                for note in &endnotes.content {
+                    v.within(siblings.step("w:endnote"), |v| v.blocks(&note.content));
+                }
+            });
+        }
+        if let Some(comments) = &self.comments {
+            validator.part("word/comments.xml", "w:comments", |v| {
+                let mut ids = HashSet::new();
+                let mut siblings = Siblings::default();
+                for comment in &comments.comments {
+                    v.within(siblings.step("w:comment"), |v| {
+                        match comment.id {
+                            None => v.report(Severity::Error, "comment has no id".to_string()),
+                            Some(id) if !ids.insert(id) => v.report(
+                                Severity::Error,
+                                format!("comment id {} is already used", id),
+                            ),
+                            _ => {}
+                        }
+                        v.within("w:p[1]".to_string(), |v| v.paragraph(&comment.content));
+                    });
+                }
+            });
+        }
+        if let Some(numbering) = &self.numbering {
+            validator.part("word/numbering.xml", "w:numbering", |v| {
+                let mut siblings = Siblings::default();
+                for num in &numbering.numberings {
+                    v.within(siblings.step("w:num"), |v| {
+                        let id = num.abstract_num_id.as_ref().and_then(|id| id.value);
+                        let defined = numbering
+                            .abstract_numberings
+                            .iter()
+                            .any(|a| a.abstract_num_id.is_some() && a.abstract_num_id == id);
+                        if !defined {
+                            v.report(
+                                Severity::Warning,
+                                format!(
+                                    "abstract numbering {} isn't defined",
+                                    id.map(|id| id.to_string()).unwrap_or_default()
+                                ),
+                            );
+                        }
+                    });
+                }
+            });
+        }
+
+        validator.findings
+    }
+}
+
+/// Counts the siblings of each name to build the steps of paths
+#[derive(Default)]
+struct Siblings(Vec<(&'static str, usize)>);
+
+impl Siblings {
+    fn step(&mut self, name: &'static str) -> String {
+        let n = match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => {
+                *count += 1;
+                *count
+            }
+            None => {
+                self.0.push((name, 1));
+                1
+            }
+        };
+        format!("{}[{}]", name, n)
+    }
+}
+
+struct Validator<'d, 'a> {
+    docx: &'d Docx<'a>,
+    findings: Vec<Finding>,
+    part: String,
+    path: Vec<String>,
+    /// Whether the relationships of the current part are checked
+    check_rels: bool,
+    comment_ids: HashSet<String>,
+    num_ids: HashSet<isize>,
+    style_ids: HashSet<&'d str>,
+    doc_pr_ids: HashSet<isize>,
+    bookmark_names: HashSet<String>,
+    /// Bookmark ids of the current part
+    bookmark_ids: HashSet<String>,
+    /// Fields started in the current part, and whether they're separated
+    fields: Vec<bool>,
+    /// Comment ranges started in the current part
+    comment_ranges: Vec<String>,
+}
+
+impl<'d, 'a> Validator<'d, 'a> {
+    fn new(docx: &'d Docx<'a>) -> Self {
+        Validator {
+            docx,
+            findings: Vec::new(),
+            part: String::new(),
+            path: Vec::new(),
+            check_rels: false,
+            comment_ids: docx
+                .comments
+                .iter()
+                .flat_map(|c| &c.comments)
+                .filter_map(|c| c.id)
+                .map(|id| id.to_string())
+                .collect(),
+            num_ids: docx
+                .numbering
+                .iter()
+                .flat_map(|n| &n.numberings)
+                .filter_map(|n| n.num_id)
+                .collect(),
+            style_ids: docx.styles.styles.iter().map(|s| &*s.style_id).collect(),
+            doc_pr_ids: HashSet::new(),
+            bookmark_names: HashSet::new(),
+            bookmark_ids: HashSet::new(),
+            fields: Vec::new(),
+            comment_ranges: Vec::new(),
+        }
+    }
+
+    fn report(&mut self, severity: Severity, message: String) {
+        self.findings.push(Finding {
+            severity,
+            location: Location {
+                part: self.part.clone(),
+                path: self.path.join("/"),
+            },
+            message,
+        });
+    }
+
+    fn within<F: FnOnce(&mut Self)>(&mut self, step: String, f: F) {
+        self.path.push(step);
+        f(self);
+        self.path.pop();
+    }
+
+    fn part<F: FnOnce(&mut Self)>(&mut self, part: &str, root: &str, f: F) {
+        self.part = part.to_string();
+        self.path = vec![root.to_string()];
+        self.bookmark_ids.clear();
+
+        f(self);
+
+        for _ in 0..self.fields.len() {
+            self.report(Severity::Error, "field isn't ended".to_string());
+        }
+        self.fields.clear();
+        for id in std::mem::take(&mut self.comment_ranges) {
+            self.report(
+                Severity::Warning,
+                format!("comment range {} isn't ended", id),
+            );
+        }
+    }
+
+    /// Checks that every media has a content type, which isn't added when writing.
+    fn content_types(&mut self) {
+        let content_types = &self.docx.content_types;
+        let mut names: Vec<_> = self.docx.media.keys().collect();
+        names.sort();
+        for name in names {
+            let part = format!("/word/{}", name);
+            let ext = name
+                .rsplit_once('.')
+                .map(|(_, ext)| ext)
+                .unwrap_or_default();
+            let typed = content_types
+                .defaults
+                .iter()
+                .any(|d| d.ext.eq_ignore_ascii_case(ext))
+                || content_types.overrides.iter().any(|o| o.part == part);
+            if !typed {
+                self.part = part[1..].to_string();
+                self.path.clear();
+                self.report(
+                    Severity::Error,
+                    format!("no content type for extension `{}`", ext),
+                );
+            }
+        }
+    }
+
+    /// Returns the target of relationship `id`, reporting it if it's missing.
+    ///
+    /// Returns `None` as well when the relationships of the part aren't checked.
+    fn target(&mut self, id: &str) -> Option<&'d str> {
+        if !self.check_rels {
+            return None;
+        }
+        let docx = self.docx;
+        let target = docx
+            .document_rels
+            .as_ref()
+            .and_then(|rels| rels.get_target(id));
+        if target.is_none() {
+            self.report(
+                Severity::Error,
+                format!("relationship `{}` doesn't exist", id),
+            );
+        }
+        target.map(|target| target.trim_start_matches("/word/"))
+    }
+
+    fn style(&mut self, id: &str) {
+        if !self.style_ids.contains(id) {
+            self.report(Severity::Warning, format!("style `{}` isn't defined", id));
+        }
+    }
+
+    /// Checks that the children of `element` are in the order of the schema.
+    fn order<T: XmlWrite>(&mut self, element: &T, order: &[&str]) {
+        let Ok(xml) = element.to_string() else {
+            return;
+        };
+        let mut previous: Option<usize> = None;
+        for name in child_names(&xml) {
+            let Some(pos) = order.iter().position(|e| e.split('|').any(|n| n == name)) else {
+                continue;
+            };
+            match previous {
+                Some(p) if pos < p => {
+                    self.report(Severity::Error, format!("`{}` is out of order", name));
+                }
+                Some(p) if pos == p && !order[pos].contains('|') => {
+                    self.report(Severity::Error, format!("`{}` is repeated", name));
+                }
+                _ => {}
+            }
+            previous = Some(previous.map_or(pos, |p| p.max(pos)));
+        }
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        let mut siblings = Siblings::default();
+        for c in content {
+            match c {
+                BodyContent::Paragraph(p) => self.within(siblings.step("w:p"), |v| v.paragraph(p)),
+                BodyContent::Table(t) => self.within(siblings.step("w:tbl"), |v| v.table(t)),
+                BodyContent::Sdt(sdt) => self.within(siblings.step("w:sdt"), |v| v.sdt(sdt)),
+                BodyContent::SectionProperty(section) => {
+                    self.within(siblings.step("w:sectPr"), |v| v.section(section))
+                }
+                BodyContent::TableCell(cell) => {
+                    self.within(siblings.step("w:tc"), |v| v.cell(cell))
+                }
+                BodyContent::Run(r) => self.within(siblings.step("w:r"), |v| v.run(r)),
+                BodyContent::BookmarkStart(b) => {
+                    self.within(siblings.step("w:bookmarkStart"), |v| v.bookmark(b))
+                }
+                BodyContent::BookmarkEnd(_)
+                | BodyContent::PermStart(_)
+                | BodyContent::PermEnd(_) => {}
+            }
+        }
+    }
+
+    /// Checks that the body ends with a paragraph and the properties of the last section.
+    fn body_end(&mut self, content: &[BodyContent]) {
+        if !matches!(content.last(), Some(BodyContent::SectionProperty(_))) {
+            self.report(
+                Severity::Warning,
+                "body doesn't end with section properties".to_string(),
+            );
+        }
+        let last = content
+            .iter()
+            .rev()
+            .find(|c| !matches!(c, BodyContent::SectionProperty(_)));
+        if matches!(last, Some(BodyContent::Table(_))) {
+            self.report(
+                Severity::Warning,
+                "body ends with a table instead of a paragraph".to_string(),
+            );
+        }
+    }
+
+    fn sdt(&mut self, sdt: &SDT) {
+        if let Some(content) = &sdt.content {
+            self.within("w:sdtContent".to_string(), |v| v.blocks(&content.content));
+        }
+    }
+
+    fn table(&mut self, table: &Table) {
+        if let Some(style) = &table.property.style_id {
+            self.within("w:tblPr".to_string(), |v| v.style(&style.value));
+        }
+        if table.rows.is_empty() {
+            self.report(Severity::Error, "table has no rows".to_string());
+        }
+
+        let mut rows = Siblings::default();
+        for row in &table.rows {
+            self.within(rows.step("w:tr"), |v| {
+                if row.cells.is_empty() {
+                    v.report(Severity::Error, "table row has no cells".to_string());
+                }
+                let mut cells = Siblings::default();
+                for cell in &row.cells {
+                    match cell {
+                        TableRowContent::TableCell(cell) => {
+                            v.within(cells.step("w:tc"), |v| v.cell(cell))
+                        }
+                        TableRowContent::SDT(sdt) => v.within(cells.step("w:sdt"), |v| v.sdt(sdt)),
+                    }
+                }
+            });
+        }
+    }
+
+    fn cell(&mut self, cell: &TableCell) {
+        if cell.content.is_empty() {
+            self.report(Severity::Error, "table cell has no paragraph".to_string());
+        }
+        let mut siblings = Siblings::default();
+        for TableCellContent::Paragraph(p) in &cell.content {
+            self.within(siblings.step("w:p"), |v| v.paragraph(p));
+        }
+    }
+
+    fn paragraph(&mut self, p: &Paragraph) {
+        if let Some(property) = &p.property {
+            self.within("w:pPr".to_string(), |v| v.paragraph_property(property));
+        }
+
+        let mut siblings = Siblings::default();
+        for c in &p.content {
+            match c {
+                ParagraphContent::Run(r) => self.within(siblings.step("w:r"), |v| v.run(r)),
+                ParagraphContent::Link(link) => {
+                    self.within(siblings.step("w:hyperlink"), |v| v.link(link))
+                }
+                ParagraphContent::CommentRangeStart(range) => self
+                    .within(siblings.step("w:commentRangeStart"), |v| {
+                        v.comment_range_start(&range.id)
+                    }),
+                ParagraphContent::CommentRangeEnd(range) => self
+                    .within(siblings.step("w:commentRangeEnd"), |v| {
+                        v.comment_range_end(&range.id)
+                    }),
+                ParagraphContent::BookmarkStart(b) => {
+                    self.within(siblings.step("w:bookmarkStart"), |v| v.bookmark(b))
+                }
+                ParagraphContent::SDT(sdt) => self.within(siblings.step("w:sdt"), |v| v.sdt(sdt)),
+                ParagraphContent::SimpleField(field) => {
+                    self.within(siblings.step("w:fldSimple"), |v| v.runs(&field.runs))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn paragraph_property(&mut self, property: &ParagraphProperty) {
+        self.order(property, PARAGRAPH_PROPERTY_ORDER);
+        if let Some(style) = &property.style_id {
+            self.style(&style.value);
+        }
+        if let Some(id) = property.numbering.as_ref().and_then(|n| n.id.as_ref()) {
+            if id.value != 0 && !self.num_ids.contains(&id.value) {
+                self.report(
+                    Severity::Warning,
+                    format!("numbering {} isn't defined", id.value),
+                );
+            }
+        }
+        if let Some(section) = &property.section_property {
+            self.within("w:sectPr".to_string(), |v| v.section(section));
+        }
+    }
+
+    fn section(&mut self, section: &SectionProperty) {
+        self.order(section, SECTION_PROPERTY_ORDER);
+
+        for reference in &section.header_footer_references {
+            let (name, id, parts) = match reference {
+                crate::document::HeaderFooterReference::Header(r) => (
+                    "w:headerReference",
+                    &r.id,
+                    self.docx.headers.keys().collect::<Vec<_>>(),
+                ),
+                crate::document::HeaderFooterReference::Footer(r) => (
+                    "w:footerReference",
+                    &r.id,
+                    self.docx.footers.keys().collect(),
+                ),
+            };
+            let Some(id) = id else {
+                self.report(
+                    Severity::Error,
+                    format!("`{}` has no relationship id", name),
+                );
+                continue;
+            };
+            if let Some(target) = self.target(id) {
+                if !parts.iter().any(|part| *part == target) {
+                    self.report(
+                        Severity::Error,
+                        format!("relationship `{}` targets missing part `{}`", id, target),
+                    );
+                }
+            }
+        }
+    }
+
+    fn runs(&mut self, runs: &[Run]) {
+        let mut siblings = Siblings::default();
+        for run in runs {
+            self.within(siblings.step("w:r"), |v| v.run(run));
+        }
+    }
+
+    fn link(&mut self, link: &Hyperlink) {
+        if let Some(id) = &link.id {
+            self.target(id);
+        }
+        if let Some(run) = &link.content {
+            self.within("w:r[1]".to_string(), |v| v.run(run));
+        }
+        if let Some(level) = &link.bidirectional_embedding {
+            self.within("w:dir[1]".to_string(), |v| {
+                let mut levels = vec![level];
+                while let Some(level) = levels.pop() {
+                    v.runs(&level.runs);
+                    levels.extend(level.nested_levels.iter());
+                }
+            });
+        }
+    }
+
+    fn run(&mut self, run: &Run) {
+        if let Some(property) = &run.property {
+            self.within("w:rPr".to_string(), |v| v.character_property(property));
+        }
+
+        let mut siblings = Siblings::default();
+        for c in &run.content {
+            match c {
+                RunContent::FieldChar(c) => {
+                    self.within(siblings.step("w:fldChar"), |v| v.field_char(c))
+                }
+                RunContent::CommentReference(reference) => {
+                    self.within(siblings.step("w:commentReference"), |v| {
+                        match &reference.id {
+                            None => {
+                                v.report(Severity::Error, "comment reference has no id".to_string())
+                            }
+                            Some(id) if !v.comment_ids.contains(&**id) => {
+                                v.report(Severity::Error, format!("comment {} doesn't exist", id))
+                            }
+                            _ => {}
+                        }
+                    })
+                }
+                RunContent::Drawing(drawing) => {
+                    self.within(siblings.step("w:drawing"), |v| v.drawing(drawing))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn character_property(&mut self, property: &CharacterProperty) {
+        self.order(property, CHARACTER_PROPERTY_ORDER);
+        if let Some(style) = &property.style_id {
+            self.style(&style.value);
+        }
+    }
+
+    fn field_char(&mut self, c: &FieldChar) {
+        match c.ty {
+            Some(CharType::Begin) => self.fields.push(false),
+            Some(CharType::Separate) => match self.fields.last_mut() {
+                Some(separated) if !*separated => *separated = true,
+                Some(_) => self.report(Severity::Error, "field is already separated".to_string()),
+                None => self.report(
+                    Severity::Error,
+                    "field separator outside of a field".to_string(),
+                ),
+            },
+            Some(CharType::End) => {
+                if self.fields.pop().is_none() {
+                    self.report(Severity::Error, "field end outside of a field".to_string());
+                }
+            }
+            None => self.report(Severity::Error, "field character has no type".to_string()),
+        }
+    }
+
+    fn comment_range_start(&mut self, id: &str) {
+        if !self.comment_ids.contains(id) {
+            self.report(Severity::Warning, format!("comment {} doesn't exist", id));
+        }
+        if self.comment_ranges.iter().any(|open| open == id) {
+            self.report(
+                Severity::Warning,
+                format!("comment range {} is already started", id),
+            );
+        } else {
+            self.comment_ranges.push(id.to_string());
+        }
+    }
+
+    fn comment_range_end(&mut self, id: &str) {
+        match self.comment_ranges.iter().position(|open| open == id) {
+            Some(pos) => {
+                self.comment_ranges.remove(pos);
+            }
+            None => self.report(
+                Severity::Warning,
+                format!("comment range {} isn't started", id),
+            ),
+        }
+    }
+
+    fn bookmark(&mut self, bookmark: &BookmarkStart) {
+        if let Some(id) = &bookmark.id {
+            if !self.bookmark_ids.insert(id.to_string()) {
+                self.report(
+                    Severity::Error,
+                    format!("bookmark id {} is already used", id),
+                );
+            }
+        }
+        if let Some(name) = &bookmark.name {
+            if !self.bookmark_names.insert(name.to_string()) {
+                self.report(
+                    Severity::Warning,
+                    format!("bookmark name `{}` is already used", name),
+                );
+            }
+        }
+    }
+
+    fn drawing(&mut self, drawing: &Drawing) {
+        let frames = drawing
+            .anchor
+            .iter()
+            .map(|a| ("wp:anchor", &a.doc_property, &a.graphic))
+            .chain(
+                drawing
+                    .inline
+                    .iter()
+                    .map(|i| ("wp:inline", &i.doc_property, &i.graphic)),
+            );
+
+        for (name, doc_property, graphic) in frames {
+            self.within(name.to_string(), |v| {
+                v.within("wp:docPr".to_string(), |v| match doc_property.id {
+                    None => v.report(Severity::Error, "drawing has no id".to_string()),
+                    Some(id) if !v.doc_pr_ids.insert(id) => v.report(
+                        Severity::Error,
+                        format!("drawing id {} is already used", id),
+                    ),
+                    _ => {}
+                });
+
+                let mut siblings = Siblings::default();
+                for picture in graphic.iter().flat_map(|g| &g.data.children) {
+                    let step = format!(
+                        "a:graphic/a:graphicData/{}/pic:blipFill/a:blip",
+                        siblings.step("pic:pic")
+                    );
+                    v.within(step, |v| {
+                        let id = &picture.fill.blip.embed;
+                        if let Some(target) = v.target(id) {
+                            if !v.docx.media.contains_key(target) {
+                                v.report(
+                                    Severity::Error,
+                                    format!(
+                                        "relationship `{}` targets missing part `{}`",
+                                        id, target
+                                    ),
+                                );
+                            }
+                        }
+                    });
+                }
+            });
+        }
+    }
+}
+
+/// Returns the names of the children of the root element of `xml`.
+fn child_names(xml: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut depth = 0usize;
+    let mut pos = 0;
+
+    while let Some(offset) = xml[pos..].find('<') {
+        let Some((markup, end)) = next_markup(xml, pos + offset) else {
+            break;
+        };
+        pos = end;
+        match markup {
+            Markup::Start(name) => {
+                if depth == 1 {
+                    names.push(name);
+                }
+                depth += 1;
+            }
+            Markup::Empty(name) => {
+                if depth == 1 {
+                    names.push(name);
+                }
+            }
+            Markup::End => depth = depth.saturating_sub(1),
+            Markup::Other => {}
+        }
+    }
+
+    names
+}
+
+#[test]
+fn validate_test() {
+    use crate::{
+        document::{CommentRangeStart, TableRow},
+        formatting::{NumberingId, NumberingProperty},
+    };
+
+    let mut docx = Docx::default();
+    docx.document.push(
+        Paragraph::default()
+            .property(ParagraphProperty::default().numbering(NumberingProperty {
+                id: Some(NumberingId { value: 7 }),
+                ..Default::default()
+            }))
+            .push(CommentRangeStart::default().id("3"))
+            .push(Run::default().push(FieldChar::from(CharType::Begin)))
+            .push(Run::default().push(Drawing::inline_picture(1, "a", "rId9", 10i64, 10i64)))
+            .push(Run::default().push(Drawing::inline_picture(1, "b", "rId9", 10i64, 10i64))),
+    );
+    docx.document
+        .push(Table::default().push_row(TableRow::default().push_cell(TableCell::default())));
+
+    let findings: Vec<_> = docx.validate().iter().map(|f| f.to_string()).collect();
+    assert_eq!(
+        findings,
+        [
+            "warning: word/document.xml, w:body/w:p[1]/w:pPr: numbering 7 isn't defined",
+            "warning: word/document.xml, w:body/w:p[1]/w:commentRangeStart[1]: comment 3 doesn't exist",
+            "error: word/document.xml, w:body/w:p[1]/w:r[2]/w:drawing[1]/wp:inline/a:graphic/a:graphicData/pic:pic[1]/pic:blipFill/a:blip: relationship `rId9` doesn't exist",
+            "error: word/document.xml, w:body/w:p[1]/w:r[3]/w:drawing[1]/wp:inline/wp:docPr: drawing id 1 is already used",
+            "error: word/document.xml, w:body/w:p[1]/w:r[3]/w:drawing[1]/wp:inline/a:graphic/a:graphicData/pic:pic[1]/pic:blipFill/a:blip: relationship `rId9` doesn't exist",
+            "error: word/document.xml, w:body/w:tbl[1]/w:tr[1]/w:tc[1]: table cell has no paragraph",
+            "warning: word/document.xml, w:body: body doesn't end with section properties",
+            "warning: word/document.xml, w:body: body ends with a table instead of a paragraph",
+            "error: word/document.xml, w:body: field isn't ended",
+            "warning: word/document.xml, w:body: comment range 3 isn't ended",
+        ]
+    );
+
+    assert_eq!(
+        child_names(
+            r#"<w:sectPr a="<"><w:pgSz/><w:cols><w:col/></w:cols><!-- <w:x/> --></w:sectPr>"#
+        ),
+        ["w:pgSz", "w:cols"]
+    );
+}
@@ -110,7 +110,7 @@ fn regular_namespace() {
         <w:doNotSaveAsSingleFile />
     </w:webSettings>
     "#;
-    let web_settings = WebSettings::from_str(&alt_web_settings).unwrap();
+    let web_settings = WebSettings::from_str(alt_web_settings).unwrap();
     assert_eq!(web_settings.allow_png, Some(AllowPNG {}));
     assert_eq!(
         web_settings.do_not_save_as_single_file,